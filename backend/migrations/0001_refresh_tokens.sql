@@ -0,0 +1,14 @@
+-- Ротируемые refresh-токены. Хранится только SHA-256 хэш токена.
+-- Все токены, выданные в рамках одного входа, объединены в семейство (family_id).
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id          SERIAL PRIMARY KEY,
+    user_id     INT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    family_id   TEXT NOT NULL,
+    token_hash  TEXT NOT NULL UNIQUE,
+    expires_at  TIMESTAMP NOT NULL,
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    rotated_at  TIMESTAMP,
+    revoked_at  TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family ON refresh_tokens (family_id);
@@ -0,0 +1,3 @@
+-- Регистрация полагается на уникальность username при вставке,
+-- а не на предварительную проверку SELECT.
+CREATE UNIQUE INDEX IF NOT EXISTS users_username_key ON users (username);
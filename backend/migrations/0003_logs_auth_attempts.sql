@@ -0,0 +1,7 @@
+-- Попытки входа пишутся в logs; для подсчёта неудачных попыток
+-- по имени пользователя и по IP нужны отдельные индексируемые поля.
+ALTER TABLE logs ADD COLUMN IF NOT EXISTS username VARCHAR(50);
+ALTER TABLE logs ADD COLUMN IF NOT EXISTS ip_address VARCHAR(45);
+
+CREATE INDEX IF NOT EXISTS idx_logs_action_username ON logs (action, username, logged_at);
+CREATE INDEX IF NOT EXISTS idx_logs_action_ip ON logs (action, ip_address, logged_at);
@@ -0,0 +1,30 @@
+-- Права ролей. Набор допустимых прав задаётся в коде
+-- (controllers/auth/permissions.rs).
+CREATE UNIQUE INDEX IF NOT EXISTS roles_role_name_key ON roles (role_name);
+
+CREATE TABLE IF NOT EXISTS role_permissions (
+    role_id     INT NOT NULL REFERENCES roles(id) ON DELETE CASCADE,
+    permission  TEXT NOT NULL,
+    PRIMARY KEY (role_id, permission)
+);
+
+INSERT INTO roles (role_name)
+SELECT r FROM (VALUES ('оператор'), ('администратор')) AS v(r)
+WHERE NOT EXISTS (SELECT 1 FROM roles WHERE role_name = v.r);
+
+INSERT INTO role_permissions (role_id, permission)
+SELECT r.id, p.permission
+FROM roles r
+JOIN (VALUES
+    ('оператор', 'telemetry:read'),
+    ('оператор', 'telemetry:write'),
+    ('оператор', 'thresholds:read'),
+    ('администратор', 'telemetry:read'),
+    ('администратор', 'telemetry:write'),
+    ('администратор', 'thresholds:read'),
+    ('администратор', 'thresholds:write'),
+    ('администратор', 'devices:manage'),
+    ('администратор', 'users:manage'),
+    ('администратор', 'roles:manage')
+) AS p(role_name, permission) ON p.role_name = r.role_name
+ON CONFLICT DO NOTHING;
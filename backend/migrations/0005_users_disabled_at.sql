@@ -0,0 +1,3 @@
+-- Отключённые учётные записи: вход и проверка токена отклоняются,
+-- пока disabled_at не сброшен администратором.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS disabled_at TIMESTAMP;
@@ -0,0 +1,12 @@
+-- Метрики, которые отправляет генератор телеметрии. Остальные
+-- регистрируются вручную или автоматически (METRIC_AUTO_REGISTER=true).
+-- Сид и регистрация метрик полагаются на уникальность name (ON CONFLICT).
+CREATE UNIQUE INDEX IF NOT EXISTS metric_types_name_key ON metric_types (name);
+
+INSERT INTO metric_types (name, description) VALUES
+    ('cpu_usage', 'Загрузка CPU, %'),
+    ('memory_usage', 'Использование памяти, %'),
+    ('latency_ms', 'Задержка, мс'),
+    ('packet_loss', 'Потери пакетов, %'),
+    ('bandwidth_usage', 'Загрузка канала, %')
+ON CONFLICT (name) DO NOTHING;
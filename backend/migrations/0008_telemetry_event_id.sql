@@ -0,0 +1,4 @@
+-- Идентификатор события от источника. Повторная отправка того же события
+-- не создаёт дубликат; строки без event_id не ограничиваются (NULL различны).
+ALTER TABLE telemetry_data ADD COLUMN IF NOT EXISTS event_id UUID;
+ALTER TABLE telemetry_data ADD CONSTRAINT telemetry_data_event_id_key UNIQUE (event_id);
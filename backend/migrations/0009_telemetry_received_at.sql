@@ -0,0 +1,4 @@
+-- recorded_at — время измерения у источника (observed_at события),
+-- received_at — время приёма сервером. Для старых строк они совпадают.
+ALTER TABLE telemetry_data ADD COLUMN IF NOT EXISTS received_at TIMESTAMP;
+UPDATE telemetry_data SET received_at = recorded_at WHERE received_at IS NULL;
@@ -0,0 +1,10 @@
+-- Постраничная выдача идёт по ключу (recorded_at, id), поэтому recorded_at
+-- не может быть NULL.
+UPDATE telemetry_data SET recorded_at = COALESCE(received_at, now()) WHERE recorded_at IS NULL;
+ALTER TABLE telemetry_data ALTER COLUMN recorded_at SET NOT NULL;
+ALTER TABLE telemetry_data ALTER COLUMN received_at SET DEFAULT now();
+
+CREATE INDEX IF NOT EXISTS telemetry_data_recorded_at_id_idx
+    ON telemetry_data (recorded_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS telemetry_data_device_recorded_at_idx
+    ON telemetry_data (device_id, recorded_at DESC);
@@ -0,0 +1,33 @@
+-- Агрегаты телеметрии по минутам, часам и дням. Поддерживаются фоновой
+-- задачей и переживают удаление сырых строк из telemetry_data по сроку хранения.
+CREATE TABLE IF NOT EXISTS telemetry_rollup_1m (
+    device_id      INTEGER   NOT NULL REFERENCES devices(id) ON DELETE CASCADE,
+    metric_type_id INTEGER   NOT NULL REFERENCES metric_types(id) ON DELETE CASCADE,
+    bucket         TIMESTAMP NOT NULL,
+    count          BIGINT    NOT NULL,
+    sum            DOUBLE PRECISION NOT NULL,
+    min            DOUBLE PRECISION NOT NULL,
+    max            DOUBLE PRECISION NOT NULL,
+    p95            DOUBLE PRECISION NOT NULL,
+    anomaly_count  BIGINT    NOT NULL,
+    PRIMARY KEY (device_id, metric_type_id, bucket)
+);
+CREATE INDEX IF NOT EXISTS telemetry_rollup_1m_bucket_idx ON telemetry_rollup_1m (bucket);
+
+CREATE TABLE IF NOT EXISTS telemetry_rollup_1h (LIKE telemetry_rollup_1m INCLUDING ALL);
+ALTER TABLE telemetry_rollup_1h
+    ADD FOREIGN KEY (device_id) REFERENCES devices(id) ON DELETE CASCADE,
+    ADD FOREIGN KEY (metric_type_id) REFERENCES metric_types(id) ON DELETE CASCADE;
+
+CREATE TABLE IF NOT EXISTS telemetry_rollup_1d (LIKE telemetry_rollup_1m INCLUDING ALL);
+ALTER TABLE telemetry_rollup_1d
+    ADD FOREIGN KEY (device_id) REFERENCES devices(id) ON DELETE CASCADE,
+    ADD FOREIGN KEY (metric_type_id) REFERENCES metric_types(id) ON DELETE CASCADE;
+
+-- Строки telemetry_data с received_at < rolled_until уже учтены в агрегатах уровня.
+CREATE TABLE IF NOT EXISTS telemetry_rollup_state (
+    level        TEXT PRIMARY KEY,
+    rolled_until TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS telemetry_data_received_at_idx ON telemetry_data (received_at);
@@ -0,0 +1,19 @@
+-- Уровень строки телеметрии по порогам warning_level/critical_level.
+-- is_anomaly остаётся для совместимости и равен severity = 'critical'.
+CREATE TYPE telemetry_severity AS ENUM ('ok', 'warning', 'critical');
+
+ALTER TABLE telemetry_data ADD COLUMN IF NOT EXISTS severity telemetry_severity NOT NULL DEFAULT 'ok';
+UPDATE telemetry_data SET severity = 'critical' WHERE is_anomaly;
+
+ALTER TABLE devices ADD COLUMN IF NOT EXISTS status_changed_at TIMESTAMP;
+
+-- Последний класс уровня (ok / тревога) по паре устройство/метрика и сколько
+-- измерений подряд он держится. Нужен для гистерезиса статуса устройства.
+CREATE TABLE IF NOT EXISTS device_metric_health (
+    device_id      INTEGER NOT NULL REFERENCES devices(id) ON DELETE CASCADE,
+    metric_type_id INTEGER NOT NULL REFERENCES metric_types(id) ON DELETE CASCADE,
+    severity       telemetry_severity NOT NULL,
+    streak         INTEGER NOT NULL,
+    updated_at     TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (device_id, metric_type_id)
+);
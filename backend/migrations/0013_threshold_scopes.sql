@@ -0,0 +1,44 @@
+-- Область действия порога: глобальный по метрике, по расположению или по
+-- устройству. При оценке более узкая область перекрывает более широкую.
+-- comparison задаёт направление: gte/gt — верхняя граница (значение растёт
+-- к порогу), lte/lt — нижняя (например, пропускная способность падает до нуля).
+CREATE TYPE threshold_comparison AS ENUM ('gte', 'gt', 'lte', 'lt');
+
+ALTER TABLE thresholds
+    ADD COLUMN IF NOT EXISTS device_id  INTEGER REFERENCES devices(id) ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS location   VARCHAR(150),
+    ADD COLUMN IF NOT EXISTS comparison threshold_comparison NOT NULL DEFAULT 'gte',
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMP NOT NULL DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP NOT NULL DEFAULT now();
+
+ALTER TABLE thresholds
+    ADD CONSTRAINT thresholds_single_scope CHECK (device_id IS NULL OR location IS NULL);
+
+-- Раньше действовал порог с наименьшим id, остальные дубликаты не учитывались.
+-- Какой из них оставить, решает администратор: миграция их не удаляет.
+DO $$
+DECLARE
+    duplicated TEXT;
+BEGIN
+    SELECT string_agg(format('metric_type_id %s: id %s', metric_type_id, ids), '; ' ORDER BY metric_type_id)
+    INTO duplicated
+    FROM (
+        SELECT metric_type_id, string_agg(id::text, ', ' ORDER BY id) AS ids
+        FROM thresholds
+        GROUP BY metric_type_id
+        HAVING COUNT(*) > 1
+    ) d;
+
+    IF duplicated IS NOT NULL THEN
+        RAISE EXCEPTION 'Несколько порогов для одной метрики: %', duplicated
+            USING HINT = 'Оставьте по одному порогу на metric_type_id и повторите миграцию';
+    END IF;
+END $$;
+
+-- Не больше одной верхней и одной нижней границы на метрику в каждой области
+CREATE UNIQUE INDEX IF NOT EXISTS thresholds_scope_key ON thresholds (
+    metric_type_id,
+    COALESCE(device_id, 0),
+    COALESCE(location, ''),
+    (comparison IN ('lte', 'lt'))
+);
@@ -0,0 +1,26 @@
+-- Статистические детекторы аномалий по типам метрик. У метрики может быть
+-- несколько детекторов; окно считается по паре устройство/метрика.
+-- threshold — предельная оценка: число сигм для zscore/ewma, робастная
+-- оценка для mad, изменение значения в секунду для rate_of_change.
+CREATE TYPE anomaly_detector AS ENUM ('zscore', 'ewma', 'mad', 'rate_of_change');
+
+CREATE TABLE IF NOT EXISTS metric_detectors (
+    metric_type_id INTEGER NOT NULL REFERENCES metric_types(id) ON DELETE CASCADE,
+    detector       anomaly_detector NOT NULL,
+    window_size    INTEGER NOT NULL CHECK (window_size BETWEEN 2 AND 1000),
+    threshold      DOUBLE PRECISION NOT NULL CHECK (threshold > 0),
+    -- Коэффициент сглаживания, только для ewma
+    alpha          DOUBLE PRECISION CHECK (alpha > 0 AND alpha <= 1),
+    updated_at     TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (metric_type_id, detector)
+);
+
+-- Что пометило строку аномальной: имя детектора или 'threshold'
+-- для критического порога, и оценка детектора.
+ALTER TABLE telemetry_data
+    ADD COLUMN IF NOT EXISTS anomaly_detector TEXT,
+    ADD COLUMN IF NOT EXISTS anomaly_score    DOUBLE PRECISION;
+
+-- Окно последних значений пары устройство/метрика
+CREATE INDEX IF NOT EXISTS telemetry_data_device_metric_recorded_at_idx
+    ON telemetry_data (device_id, metric_type_id, recorded_at DESC);
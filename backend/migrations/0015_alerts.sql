@@ -0,0 +1,59 @@
+-- Алерты: один алерт на эпизод (устройство, метрика, уровень). Последующие
+-- аномальные измерения того же эпизода учитываются в нём, а не порождают
+-- новые записи. Эпизод закрывается вручную или после серии нормальных
+-- измерений (ALERT_AUTO_RESOLVE_AFTER).
+CREATE TYPE alert_status AS ENUM ('open', 'acknowledged', 'resolved');
+CREATE TYPE alert_event_type AS ENUM (
+    'opened', 'acknowledged', 'assigned', 'commented', 'resolved', 'auto_resolved'
+);
+
+CREATE TABLE IF NOT EXISTS alerts (
+    id              SERIAL PRIMARY KEY,
+    device_id       INTEGER NOT NULL REFERENCES devices(id) ON DELETE CASCADE,
+    metric_type_id  INTEGER NOT NULL REFERENCES metric_types(id) ON DELETE CASCADE,
+    severity        telemetry_severity NOT NULL,
+    status          alert_status NOT NULL DEFAULT 'open',
+    -- Что открыло алерт: детектор или 'threshold'
+    detector        TEXT,
+    first_value     DOUBLE PRECISION NOT NULL,
+    last_value      DOUBLE PRECISION NOT NULL,
+    sample_count    INTEGER NOT NULL DEFAULT 1,
+    -- Нормальных измерений подряд с последнего аномального
+    healthy_streak  INTEGER NOT NULL DEFAULT 0,
+    opened_at       TIMESTAMP NOT NULL,
+    last_seen_at    TIMESTAMP NOT NULL,
+    acknowledged_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    acknowledged_at TIMESTAMP,
+    assigned_to     INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    -- NULL при закрытом алерте — закрыт автоматически
+    resolved_by     INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    resolved_at     TIMESTAMP
+);
+
+-- Не больше одного незакрытого алерта на эпизод
+CREATE UNIQUE INDEX IF NOT EXISTS alerts_active_episode_key
+    ON alerts (device_id, metric_type_id, severity)
+    WHERE status <> 'resolved';
+CREATE INDEX IF NOT EXISTS alerts_opened_at_idx ON alerts (opened_at DESC, id DESC);
+
+CREATE TABLE IF NOT EXISTS alert_events (
+    id         SERIAL PRIMARY KEY,
+    alert_id   INTEGER NOT NULL REFERENCES alerts(id) ON DELETE CASCADE,
+    event_type alert_event_type NOT NULL,
+    -- NULL для действий системы
+    user_id    INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    details    TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS alert_events_alert_id_idx ON alert_events (alert_id, id);
+
+INSERT INTO role_permissions (role_id, permission)
+SELECT r.id, p.permission
+FROM roles r
+JOIN (VALUES
+    ('оператор', 'alerts:read'),
+    ('оператор', 'alerts:manage'),
+    ('администратор', 'alerts:read'),
+    ('администратор', 'alerts:manage')
+) AS p(role_name, permission) ON p.role_name = r.role_name
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,29 @@
+-- Подписки на уведомления об алертах. Подписчик — пользователь или роль
+-- (все её пользователи); область — все устройства, одно устройство или
+-- расположение. Переходы алертов доставляются в mailboxes подписчикам,
+-- чья область и min_severity подходят алерту.
+CREATE TABLE IF NOT EXISTS alert_subscriptions (
+    id           SERIAL PRIMARY KEY,
+    user_id      INTEGER REFERENCES users(id) ON DELETE CASCADE,
+    role_id      INTEGER REFERENCES roles(id) ON DELETE CASCADE,
+    device_id    INTEGER REFERENCES devices(id) ON DELETE CASCADE,
+    location     VARCHAR(150),
+    min_severity telemetry_severity NOT NULL DEFAULT 'warning',
+    created_at   TIMESTAMP NOT NULL DEFAULT now(),
+    CONSTRAINT alert_subscriptions_single_subscriber
+        CHECK ((user_id IS NULL) <> (role_id IS NULL)),
+    CONSTRAINT alert_subscriptions_single_scope
+        CHECK (device_id IS NULL OR location IS NULL)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS alert_subscriptions_scope_key
+    ON alert_subscriptions (
+        COALESCE(user_id, 0), COALESCE(role_id, 0), COALESCE(device_id, 0), COALESCE(location, '')
+    );
+
+-- Сообщение об алерте ссылается на него; удалённый алерт сообщение не удаляет
+ALTER TABLE mailboxes
+    ADD COLUMN IF NOT EXISTS alert_id INTEGER REFERENCES alerts(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS mailboxes_user_id_idx ON mailboxes (user_id, id DESC);
+CREATE INDEX IF NOT EXISTS mailboxes_unread_idx ON mailboxes (user_id) WHERE NOT read_status;
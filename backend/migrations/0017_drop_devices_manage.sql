@@ -0,0 +1,3 @@
+-- Право devices:manage не проверяет ни один маршрут, поэтому его выдача
+-- ничего не давала. Убираем его из ролей, в том числе из посева 0004.
+DELETE FROM role_permissions WHERE permission = 'devices:manage';
@@ -1,17 +1,52 @@
+#![allow(clippy::module_inception)]
+
 use actix_cors::Cors;
+use actix_web::error::{JsonPayloadError, PayloadError};
 use actix_web::{App, HttpServer, web};
 use dotenvy::dotenv;
 use sqlx::PgPool;
 use std::env;
 use tracing::{info, Level};
-use tracing_subscriber;
+use utoipa::OpenApi;
+use utoipa_actix_web::AppExt;
+use utoipa_redoc::{Redoc, Servable};
+use utoipa_swagger_ui::SwaggerUi;
 
 mod management_engine;
 
-use management_engine::api::auth::{login, register};
+use management_engine::api::admin_api::{
+    assign_user_role, create_role, create_role_subscription, delete_role_subscription, delete_user,
+    disable_user, enable_user, list_role_subscriptions, list_roles, list_users, revoke_user_role,
+    unlock_user, update_role_permissions,
+};
+use management_engine::api::profile_api::{
+    change_password, create_subscription, delete_mailbox, delete_subscription, get_mailbox, get_me,
+    list_subscriptions, mark_mailbox, update_me,
+};
+use management_engine::api::auth::{
+    forgot_password, jwks, login, logout, refresh, register, reset_password,
+};
+use management_engine::api::docs::ApiDoc;
+use management_engine::clients::clients::notifier::notifier::{init_notifier, notifier_from_env};
+use management_engine::controllers::auth::keys::{SigningKeys, init_signing_keys};
+use management_engine::controllers::errors::{AppError, FieldError};
+use management_engine::controllers::telemetry::rollups::start_rollup_job;
 use management_engine::api::operator_api::{
     receive_telemetry,
     get_telemetry,
+    get_telemetry_aggregate,
+    list_thresholds,
+    create_threshold,
+    update_threshold,
+    delete_threshold,
+    list_detectors,
+    set_metric_detectors,
+    list_alerts,
+    get_alert,
+    acknowledge_alert,
+    assign_alert,
+    comment_alert,
+    resolve_alert,
     start_generator_polling
 };
 
@@ -24,6 +59,12 @@ async fn main() -> std::io::Result<()> {
 
     info!("=== BACKEND START ===");
 
+    let keys = SigningKeys::from_env().expect("❌ Не удалось загрузить ключи JWT");
+    init_signing_keys(keys);
+
+    let notifier = notifier_from_env().expect("❌ Не удалось настроить канал уведомлений");
+    init_notifier(notifier);
+
     let database_url = env::var("DATABASE_URL")
         .expect("DATABASE_URL must be set");
 
@@ -31,8 +72,19 @@ async fn main() -> std::io::Result<()> {
         .await
         .expect("❌ Не удалось подключиться к базе");
 
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("❌ Не удалось применить миграции");
+
     let pool = web::Data::new(pool);
 
+    // Пачки телеметрии в тысячи событий не помещаются в стандартный лимит 2 МБ
+    let json_limit: usize = env::var("INGEST_MAX_BODY_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(8 * 1024 * 1024);
+
     // ------------------------------------------------------------
     // 🔥 Запуск фонового процесса опроса генератора каждые 20 сек
     // ------------------------------------------------------------
@@ -43,14 +95,84 @@ async fn main() -> std::io::Result<()> {
         });
     }
 
+    // Агрегаты телеметрии и удаление устаревших строк
+    {
+        let pool_clone = pool.clone();
+        tokio::spawn(async move {
+            start_rollup_job(pool_clone).await;
+        });
+    }
+
     info!("HTTP сервер => http://0.0.0.0:8080");
+    info!("Документация API => http://0.0.0.0:8080/swagger-ui/ и http://0.0.0.0:8080/redoc");
 
     HttpServer::new(move || {
-        App::new()
+        let (app, api) = App::new()
+            .into_utoipa_app()
+            .openapi(ApiDoc::openapi())
+            .app_data(pool.clone())
+            .app_data(web::JsonConfig::default().limit(json_limit).error_handler(move |err, _| match err {
+                // 413 отличает слишком большую пачку от некорректного JSON
+                JsonPayloadError::Overflow { .. }
+                | JsonPayloadError::OverflowKnownLength { .. }
+                | JsonPayloadError::Payload(PayloadError::Overflow) => {
+                    AppError::PayloadTooLarge { limit: json_limit }.into()
+                }
+                err => AppError::Validation(vec![FieldError::new("body", "invalid_json", err.to_string())])
+                    .into(),
+            }))
+            .service(register)
+            .service(login)
+            .service(refresh)
+            .service(logout)
+            .service(jwks)
+            .service(forgot_password)
+            .service(reset_password)
+            .service(receive_telemetry)  // <-- POST вручную
+            .service(get_telemetry)      // <-- GET для фронта
+            .service(get_telemetry_aggregate)
+            .service(list_thresholds)
+            .service(create_threshold)
+            .service(update_threshold)
+            .service(delete_threshold)
+            .service(list_detectors)
+            .service(set_metric_detectors)
+            .service(list_alerts)
+            .service(get_alert)
+            .service(acknowledge_alert)
+            .service(assign_alert)
+            .service(comment_alert)
+            .service(resolve_alert)
+            .service(unlock_user)
+            .service(list_roles)
+            .service(create_role)
+            .service(update_role_permissions)
+            .service(list_role_subscriptions)
+            .service(create_role_subscription)
+            .service(delete_role_subscription)
+            .service(assign_user_role)
+            .service(revoke_user_role)
+            .service(list_users)
+            .service(disable_user)
+            .service(enable_user)
+            .service(delete_user)
+            .service(get_me)
+            .service(update_me)
+            .service(change_password)
+            .service(get_mailbox)
+            .service(mark_mailbox)
+            .service(delete_mailbox)
+            .service(list_subscriptions)
+            .service(create_subscription)
+            .service(delete_subscription)
+            .split_for_parts();
+
+        app.service(SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", api.clone()))
+            .service(Redoc::with_url("/redoc", api))
             .wrap(
                 Cors::default()
                     .allowed_origin("http://localhost:5173")
-                    .allowed_methods(vec!["GET", "POST", "OPTIONS"])
+                    .allowed_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"])
                     .allowed_headers(vec![
                         actix_web::http::header::AUTHORIZATION,
                         actix_web::http::header::ACCEPT,
@@ -58,11 +180,6 @@ async fn main() -> std::io::Result<()> {
                     ])
                     .max_age(3600),
             )
-            .app_data(pool.clone())
-            .service(register)
-            .service(login)
-            .service(receive_telemetry)  // <-- POST вручную
-            .service(get_telemetry)      // <-- GET для фронта
     })
     .bind("0.0.0.0:8080")?
     .run()
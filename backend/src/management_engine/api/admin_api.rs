@@ -0,0 +1,398 @@
+use crate::management_engine::controllers::auth::auth::unlock_account_logic;
+use crate::management_engine::controllers::auth::middleware::{AuthenticatedUser, RequirePermissions};
+use crate::management_engine::controllers::auth::permissions::{ROLES_MANAGE, USERS_MANAGE};
+use crate::management_engine::controllers::errors::{AppError, ErrorBody};
+use crate::management_engine::controllers::mailbox::subscriptions::{
+    create_role_subscription_logic, delete_role_subscription_logic, list_role_subscriptions_logic,
+};
+use crate::management_engine::controllers::roles::roles::{
+    assign_role_logic, create_role_logic, list_roles_logic, revoke_role_logic,
+    update_role_permissions_logic,
+};
+use crate::management_engine::controllers::users::users::{
+    delete_user_logic, list_users_logic, set_user_disabled_logic,
+};
+use crate::management_engine::models::mailbox::mailbox::{SubscriptionRequest, SubscriptionView};
+use crate::management_engine::models::roles::roles::{
+    AssignRoleRequest, CreateRoleRequest, RoleWithPermissions, UpdateRolePermissionsRequest,
+};
+use crate::management_engine::models::users::users::{UserListQuery, UserListResponse};
+use actix_web::{HttpResponse, delete, get, post, put, web};
+use sqlx::PgPool;
+use tracing::{error, info};
+
+// ==================== POST /admin/users/{username}/unlock ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("username" = String, Path, description = "Имя пользователя")),
+    responses(
+        (status = 204, description = "Блокировка входа снята"),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Пользователь не найден", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/users/{username}/unlock", wrap = "RequirePermissions::all(&[USERS_MANAGE])")]
+pub async fn unlock_user(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let username = path.into_inner();
+    info!("POST /admin/users/{}/unlock от {}", username, admin.username);
+
+    match unlock_account_logic(&pool, &username, &admin.username).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка разблокировки пользователя {}: {}", username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== GET /admin/roles ====================
+#[utoipa::path(
+    tag = "admin",
+    responses(
+        (status = 200, description = "Роли и их права", body = Vec<RoleWithPermissions>),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/admin/roles", wrap = "RequirePermissions::all(&[ROLES_MANAGE])")]
+pub async fn list_roles(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /admin/roles от {}", admin.username);
+
+    let roles = list_roles_logic(&pool).await?;
+    Ok(HttpResponse::Ok().json(roles))
+}
+
+// ==================== POST /admin/roles ====================
+#[utoipa::path(
+    tag = "admin",
+    request_body = CreateRoleRequest,
+    responses(
+        (status = 201, description = "Роль создана", body = RoleWithPermissions),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 409, description = "Роль уже существует", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/roles", wrap = "RequirePermissions::all(&[ROLES_MANAGE])")]
+pub async fn create_role(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    req: web::Json<CreateRoleRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("POST /admin/roles '{}' от {}", req.role_name, admin.username);
+
+    match create_role_logic(&pool, &req, &admin.username).await {
+        Ok(role) => Ok(HttpResponse::Created().json(role)),
+        Err(err) => {
+            error!("Ошибка создания роли '{}': {}", req.role_name, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== PUT /admin/roles/{role_name}/permissions ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("role_name" = String, Path, description = "Название роли")),
+    request_body = UpdateRolePermissionsRequest,
+    responses(
+        (status = 200, description = "Права роли заменены", body = RoleWithPermissions),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Роль не найдена", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/admin/roles/{role_name}/permissions", wrap = "RequirePermissions::all(&[ROLES_MANAGE])")]
+pub async fn update_role_permissions(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+    req: web::Json<UpdateRolePermissionsRequest>,
+) -> Result<HttpResponse, AppError> {
+    let role_name = path.into_inner();
+    info!("PUT /admin/roles/{}/permissions от {}", role_name, admin.username);
+
+    match update_role_permissions_logic(&pool, &role_name, &req, &admin.username).await {
+        Ok(role) => Ok(HttpResponse::Ok().json(role)),
+        Err(err) => {
+            error!("Ошибка изменения прав роли '{}': {}", role_name, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== GET /admin/roles/{role_name}/subscriptions ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("role_name" = String, Path, description = "Название роли")),
+    responses(
+        (status = 200, description = "Подписки роли на алерты", body = Vec<SubscriptionView>),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Роль не найдена", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/admin/roles/{role_name}/subscriptions", wrap = "RequirePermissions::all(&[ROLES_MANAGE])")]
+pub async fn list_role_subscriptions(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let role_name = path.into_inner();
+    info!("GET /admin/roles/{}/subscriptions от {}", role_name, admin.username);
+
+    let subscriptions = list_role_subscriptions_logic(&pool, &role_name).await?;
+    Ok(HttpResponse::Ok().json(subscriptions))
+}
+
+// ==================== POST /admin/roles/{role_name}/subscriptions ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("role_name" = String, Path, description = "Название роли")),
+    request_body = SubscriptionRequest,
+    responses(
+        (status = 201, description = "Подписка роли создана", body = SubscriptionView),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Роль или устройство не найдены", body = ErrorBody),
+        (status = 409, description = "Такая подписка уже существует", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/roles/{role_name}/subscriptions", wrap = "RequirePermissions::all(&[ROLES_MANAGE])")]
+pub async fn create_role_subscription(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+    req: web::Json<SubscriptionRequest>,
+) -> Result<HttpResponse, AppError> {
+    let role_name = path.into_inner();
+    info!("POST /admin/roles/{}/subscriptions от {}", role_name, admin.username);
+
+    match create_role_subscription_logic(&pool, &role_name, &req, &admin.username).await {
+        Ok(subscription) => Ok(HttpResponse::Created().json(subscription)),
+        Err(err) => {
+            error!("Ошибка создания подписки роли '{}': {}", role_name, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== DELETE /admin/roles/{role_name}/subscriptions/{id} ====================
+#[utoipa::path(
+    tag = "admin",
+    params(
+        ("role_name" = String, Path, description = "Название роли"),
+        ("id" = i32, Path, description = "Идентификатор подписки"),
+    ),
+    responses(
+        (status = 204, description = "Подписка роли удалена"),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Роль или подписка не найдены", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete(
+    "/admin/roles/{role_name}/subscriptions/{id}",
+    wrap = "RequirePermissions::all(&[ROLES_MANAGE])"
+)]
+pub async fn delete_role_subscription(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<(String, i32)>,
+) -> Result<HttpResponse, AppError> {
+    let (role_name, id) = path.into_inner();
+    info!("DELETE /admin/roles/{}/subscriptions/{} от {}", role_name, id, admin.username);
+
+    match delete_role_subscription_logic(&pool, &role_name, id, &admin.username).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка удаления подписки #{} роли '{}': {}", id, role_name, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== PUT /admin/users/{username}/role ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("username" = String, Path, description = "Имя пользователя")),
+    request_body = AssignRoleRequest,
+    responses(
+        (status = 204, description = "Роль назначена"),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Пользователь или роль не найдены", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/admin/users/{username}/role", wrap = "RequirePermissions::all(&[ROLES_MANAGE])")]
+pub async fn assign_user_role(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+    req: web::Json<AssignRoleRequest>,
+) -> Result<HttpResponse, AppError> {
+    let username = path.into_inner();
+    info!(
+        "PUT /admin/users/{}/role '{}' от {}",
+        username, req.role_name, admin.username
+    );
+
+    match assign_role_logic(&pool, &username, &req.role_name, &admin.username).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка назначения роли пользователю {}: {}", username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== DELETE /admin/users/{username}/role ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("username" = String, Path, description = "Имя пользователя")),
+    responses(
+        (status = 204, description = "Роль отозвана, пользователь остался без прав"),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Пользователь не найден", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/admin/users/{username}/role", wrap = "RequirePermissions::all(&[ROLES_MANAGE])")]
+pub async fn revoke_user_role(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let username = path.into_inner();
+    info!("DELETE /admin/users/{}/role от {}", username, admin.username);
+
+    match revoke_role_logic(&pool, &username, &admin.username).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка отзыва роли пользователя {}: {}", username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== GET /admin/users ====================
+#[utoipa::path(
+    tag = "admin",
+    params(UserListQuery),
+    responses(
+        (status = 200, description = "Страница списка пользователей", body = UserListResponse),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/admin/users", wrap = "RequirePermissions::all(&[USERS_MANAGE])")]
+pub async fn list_users(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    query: web::Query<UserListQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /admin/users {:?} от {}", query, admin.username);
+
+    let users = list_users_logic(&pool, &query).await?;
+    Ok(HttpResponse::Ok().json(users))
+}
+
+// ==================== POST /admin/users/{username}/disable ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("username" = String, Path, description = "Имя пользователя")),
+    responses(
+        (status = 204, description = "Учётная запись отключена, сессии завершены"),
+        (status = 400, description = "Попытка отключить собственную учётную запись", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Пользователь не найден", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/users/{username}/disable", wrap = "RequirePermissions::all(&[USERS_MANAGE])")]
+pub async fn disable_user(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let username = path.into_inner();
+    info!("POST /admin/users/{}/disable от {}", username, admin.username);
+
+    match set_user_disabled_logic(&pool, &username, true, &admin.username).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка отключения пользователя {}: {}", username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== POST /admin/users/{username}/enable ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("username" = String, Path, description = "Имя пользователя")),
+    responses(
+        (status = 204, description = "Учётная запись включена"),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Пользователь не найден", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/users/{username}/enable", wrap = "RequirePermissions::all(&[USERS_MANAGE])")]
+pub async fn enable_user(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let username = path.into_inner();
+    info!("POST /admin/users/{}/enable от {}", username, admin.username);
+
+    match set_user_disabled_logic(&pool, &username, false, &admin.username).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка включения пользователя {}: {}", username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== DELETE /admin/users/{username} ====================
+#[utoipa::path(
+    tag = "admin",
+    params(("username" = String, Path, description = "Имя пользователя")),
+    responses(
+        (status = 204, description = "Пользователь удалён"),
+        (status = 400, description = "Попытка удалить собственную учётную запись", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Пользователь не найден", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/admin/users/{username}", wrap = "RequirePermissions::all(&[USERS_MANAGE])")]
+pub async fn delete_user(
+    pool: web::Data<PgPool>,
+    admin: AuthenticatedUser,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let username = path.into_inner();
+    info!("DELETE /admin/users/{} от {}", username, admin.username);
+
+    match delete_user_logic(&pool, &username, &admin.username).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка удаления пользователя {}: {}", username, err);
+            Err(err)
+        }
+    }
+}
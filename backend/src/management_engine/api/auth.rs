@@ -1,48 +1,183 @@
-use crate::management_engine::controllers::auth::auth::{login_logic, register_logic};
-use crate::management_engine::models::auth::auth::{AuthRequest, TokenResponse};
-use actix_web::{HttpResponse, Responder, get, post, web};
+use crate::management_engine::controllers::auth::auth::{
+    login_logic, logout_logic, refresh_logic, register_logic,
+};
+use crate::management_engine::controllers::auth::keys::signing_keys;
+use crate::management_engine::controllers::auth::lockout::lockout_policy;
+use crate::management_engine::controllers::auth::password_reset::{
+    forgot_password_logic, reset_password_logic,
+};
+use crate::management_engine::controllers::errors::{AppError, ErrorBody};
+use crate::management_engine::models::auth::auth::{
+    ForgotPasswordRequest, LoginRequest, RefreshRequest, RegisterRequest, ResetPasswordRequest,
+    TokenResponse,
+};
+use actix_web::{HttpRequest, HttpResponse, Responder, get, post, web};
 use tracing::{error, info};
 
+#[utoipa::path(
+    tag = "auth",
+    request_body = RegisterRequest,
+    responses(
+        (status = 200, description = "Пользователь зарегистрирован", body = TokenResponse),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 409, description = "Пользователь уже существует", body = ErrorBody),
+    )
+)]
 #[post("/register")]
 pub async fn register(
     pool: web::Data<sqlx::PgPool>,
-    req: web::Json<AuthRequest>,
-) -> impl Responder {
-    // Логируем входящий запрос
-    info!("Вошли в /register с телом: {:?}", req);
-
-    let result = register_logic(&pool, &req).await;
+    req: web::Json<RegisterRequest>,
+) -> Result<HttpResponse, AppError> {
+    // Логируем входящий запрос (без пароля)
+    info!("Вошли в /register с пользователем: {}, email: {}", req.username, req.email);
 
-    // Логируем результат вызова логики
-    info!("Результат register_logic: {:?}", result);
-
-    match result {
+    match register_logic(&pool, &req).await {
         Ok(resp) => {
             info!("Регистрация пользователя {} успешна", req.username);
-            HttpResponse::Ok().json(resp)
+            Ok(HttpResponse::Ok().json(resp))
         }
-        Err(msg) => {
-            error!("Ошибка регистрации пользователя {}: {}", req.username, msg);
-            HttpResponse::BadRequest().body(msg)
+        Err(err) => {
+            error!("Ошибка регистрации пользователя {}: {}", req.username, err);
+            Err(err)
         }
     }
 }
 
+#[utoipa::path(
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Успешный вход", body = TokenResponse),
+        (status = 401, description = "Неверные учетные данные", body = ErrorBody),
+        (status = 429, description = "Вход временно заблокирован, см. Retry-After", body = ErrorBody),
+    )
+)]
 #[post("/login")]
-pub async fn login(pool: web::Data<sqlx::PgPool>, req: web::Json<AuthRequest>) -> impl Responder {
+pub async fn login(
+    pool: web::Data<sqlx::PgPool>,
+    http_req: HttpRequest,
+    req: web::Json<LoginRequest>,
+) -> Result<HttpResponse, AppError> {
     // Логируем входящий запрос
     info!("Вошли в /login с пользователем: {}", req.username);
 
-    let result = login_logic(&pool, &req).await;
+    let ip_address = lockout_policy().client_ip(&http_req).map(|ip| ip.to_string());
+    let result = login_logic(&pool, &req, ip_address.as_deref()).await;
 
     match result {
         Ok(resp) => {
             info!("Пользователь {} успешно вошел", req.username);
-            HttpResponse::Ok().json(resp)
+            Ok(HttpResponse::Ok().json(resp))
+        }
+        Err(err) => {
+            error!("Ошибка входа пользователя {}: {}", req.username, err);
+            Err(err)
         }
-        Err(msg) => {
-            error!("Ошибка входа пользователя {}: {}", req.username, msg);
-            HttpResponse::Unauthorized().body(msg)
+    }
+}
+
+#[utoipa::path(
+    tag = "auth",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "Новая пара токенов", body = TokenResponse),
+        (status = 401, description = "Refresh-токен недействителен, истёк или переиспользован", body = ErrorBody),
+    )
+)]
+#[post("/refresh")]
+pub async fn refresh(
+    pool: web::Data<sqlx::PgPool>,
+    req: web::Json<RefreshRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("Вошли в /refresh");
+
+    match refresh_logic(&pool, &req).await {
+        Ok(resp) => Ok(HttpResponse::Ok().json(resp)),
+        Err(err) => {
+            error!("Ошибка обновления токена: {}", err);
+            Err(err)
         }
     }
 }
+
+#[utoipa::path(
+    tag = "auth",
+    request_body = RefreshRequest,
+    responses(
+        (status = 204, description = "Семейство refresh-токенов отозвано"),
+        (status = 401, description = "Недействительный refresh-токен", body = ErrorBody),
+    )
+)]
+#[post("/logout")]
+pub async fn logout(
+    pool: web::Data<sqlx::PgPool>,
+    req: web::Json<RefreshRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("Вошли в /logout");
+
+    match logout_logic(&pool, &req).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка выхода: {}", err);
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "auth",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 202, description = "Если адрес зарегистрирован, на него отправлена ссылка для сброса"),
+    )
+)]
+#[post("/password/forgot")]
+pub async fn forgot_password(
+    pool: web::Data<sqlx::PgPool>,
+    req: web::Json<ForgotPasswordRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("Вошли в /password/forgot");
+
+    match forgot_password_logic(&pool, &req).await {
+        Ok(()) => Ok(HttpResponse::Accepted().finish()),
+        Err(err) => {
+            error!("Ошибка запроса сброса пароля: {}", err);
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "auth",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 204, description = "Пароль изменён, все сессии завершены"),
+        (status = 400, description = "Токен недействителен или пароль не соответствует политике", body = ErrorBody),
+    )
+)]
+#[post("/password/reset")]
+pub async fn reset_password(
+    pool: web::Data<sqlx::PgPool>,
+    req: web::Json<ResetPasswordRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("Вошли в /password/reset");
+
+    match reset_password_logic(&pool, &req).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка сброса пароля: {}", err);
+            Err(err)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "auth",
+    responses(
+        (status = 200, description = "Открытые ключи проверки JWT (RFC 7517)", content_type = "application/json"),
+    )
+)]
+#[get("/.well-known/jwks.json")]
+pub async fn jwks() -> impl Responder {
+    HttpResponse::Ok().json(signing_keys().jwks())
+}
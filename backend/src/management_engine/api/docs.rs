@@ -0,0 +1,30 @@
+use utoipa::Modify;
+use utoipa::OpenApi;
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+
+/// Базовый документ OpenAPI. Пути и схемы собираются автоматически
+/// из `service(...)` через `utoipa_actix_web`.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Network Monitoring API", description = "API мониторинга сетевых устройств"),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "auth", description = "Регистрация, вход и токены"),
+        (name = "profile", description = "Профиль текущего пользователя"),
+        (name = "operator", description = "Телеметрия для операторов"),
+        (name = "admin", description = "Администрирование"),
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(Http::builder().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
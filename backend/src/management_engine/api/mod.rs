@@ -1,2 +1,5 @@
+pub mod admin_api;
 pub mod auth;
-pub mod operator_api;
\ No newline at end of file
+pub mod docs;
+pub mod operator_api;
+pub mod profile_api;
\ No newline at end of file
@@ -1,197 +1,476 @@
-use actix_web::{post, get, web, HttpResponse};
-use chrono::NaiveDateTime;
-use serde::{Deserialize, Serialize};
+use actix_web::{delete, get, post, put, web, HttpResponse};
 use sqlx::PgPool;
-use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
 use tracing::{error, info};
 use reqwest;
 
-#[derive(Debug, Deserialize, Clone)]
-pub struct TelemetryEvent {
-    pub device_name: String,
-    pub ip_address: String,
-    pub location: Option<String>,
-    pub metric_type_id: i32,
-    pub metric_value: f64,
-    pub action_description: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-pub struct TelemetryResponse {
-    pub device_name: String,
-    pub ip_address: String,
-    pub location: Option<String>,
-    pub metric_value: f64,
-    pub is_anomaly: bool,
-    pub action_description: Option<String>,
-    pub recorded_at: Option<NaiveDateTime>,
-}
-
-// ------------------ Вставка события с проверкой аномалии ------------------
-pub async fn insert_event_to_db(pool: &web::Data<PgPool>, event: &TelemetryEvent) -> Result<(), sqlx::Error> {
-    info!("Начало вставки события: {:?}", event);
-
-    // Вставка/получение устройства
-    let device_id = match sqlx::query_scalar!(
-        r#"
-        INSERT INTO devices (device_name, ip_address, location)
-        VALUES ($1, $2, $3)
-        ON CONFLICT (device_name) DO UPDATE SET device_name = EXCLUDED.device_name
-        RETURNING id
-        "#,
-        event.device_name,
-        event.ip_address,
-        event.location
-    )
-    .fetch_one(pool.get_ref())
-    .await
-    {
-        Ok(id) => {
-            info!("Устройство вставлено/найдено, device_id={}", id);
-            id
-        }
+use crate::management_engine::controllers::auth::middleware::{AuthenticatedUser, RequirePermissions};
+use crate::management_engine::controllers::auth::permissions::{
+    ALERTS_MANAGE, ALERTS_READ, TELEMETRY_READ, TELEMETRY_WRITE, THRESHOLDS_READ, THRESHOLDS_WRITE,
+};
+use crate::management_engine::controllers::alerts::alerts::{
+    acknowledge_alert_logic, assign_alert_logic, comment_alert_logic, get_alert_logic,
+    list_alerts_logic, resolve_alert_logic,
+};
+use crate::management_engine::controllers::errors::{AppError, ErrorBody};
+use crate::management_engine::controllers::telemetry::ingest::{ingest_batch, ingest_config};
+use crate::management_engine::controllers::telemetry::aggregate::aggregate_telemetry_logic;
+use crate::management_engine::controllers::telemetry::query::list_telemetry_logic;
+use crate::management_engine::controllers::thresholds::detectors::{
+    list_detectors_logic, set_metric_detectors_logic,
+};
+use crate::management_engine::controllers::thresholds::thresholds::{
+    create_threshold_logic, delete_threshold_logic, list_thresholds_logic, update_threshold_logic,
+};
+use crate::management_engine::models::alerts::alerts::{
+    AlertCommentRequest, AlertDetail, AlertListResponse, AlertQuery, AssignAlertRequest,
+    ResolveAlertRequest,
+};
+use crate::management_engine::models::telemetry::telemetry::{
+    AggregateQuery, AggregateResponse, IngestQuery, IngestResponse, TelemetryPage, TelemetryQuery,
+};
+use crate::management_engine::models::thresholds::detectors::{
+    DetectorConfig, UpdateDetectorsRequest,
+};
+use crate::management_engine::models::thresholds::thresholds::{
+    ThresholdQuery, ThresholdRequest, ThresholdView,
+};
+use telemetry_protocol::TelemetryEvent;
+
+// ==================== POST /operator/telemetry ====================
+#[utoipa::path(
+    tag = "operator",
+    request_body = Vec<TelemetryEvent>,
+    params(IngestQuery),
+    responses(
+        (status = 200, description = "Пачка обработана: записанные и отклонённые события", body = IngestResponse),
+        (status = 400, description = "Некорректный JSON", body = ErrorBody),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 413, description = "Тело больше INGEST_MAX_BODY_BYTES, разбейте пачку", body = ErrorBody),
+        (status = 422, description = "Ни одно событие не записано", body = IngestResponse),
+        (status = 500, description = "Ошибка БД, пачка не записана", body = IngestResponse),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/operator/telemetry", wrap = "RequirePermissions::all(&[TELEMETRY_WRITE])")]
+pub async fn receive_telemetry(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    query: web::Query<IngestQuery>,
+    events: web::Json<Vec<TelemetryEvent>>,
+) -> HttpResponse {
+    let mode = query.mode.unwrap_or(ingest_config().default_mode);
+    info!(
+        "POST /operator/telemetry от {} получено {} событий, режим {:?}",
+        user.username,
+        events.len(),
+        mode
+    );
+    let outcome = ingest_batch(pool.get_ref(), &events, mode).await;
+    log_rejected(&outcome.response, &events);
+    HttpResponse::build(outcome.status()).json(outcome.response)
+}
+
+fn log_rejected(response: &IngestResponse, events: &[TelemetryEvent]) {
+    for err in &response.errors {
+        error!(
+            "Событие #{} отклонено ({}): {}, событие={:?}",
+            err.index, err.code, err.message, events[err.index]
+        );
+    }
+}
+
+// ==================== GET /operator/telemetry ====================
+#[utoipa::path(
+    tag = "operator",
+    params(TelemetryQuery),
+    responses(
+        (status = 200, description = "Страница записей телеметрии", body = TelemetryPage),
+        (status = 400, description = "Некорректные фильтры или курсор", body = ErrorBody),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/operator/telemetry", wrap = "RequirePermissions::all(&[TELEMETRY_READ])")]
+pub async fn get_telemetry(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    query: web::Query<TelemetryQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /operator/telemetry {:?} от {}", query, user.username);
+
+    let page = list_telemetry_logic(&pool, &query).await?;
+    info!("Возвращено {} записей", page.items.len());
+    Ok(HttpResponse::Ok().json(page))
+}
+
+// ==================== GET /operator/telemetry/aggregate ====================
+#[utoipa::path(
+    tag = "operator",
+    params(AggregateQuery),
+    responses(
+        (status = 200, description = "Агрегаты по корзинам времени", body = AggregateResponse),
+        (status = 400, description = "Некорректный интервал или размер корзины", body = ErrorBody),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/operator/telemetry/aggregate", wrap = "RequirePermissions::all(&[TELEMETRY_READ])")]
+pub async fn get_telemetry_aggregate(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    query: web::Query<AggregateQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /operator/telemetry/aggregate {:?} от {}", query, user.username);
+
+    let aggregates = aggregate_telemetry_logic(&pool, &query).await?;
+    Ok(HttpResponse::Ok().json(aggregates))
+}
+
+// ==================== GET /operator/thresholds ====================
+#[utoipa::path(
+    tag = "operator",
+    params(ThresholdQuery),
+    responses(
+        (status = 200, description = "Пороги по метрикам и областям", body = Vec<ThresholdView>),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/operator/thresholds", wrap = "RequirePermissions::all(&[THRESHOLDS_READ])")]
+pub async fn list_thresholds(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    query: web::Query<ThresholdQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /operator/thresholds {:?} от {}", query, user.username);
+
+    let thresholds = list_thresholds_logic(&pool, &query).await?;
+    Ok(HttpResponse::Ok().json(thresholds))
+}
+
+// ==================== POST /operator/thresholds ====================
+#[utoipa::path(
+    tag = "operator",
+    request_body = ThresholdRequest,
+    responses(
+        (status = 201, description = "Порог создан", body = ThresholdView),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Устройство не найдено", body = ErrorBody),
+        (status = 409, description = "Порог для этой области и направления уже есть", body = ErrorBody),
+        (status = 422, description = "Неизвестная метрика", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/operator/thresholds", wrap = "RequirePermissions::all(&[THRESHOLDS_WRITE])")]
+pub async fn create_threshold(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    req: web::Json<ThresholdRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("POST /operator/thresholds {:?} от {}", req, user.username);
+
+    match create_threshold_logic(&pool, &req, &user.username).await {
+        Ok(threshold) => Ok(HttpResponse::Created().json(threshold)),
         Err(err) => {
-            error!("Ошибка при вставке/поиске устройства: {:?}, событие={:?}", err, event);
-            return Err(err);
-        }
-    };
-
-    // Получаем critical_level для данного metric_type
-    let threshold_row = match sqlx::query!(
-        r#"SELECT critical_level FROM thresholds WHERE metric_type_id = $1"#,
-        event.metric_type_id
-    )
-    .fetch_optional(pool.get_ref())
-    .await
-    {
-        Ok(row) => {
-            info!("Порог для metric_type_id {} получен: {:?}", event.metric_type_id, row);
-            row
+            error!("Ошибка создания порога для '{}': {}", req.metric_name, err);
+            Err(err)
         }
+    }
+}
+
+// ==================== PUT /operator/thresholds/{id} ====================
+#[utoipa::path(
+    tag = "operator",
+    params(("id" = i32, Path, description = "Идентификатор порога")),
+    request_body = ThresholdRequest,
+    responses(
+        (status = 200, description = "Порог заменён", body = ThresholdView),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Порог или устройство не найдены", body = ErrorBody),
+        (status = 409, description = "Порог для этой области и направления уже есть", body = ErrorBody),
+        (status = 422, description = "Неизвестная метрика", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/operator/thresholds/{id}", wrap = "RequirePermissions::all(&[THRESHOLDS_WRITE])")]
+pub async fn update_threshold(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+    req: web::Json<ThresholdRequest>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+    info!("PUT /operator/thresholds/{} {:?} от {}", id, req, user.username);
+
+    match update_threshold_logic(&pool, id, &req, &user.username).await {
+        Ok(threshold) => Ok(HttpResponse::Ok().json(threshold)),
         Err(err) => {
-            error!("Ошибка при получении порога для metric_type_id {}: {:?}, событие={:?}", event.metric_type_id, err, event);
-            return Err(err);
-        }
-    };
-
-    // Проверка на аномалию
-    let is_anomaly = match threshold_row {
-        Some(t) => {
-            if let Some(critical) = t.critical_level {
-                match critical.to_f64() {
-                    Some(critical_f64) => {
-                        let result = event.metric_value >= critical_f64;
-                        info!("Проверка аномалии: metric_value={} >= critical_level={} -> {}", event.metric_value, critical_f64, result);
-                        result
-                    }
-                    None => {
-                        error!("Не удалось преобразовать BigDecimal {} в f64, событие={:?}", critical, event);
-                        false
-                    }
-                }
-            } else {
-                info!("critical_level отсутствует для metric_type_id {}, событие={:?}", event.metric_type_id, event);
-                false
-            }
+            error!("Ошибка изменения порога #{}: {}", id, err);
+            Err(err)
         }
-        None => {
-            info!("Порог не найден для metric_type_id {}, событие={:?}", event.metric_type_id, event);
-            false
+    }
+}
+
+// ==================== DELETE /operator/thresholds/{id} ====================
+#[utoipa::path(
+    tag = "operator",
+    params(("id" = i32, Path, description = "Идентификатор порога")),
+    responses(
+        (status = 204, description = "Порог удалён"),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Порог не найден", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/operator/thresholds/{id}", wrap = "RequirePermissions::all(&[THRESHOLDS_WRITE])")]
+pub async fn delete_threshold(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+    info!("DELETE /operator/thresholds/{} от {}", id, user.username);
+
+    match delete_threshold_logic(&pool, id, &user.username).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка удаления порога #{}: {}", id, err);
+            Err(err)
         }
-    };
-
-    // Вставка телеметрии
-    let metric_value_bd = BigDecimal::from_f64(event.metric_value).unwrap_or_else(|| {
-        error!("Ошибка преобразования metric_value {} в BigDecimal, событие={:?}", event.metric_value, event);
-        BigDecimal::from(0)
-    });
-
-    match sqlx::query!(
-        r#"
-        INSERT INTO telemetry_data
-            (device_id, metric_type_id, metric_value, is_anomaly, action_description, recorded_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        "#,
-        device_id,
-        event.metric_type_id,
-        metric_value_bd,
-        is_anomaly,
-        event.action_description,
-        chrono::Utc::now().naive_utc()
-    )
-    .execute(pool.get_ref())
-    .await
-    {
-        Ok(_) => info!("Телеметрия успешно вставлена, событие={:?}, is_anomaly={}", event, is_anomaly),
+    }
+}
+
+// ==================== GET /operator/detectors ====================
+#[utoipa::path(
+    tag = "operator",
+    responses(
+        (status = 200, description = "Детекторы аномалий по метрикам", body = Vec<DetectorConfig>),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/operator/detectors", wrap = "RequirePermissions::all(&[THRESHOLDS_READ])")]
+pub async fn list_detectors(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /operator/detectors от {}", user.username);
+
+    let detectors = list_detectors_logic(&pool).await?;
+    Ok(HttpResponse::Ok().json(detectors))
+}
+
+// ==================== PUT /operator/detectors/{metric_name} ====================
+#[utoipa::path(
+    tag = "operator",
+    params(("metric_name" = String, Path, description = "Имя метрики")),
+    request_body = UpdateDetectorsRequest,
+    responses(
+        (status = 200, description = "Набор детекторов метрики заменён", body = Vec<DetectorConfig>),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 422, description = "Неизвестная метрика", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/operator/detectors/{metric_name}", wrap = "RequirePermissions::all(&[THRESHOLDS_WRITE])")]
+pub async fn set_metric_detectors(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<String>,
+    req: web::Json<UpdateDetectorsRequest>,
+) -> Result<HttpResponse, AppError> {
+    let metric_name = path.into_inner();
+    info!("PUT /operator/detectors/{} {:?} от {}", metric_name, req, user.username);
+
+    match set_metric_detectors_logic(&pool, &metric_name, &req, &user.username).await {
+        Ok(detectors) => Ok(HttpResponse::Ok().json(detectors)),
         Err(err) => {
-            error!("Ошибка при вставке телеметрии: {:?}, событие={:?}", err, event);
-            return Err(err);
+            error!("Ошибка изменения детекторов метрики '{}': {}", metric_name, err);
+            Err(err)
         }
-    };
+    }
+}
+
+// ==================== GET /operator/alerts ====================
+#[utoipa::path(
+    tag = "operator",
+    params(AlertQuery),
+    responses(
+        (status = 200, description = "Страница алертов, новые первыми", body = AlertListResponse),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/operator/alerts", wrap = "RequirePermissions::all(&[ALERTS_READ])")]
+pub async fn list_alerts(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    query: web::Query<AlertQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /operator/alerts {:?} от {}", query, user.username);
 
-    Ok(())
+    let alerts = list_alerts_logic(&pool, &query).await?;
+    Ok(HttpResponse::Ok().json(alerts))
 }
 
-// ==================== POST /operator/telemetry ====================
-#[post("/operator/telemetry")]
-pub async fn receive_telemetry(
+// ==================== GET /operator/alerts/{id} ====================
+#[utoipa::path(
+    tag = "operator",
+    params(("id" = i32, Path, description = "Идентификатор алерта")),
+    responses(
+        (status = 200, description = "Алерт с историей действий", body = AlertDetail),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Алерт не найден", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/operator/alerts/{id}", wrap = "RequirePermissions::all(&[ALERTS_READ])")]
+pub async fn get_alert(
     pool: web::Data<PgPool>,
-    events: web::Json<Vec<TelemetryEvent>>,
-) -> impl actix_web::Responder {
-    info!("POST /operator/telemetry получено {} событий", events.len());
-    for event in events.iter() {
-        if let Err(err) = insert_event_to_db(&pool, event).await {
-            error!("Ошибка вставки события: {:?}, событие={:?}", err, event);
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+    info!("GET /operator/alerts/{} от {}", id, user.username);
+
+    let alert = get_alert_logic(&pool, id).await?;
+    Ok(HttpResponse::Ok().json(alert))
+}
+
+// ==================== POST /operator/alerts/{id}/acknowledge ====================
+#[utoipa::path(
+    tag = "operator",
+    params(("id" = i32, Path, description = "Идентификатор алерта")),
+    responses(
+        (status = 200, description = "Алерт подтверждён", body = AlertDetail),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Алерт не найден", body = ErrorBody),
+        (status = 409, description = "Алерт уже закрыт", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/operator/alerts/{id}/acknowledge", wrap = "RequirePermissions::all(&[ALERTS_MANAGE])")]
+pub async fn acknowledge_alert(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+    info!("POST /operator/alerts/{}/acknowledge от {}", id, user.username);
+
+    match acknowledge_alert_logic(&pool, id, &user.username).await {
+        Ok(alert) => Ok(HttpResponse::Ok().json(alert)),
+        Err(err) => {
+            error!("Ошибка подтверждения алерта #{}: {}", id, err);
+            Err(err)
         }
     }
-    HttpResponse::Ok().body("Telemetry processed")
 }
 
-// ==================== GET /operator/telemetry ====================
-#[get("/operator/telemetry")]
-pub async fn get_telemetry(pool: web::Data<PgPool>) -> impl actix_web::Responder {
-    info!("GET /operator/telemetry");
-
-    let rows = match sqlx::query!(
-        r#"
-        SELECT 
-            d.device_name, 
-            d.ip_address, 
-            d.location,
-            t.metric_value::float8 as metric_value,
-            t.is_anomaly,
-            t.action_description,
-            t.recorded_at
-        FROM telemetry_data t
-        JOIN devices d ON t.device_id = d.id
-        ORDER BY t.recorded_at DESC
-        "#
-    )
-    .fetch_all(pool.get_ref())
-    .await
-    {
-        Ok(rows) => rows,
+// ==================== PUT /operator/alerts/{id}/assignee ====================
+#[utoipa::path(
+    tag = "operator",
+    params(("id" = i32, Path, description = "Идентификатор алерта")),
+    request_body = AssignAlertRequest,
+    responses(
+        (status = 200, description = "Назначение изменено", body = AlertDetail),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Алерт или пользователь не найден", body = ErrorBody),
+        (status = 409, description = "Алерт уже закрыт", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/operator/alerts/{id}/assignee", wrap = "RequirePermissions::all(&[ALERTS_MANAGE])")]
+pub async fn assign_alert(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+    req: web::Json<AssignAlertRequest>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+    info!("PUT /operator/alerts/{}/assignee {:?} от {}", id, req, user.username);
+
+    match assign_alert_logic(&pool, id, &req, &user.username).await {
+        Ok(alert) => Ok(HttpResponse::Ok().json(alert)),
+        Err(err) => {
+            error!("Ошибка назначения алерта #{}: {}", id, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== POST /operator/alerts/{id}/comments ====================
+#[utoipa::path(
+    tag = "operator",
+    params(("id" = i32, Path, description = "Идентификатор алерта")),
+    request_body = AlertCommentRequest,
+    responses(
+        (status = 201, description = "Комментарий добавлен", body = AlertDetail),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Алерт не найден", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/operator/alerts/{id}/comments", wrap = "RequirePermissions::all(&[ALERTS_MANAGE])")]
+pub async fn comment_alert(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+    req: web::Json<AlertCommentRequest>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+    info!("POST /operator/alerts/{}/comments от {}", id, user.username);
+
+    match comment_alert_logic(&pool, id, &req, &user.username).await {
+        Ok(alert) => Ok(HttpResponse::Created().json(alert)),
         Err(err) => {
-            error!("Ошибка при получении телеметрии: {:?}", err);
-            return HttpResponse::InternalServerError().body("DB error");
+            error!("Ошибка комментирования алерта #{}: {}", id, err);
+            Err(err)
         }
-    };
+    }
+}
 
-    let response: Vec<TelemetryResponse> = rows
-        .into_iter()
-        .map(|r| TelemetryResponse {
-            device_name: r.device_name,
-            ip_address: r.ip_address,
-            location: r.location,
-            metric_value: r.metric_value.unwrap_or(0.0),
-            is_anomaly: r.is_anomaly.unwrap_or(false),
-            action_description: r.action_description,
-            recorded_at: r.recorded_at,
-        })
-        .collect();
+// ==================== POST /operator/alerts/{id}/resolve ====================
+#[utoipa::path(
+    tag = "operator",
+    params(("id" = i32, Path, description = "Идентификатор алерта")),
+    request_body = ResolveAlertRequest,
+    responses(
+        (status = 200, description = "Алерт закрыт", body = AlertDetail),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 403, description = "Недостаточно прав", body = ErrorBody),
+        (status = 404, description = "Алерт не найден", body = ErrorBody),
+        (status = 409, description = "Алерт уже закрыт", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/operator/alerts/{id}/resolve", wrap = "RequirePermissions::all(&[ALERTS_MANAGE])")]
+pub async fn resolve_alert(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+    req: web::Json<ResolveAlertRequest>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+    info!("POST /operator/alerts/{}/resolve от {}", id, user.username);
 
-    info!("Возвращено {} записей", response.len());
-    HttpResponse::Ok().json(response)
+    match resolve_alert_logic(&pool, id, &req, &user.username).await {
+        Ok(alert) => Ok(HttpResponse::Ok().json(alert)),
+        Err(err) => {
+            error!("Ошибка закрытия алерта #{}: {}", id, err);
+            Err(err)
+        }
+    }
 }
 
 // ==================== POLLING ГЕНЕРАТОРА (каждые 20 сек) ====================
@@ -206,11 +485,8 @@ pub async fn start_generator_polling(pool: web::Data<PgPool>) {
                 match resp.json::<Vec<TelemetryEvent>>().await {
                     Ok(events) => {
                         info!("Получено {} событий от генератора", events.len());
-                        for event in events.iter() {
-                            if let Err(err) = insert_event_to_db(&pool, event).await {
-                                error!("Ошибка при вставке события из генератора: {:?}, событие={:?}", err, event);
-                            }
-                        }
+                        let outcome = ingest_batch(pool.get_ref(), &events, ingest_config().default_mode).await;
+                        log_rejected(&outcome.response, &events);
                     }
                     Err(err) => error!("Ошибка десериализации ответа генератора: {:?}", err),
                 }
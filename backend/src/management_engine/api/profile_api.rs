@@ -0,0 +1,254 @@
+use crate::management_engine::controllers::auth::middleware::{AuthenticatedUser, RequirePermissions};
+use crate::management_engine::controllers::errors::{AppError, ErrorBody};
+use crate::management_engine::controllers::mailbox::mailbox::{
+    delete_mailbox_logic, list_mailbox_logic, mark_mailbox_logic,
+};
+use crate::management_engine::controllers::mailbox::subscriptions::{
+    create_user_subscription_logic, delete_user_subscription_logic, list_user_subscriptions_logic,
+};
+use crate::management_engine::controllers::users::users::{
+    change_password_logic, get_profile_logic, update_profile_logic,
+};
+use crate::management_engine::models::mailbox::mailbox::{
+    DeleteMailboxRequest, MailboxChange, MailboxPage, MailboxQuery, MarkMailboxRequest,
+    SubscriptionRequest, SubscriptionView,
+};
+use crate::management_engine::models::users::users::{
+    ChangePasswordRequest, UpdateProfileRequest, UserProfile,
+};
+use actix_web::{HttpResponse, delete, get, patch, post, put, web};
+use sqlx::PgPool;
+use tracing::{error, info};
+
+// ==================== GET /me ====================
+#[utoipa::path(
+    tag = "profile",
+    responses(
+        (status = 200, description = "Профиль текущего пользователя", body = UserProfile),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/me", wrap = "RequirePermissions::authenticated()")]
+pub async fn get_me(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /me от {}", user.username);
+
+    let profile = get_profile_logic(&pool, &user.username).await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+// ==================== PATCH /me ====================
+#[utoipa::path(
+    tag = "profile",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Профиль изменён", body = UserProfile),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/me", wrap = "RequirePermissions::authenticated()")]
+pub async fn update_me(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    req: web::Json<UpdateProfileRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("PATCH /me от {}", user.username);
+
+    match update_profile_logic(&pool, &user.username, &req).await {
+        Ok(profile) => Ok(HttpResponse::Ok().json(profile)),
+        Err(err) => {
+            error!("Ошибка изменения профиля {}: {}", user.username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== PUT /me/password ====================
+#[utoipa::path(
+    tag = "profile",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 204, description = "Пароль изменён, все сессии завершены"),
+        (status = 400, description = "Неверный текущий пароль или новый не соответствует политике", body = ErrorBody),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/me/password", wrap = "RequirePermissions::authenticated()")]
+pub async fn change_password(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    req: web::Json<ChangePasswordRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("PUT /me/password от {}", user.username);
+
+    match change_password_logic(&pool, &user.username, &req).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка смены пароля {}: {}", user.username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== GET /me/mailbox ====================
+#[utoipa::path(
+    tag = "profile",
+    params(MailboxQuery),
+    responses(
+        (status = 200, description = "Страница сообщений, новые первыми, и число непрочитанных", body = MailboxPage),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/me/mailbox", wrap = "RequirePermissions::authenticated()")]
+pub async fn get_mailbox(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    query: web::Query<MailboxQuery>,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /me/mailbox {:?} от {}", query, user.username);
+
+    let page = list_mailbox_logic(&pool, &user.username, &query).await?;
+    Ok(HttpResponse::Ok().json(page))
+}
+
+// ==================== PATCH /me/mailbox ====================
+#[utoipa::path(
+    tag = "profile",
+    request_body = MarkMailboxRequest,
+    responses(
+        (status = 200, description = "Статус сообщений изменён", body = MailboxChange),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/me/mailbox", wrap = "RequirePermissions::authenticated()")]
+pub async fn mark_mailbox(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    req: web::Json<MarkMailboxRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("PATCH /me/mailbox ({} сообщений) от {}", req.ids.len(), user.username);
+
+    match mark_mailbox_logic(&pool, &user.username, &req).await {
+        Ok(change) => Ok(HttpResponse::Ok().json(change)),
+        Err(err) => {
+            error!("Ошибка изменения статуса сообщений {}: {}", user.username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== DELETE /me/mailbox ====================
+#[utoipa::path(
+    tag = "profile",
+    request_body = DeleteMailboxRequest,
+    responses(
+        (status = 200, description = "Сообщения удалены", body = MailboxChange),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/me/mailbox", wrap = "RequirePermissions::authenticated()")]
+pub async fn delete_mailbox(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    req: web::Json<DeleteMailboxRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("DELETE /me/mailbox ({} сообщений) от {}", req.ids.len(), user.username);
+
+    match delete_mailbox_logic(&pool, &user.username, &req).await {
+        Ok(change) => Ok(HttpResponse::Ok().json(change)),
+        Err(err) => {
+            error!("Ошибка удаления сообщений {}: {}", user.username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== GET /me/subscriptions ====================
+#[utoipa::path(
+    tag = "profile",
+    responses(
+        (status = 200, description = "Подписки на алерты, включая подписки роли", body = Vec<SubscriptionView>),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/me/subscriptions", wrap = "RequirePermissions::authenticated()")]
+pub async fn list_subscriptions(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    info!("GET /me/subscriptions от {}", user.username);
+
+    let subscriptions = list_user_subscriptions_logic(&pool, &user.username).await?;
+    Ok(HttpResponse::Ok().json(subscriptions))
+}
+
+// ==================== POST /me/subscriptions ====================
+#[utoipa::path(
+    tag = "profile",
+    request_body = SubscriptionRequest,
+    responses(
+        (status = 201, description = "Подписка создана", body = SubscriptionView),
+        (status = 400, description = "Ошибка валидации", body = ErrorBody),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+        (status = 404, description = "Устройство не найдено", body = ErrorBody),
+        (status = 409, description = "Такая подписка уже существует", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/me/subscriptions", wrap = "RequirePermissions::authenticated()")]
+pub async fn create_subscription(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    req: web::Json<SubscriptionRequest>,
+) -> Result<HttpResponse, AppError> {
+    info!("POST /me/subscriptions от {}", user.username);
+
+    match create_user_subscription_logic(&pool, &user.username, &req).await {
+        Ok(subscription) => Ok(HttpResponse::Created().json(subscription)),
+        Err(err) => {
+            error!("Ошибка создания подписки {}: {}", user.username, err);
+            Err(err)
+        }
+    }
+}
+
+// ==================== DELETE /me/subscriptions/{id} ====================
+#[utoipa::path(
+    tag = "profile",
+    params(("id" = i32, Path, description = "Идентификатор подписки")),
+    responses(
+        (status = 204, description = "Подписка удалена"),
+        (status = 401, description = "Нет или недействителен токен", body = ErrorBody),
+        (status = 404, description = "Подписка не найдена или принадлежит роли", body = ErrorBody),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/me/subscriptions/{id}", wrap = "RequirePermissions::authenticated()")]
+pub async fn delete_subscription(
+    pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let id = path.into_inner();
+    info!("DELETE /me/subscriptions/{} от {}", id, user.username);
+
+    match delete_user_subscription_logic(&pool, &user.username, id).await {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(err) => {
+            error!("Ошибка удаления подписки #{}: {}", id, err);
+            Err(err)
+        }
+    }
+}
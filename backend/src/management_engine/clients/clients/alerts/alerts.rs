@@ -0,0 +1,136 @@
+use crate::management_engine::clients::requests::alerts::*;
+use crate::management_engine::clients::traits::alerts::{AlertClient, AlertFilter};
+use crate::management_engine::clients::traits::mailbox::{AlertMailbox, AlertNotice};
+use crate::management_engine::models::alerts::alerts::{AlertEvent, AlertEventType, AlertView};
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres, Transaction};
+
+pub struct PgAlertClient {
+    pub pool: PgPool,
+}
+
+/// Применяет изменение алерта, пишет событие истории и рассылает его
+/// подписчикам в одной транзакции. Событие пишется, только если изменение
+/// затронуло алерт.
+async fn change_with_event(
+    mut tx: Transaction<'_, Postgres>,
+    change: sqlx::query::Query<'_, Postgres, sqlx::postgres::PgArguments>,
+    id: i32,
+    event_type: AlertEventType,
+    username: &str,
+    details: Option<&str>,
+) -> Result<bool, sqlx::Error> {
+    if change.execute(&mut *tx).await?.rows_affected() == 0 {
+        return Ok(false);
+    }
+    sqlx::query(INSERT_ALERT_EVENT)
+        .bind(id)
+        .bind(event_type)
+        .bind(username)
+        .bind(details)
+        .execute(&mut *tx)
+        .await?;
+
+    let (note, recipient) = match event_type {
+        AlertEventType::Assigned => (
+            Some(details.map_or_else(|| "Назначение снято".to_string(), |d| format!("Назначен: {}", d))),
+            details,
+        ),
+        _ => (details.map(|d| format!("Комментарий: {}", d)), None),
+    };
+    tx.notify_alert_subscribers(&AlertNotice {
+        events: &[(id, event_type)],
+        actor: Some(username),
+        note: note.as_deref(),
+        recipient,
+    })
+    .await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+#[async_trait]
+impl AlertClient for PgAlertClient {
+    async fn list_alerts(&self, filter: &AlertFilter, limit: i64, offset: i64) -> Result<Vec<AlertView>, sqlx::Error> {
+        sqlx::query_as(SELECT_ALERTS)
+            .bind(filter.status)
+            .bind(filter.active_only)
+            .bind(filter.severity)
+            .bind(filter.device_name.as_deref())
+            .bind(filter.metric_name.as_deref())
+            .bind(filter.assigned_to.as_deref())
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn count_alerts(&self, filter: &AlertFilter) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(COUNT_ALERTS)
+            .bind(filter.status)
+            .bind(filter.active_only)
+            .bind(filter.severity)
+            .bind(filter.device_name.as_deref())
+            .bind(filter.metric_name.as_deref())
+            .bind(filter.assigned_to.as_deref())
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn get_alert(&self, id: i32) -> Result<Option<AlertView>, sqlx::Error> {
+        sqlx::query_as(SELECT_ALERT_BY_ID)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn get_alert_events(&self, id: i32) -> Result<Vec<AlertEvent>, sqlx::Error> {
+        sqlx::query_as(SELECT_ALERT_EVENTS)
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn user_exists(&self, username: &str) -> Result<bool, sqlx::Error> {
+        let id: Option<i32> = sqlx::query_scalar(SELECT_USER_ID)
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(id.is_some())
+    }
+
+    async fn acknowledge_alert(&self, id: i32, username: &str) -> Result<bool, sqlx::Error> {
+        let tx = self.pool.begin().await?;
+        let change = sqlx::query(ACKNOWLEDGE_ALERT).bind(id).bind(username);
+        change_with_event(tx, change, id, AlertEventType::Acknowledged, username, None).await
+    }
+
+    async fn assign_alert(&self, id: i32, assignee: Option<&str>, username: &str) -> Result<bool, sqlx::Error> {
+        let tx = self.pool.begin().await?;
+        let change = sqlx::query(ASSIGN_ALERT).bind(id).bind(assignee);
+        change_with_event(tx, change, id, AlertEventType::Assigned, username, assignee).await
+    }
+
+    async fn resolve_alert(&self, id: i32, username: &str, comment: Option<&str>) -> Result<bool, sqlx::Error> {
+        let tx = self.pool.begin().await?;
+        let change = sqlx::query(RESOLVE_ALERT).bind(id).bind(username);
+        change_with_event(tx, change, id, AlertEventType::Resolved, username, comment).await
+    }
+
+    async fn add_alert_event(
+        &self,
+        id: i32,
+        event_type: AlertEventType,
+        username: &str,
+        details: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(INSERT_ALERT_EVENT)
+            .bind(id)
+            .bind(event_type)
+            .bind(username)
+            .bind(details)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,33 +1,158 @@
 use crate::management_engine::clients::requests::auth::*;
-use crate::management_engine::clients::traits::auth::AuthClient;
+use crate::management_engine::clients::traits::auth::{AuthClient, AuthUnitOfWork};
+use crate::management_engine::models::auth::auth::RefreshToken;
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::NaiveDateTime;
+use sqlx::{PgPool, Postgres, Transaction};
 
 pub struct PgAuthClient {
     pub pool: PgPool,
 }
 
+pub struct PgAuthUnitOfWork {
+    tx: Transaction<'static, Postgres>,
+}
+
 #[async_trait]
 impl AuthClient for PgAuthClient {
-    async fn create_user(
+    async fn begin(&self) -> Result<Box<dyn AuthUnitOfWork>, sqlx::Error> {
+        let tx = self.pool.begin().await?;
+        Ok(Box::new(PgAuthUnitOfWork { tx }))
+    }
+
+    async fn get_user_details(
         &self,
         username: &str,
+    ) -> Result<Option<(i32, String, String, bool)>, sqlx::Error> {
+        let row: Option<(i32, String, String, bool)> = sqlx::query_as(SELECT_USER_DETAILS)
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row)
+    }
+
+    async fn get_role_id(&self, role_name: &str) -> Result<Option<i32>, sqlx::Error> {
+        let row: Option<(i32,)> = sqlx::query_as(SELECT_ROLE_ID_BY_NAME)
+            .bind(role_name)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|r| r.0))
+    }
+
+    async fn get_user_id(&self, username: &str) -> Result<Option<i32>, sqlx::Error> {
+        let row: Option<(i32,)> = sqlx::query_as(SELECT_USER_ID_BY_NAME)
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|r| r.0))
+    }
+
+    async fn create_refresh_token(
+        &self,
+        user_id: i32,
+        family_id: &str,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(INSERT_REFRESH_TOKEN)
+            .bind(user_id)
+            .bind(family_id)
+            .bind(token_hash)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, sqlx::Error> {
+        let row: Option<RefreshToken> = sqlx::query_as(SELECT_REFRESH_TOKEN)
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row)
+    }
+
+    async fn rotate_refresh_token(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(ROTATE_REFRESH_TOKEN)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke_refresh_family(&self, family_id: &str) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(REVOKE_REFRESH_FAMILY)
+            .bind(family_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn log_auth_event(
+        &self,
+        user_id: Option<i32>,
+        action: &str,
+        details: Option<&str>,
+        username: &str,
+        ip_address: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(INSERT_AUTH_LOG)
+            .bind(user_id)
+            .bind(action)
+            .bind(details)
+            .bind(username)
+            .bind(ip_address)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_failed_logins_by_username(
+        &self,
+        username: &str,
+        window_secs: f64,
+    ) -> Result<(i64, Option<f64>), sqlx::Error> {
+        sqlx::query_as(SELECT_FAILED_LOGINS_BY_USERNAME)
+            .bind(username)
+            .bind(window_secs)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn get_failed_logins_by_ip(
+        &self,
+        ip_address: &str,
+        window_secs: f64,
+    ) -> Result<(i64, Option<f64>), sqlx::Error> {
+        sqlx::query_as(SELECT_FAILED_LOGINS_BY_IP)
+            .bind(ip_address)
+            .bind(window_secs)
+            .fetch_one(&self.pool)
+            .await
+    }
+}
+
+#[async_trait]
+impl AuthUnitOfWork for PgAuthUnitOfWork {
+    async fn create_user(
+        &mut self,
+        username: &str,
         password_hash: &str,
         role_id: i32,
         user_info_id: i32,
-    ) -> Result<(), sqlx::Error> {
-        sqlx::query(INSERT_USER)
+    ) -> Result<i32, sqlx::Error> {
+        let row: (i32,) = sqlx::query_as(INSERT_USER)
             .bind(username)
             .bind(password_hash)
             .bind(role_id)
             .bind(user_info_id)
-            .execute(&self.pool)
+            .fetch_one(&mut *self.tx)
             .await?;
-        Ok(())
+        Ok(row.0)
     }
 
     async fn create_user_info(
-        &self,
+        &mut self,
         full_name: &str,
         email: &str,
         phone_number: Option<&str>,
@@ -38,27 +163,12 @@ impl AuthClient for PgAuthClient {
             .bind(email)
             .bind(phone_number)
             .bind(organization)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.tx)
             .await?;
         Ok(row.0)
     }
 
-    async fn get_user_details(
-        &self,
-        username: &str,
-    ) -> Result<Option<(String, String)>, sqlx::Error> {
-        let row: Option<(String, String)> = sqlx::query_as(SELECT_USER_DETAILS)
-            .bind(username)
-            .fetch_optional(&self.pool)
-            .await?;
-        Ok(row)
-    }
-
-    async fn get_role_id(&self, role_name: &str) -> Result<Option<i32>, sqlx::Error> {
-        let row: Option<(i32,)> = sqlx::query_as(SELECT_ROLE_ID_BY_NAME)
-            .bind(role_name)
-            .fetch_optional(&self.pool)
-            .await?;
-        Ok(row.map(|r| r.0))
+    async fn commit(self: Box<Self>) -> Result<(), sqlx::Error> {
+        self.tx.commit().await
     }
 }
@@ -0,0 +1,49 @@
+use crate::management_engine::clients::requests::detectors::*;
+use crate::management_engine::clients::traits::detectors::{DetectorClient, DetectorRule};
+use crate::management_engine::models::thresholds::detectors::DetectorConfig;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgDetectorClient {
+    pub pool: PgPool,
+}
+
+#[async_trait]
+impl DetectorClient for PgDetectorClient {
+    async fn list_detectors(&self) -> Result<Vec<DetectorConfig>, sqlx::Error> {
+        sqlx::query_as(SELECT_DETECTORS).fetch_all(&self.pool).await
+    }
+
+    async fn get_metric_detectors(&self, metric_type_id: i32) -> Result<Vec<DetectorConfig>, sqlx::Error> {
+        sqlx::query_as(SELECT_METRIC_DETECTORS)
+            .bind(metric_type_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn set_metric_detectors(&self, metric_type_id: i32, rules: &[DetectorRule]) -> Result<(), sqlx::Error> {
+        let detectors: Vec<&str> = rules.iter().map(|r| r.detector.as_str()).collect();
+        let windows: Vec<i32> = rules.iter().map(|r| r.window_size).collect();
+        let thresholds: Vec<f64> = rules.iter().map(|r| r.threshold).collect();
+        let alphas: Vec<Option<f64>> = rules.iter().map(|r| r.alpha).collect();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(DELETE_METRIC_DETECTORS)
+            .bind(metric_type_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(INSERT_METRIC_DETECTORS)
+            .bind(metric_type_id)
+            .bind(detectors)
+            .bind(windows)
+            .bind(thresholds)
+            .bind(alphas)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await
+    }
+
+    async fn get_detector_rules(&self) -> Result<Vec<DetectorRule>, sqlx::Error> {
+        sqlx::query_as(SELECT_DETECTOR_RULES).fetch_all(&self.pool).await
+    }
+}
@@ -0,0 +1,149 @@
+use crate::management_engine::clients::requests::mailbox::*;
+use crate::management_engine::clients::traits::mailbox::{AlertMailbox, AlertNotice, MailboxClient, NewSubscription};
+use crate::management_engine::models::mailbox::mailbox::{MailboxMessage, SubscriptionView};
+use async_trait::async_trait;
+use sqlx::{PgConnection, PgPool};
+
+pub struct PgMailboxClient {
+    pub pool: PgPool,
+}
+
+/// Соединение с открытой транзакцией: рассылка фиксируется вместе
+/// с изменением алертов.
+#[async_trait]
+impl AlertMailbox for PgConnection {
+    async fn notify_alert_subscribers(&mut self, notice: &AlertNotice<'_>) -> Result<u64, sqlx::Error> {
+        if notice.events.is_empty() {
+            return Ok(0);
+        }
+        let alert_ids: Vec<i32> = notice.events.iter().map(|(id, _)| *id).collect();
+        let titles: Vec<&str> = notice.events.iter().map(|(_, event)| event.notice_title()).collect();
+
+        let result = sqlx::query(NOTIFY_ALERT_SUBSCRIBERS)
+            .bind(alert_ids)
+            .bind(titles)
+            .bind(notice.actor)
+            .bind(notice.note)
+            .bind(notice.recipient)
+            .execute(self)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl MailboxClient for PgMailboxClient {
+    async fn list_messages(
+        &self,
+        username: &str,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<MailboxMessage>, sqlx::Error> {
+        sqlx::query_as(SELECT_MAILBOX)
+            .bind(username)
+            .bind(unread_only)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn count_messages(&self, username: &str, unread_only: bool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(COUNT_MAILBOX)
+            .bind(username)
+            .bind(unread_only)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn count_unread(&self, username: &str) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(COUNT_UNREAD)
+            .bind(username)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn mark_messages(&self, username: &str, ids: &[i32], read: bool) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(MARK_MAILBOX)
+            .bind(username)
+            .bind(ids)
+            .bind(read)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_messages(&self, username: &str, ids: &[i32]) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(DELETE_MAILBOX)
+            .bind(username)
+            .bind(ids)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn list_user_subscriptions(&self, username: &str) -> Result<Vec<SubscriptionView>, sqlx::Error> {
+        sqlx::query_as(SELECT_USER_SUBSCRIPTIONS)
+            .bind(username)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn list_role_subscriptions(&self, role_name: &str) -> Result<Vec<SubscriptionView>, sqlx::Error> {
+        sqlx::query_as(SELECT_ROLE_SUBSCRIPTIONS)
+            .bind(role_name)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn get_subscription(&self, id: i32) -> Result<Option<SubscriptionView>, sqlx::Error> {
+        sqlx::query_as(SELECT_SUBSCRIPTION_BY_ID)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn get_device_id(&self, device_name: &str) -> Result<Option<i32>, sqlx::Error> {
+        sqlx::query_scalar(SELECT_DEVICE_ID_BY_NAME)
+            .bind(device_name)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn get_role_id(&self, role_name: &str) -> Result<Option<i32>, sqlx::Error> {
+        sqlx::query_scalar(SELECT_ROLE_ID_BY_NAME)
+            .bind(role_name)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn create_subscription(&self, subscription: &NewSubscription) -> Result<i32, sqlx::Error> {
+        sqlx::query_scalar(INSERT_SUBSCRIPTION)
+            .bind(subscription.username.as_deref())
+            .bind(subscription.role_id)
+            .bind(subscription.device_id)
+            .bind(subscription.location.as_deref())
+            .bind(subscription.min_severity)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn delete_user_subscription(&self, id: i32, username: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(DELETE_USER_SUBSCRIPTION)
+            .bind(id)
+            .bind(username)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_role_subscription(&self, id: i32, role_name: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(DELETE_ROLE_SUBSCRIPTION)
+            .bind(id)
+            .bind(role_name)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
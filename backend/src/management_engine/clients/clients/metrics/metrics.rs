@@ -0,0 +1,36 @@
+use crate::management_engine::clients::requests::metrics::*;
+use crate::management_engine::clients::traits::metrics::MetricClient;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgMetricClient {
+    pub pool: PgPool,
+}
+
+#[async_trait]
+impl MetricClient for PgMetricClient {
+    async fn get_metric_type_id(&self, name: &str) -> Result<Option<i32>, sqlx::Error> {
+        let row: Option<(i32,)> = sqlx::query_as(SELECT_METRIC_TYPE_ID_BY_NAME)
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|r| r.0))
+    }
+
+    async fn metric_type_exists(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let row: Option<(i32,)> = sqlx::query_as(SELECT_METRIC_TYPE_ID)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.is_some())
+    }
+
+    async fn register_metric_type(&self, name: &str, description: Option<&str>) -> Result<i32, sqlx::Error> {
+        let row: (i32,) = sqlx::query_as(INSERT_METRIC_TYPE)
+            .bind(name)
+            .bind(description)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.0)
+    }
+}
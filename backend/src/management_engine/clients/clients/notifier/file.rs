@@ -0,0 +1,61 @@
+use crate::management_engine::clients::traits::notifier::{Notification, Notifier, NotifyError};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::info;
+
+/// Уведомления для локальной разработки: дописываются в файл
+/// или, если файл не задан, выводятся в лог.
+pub struct FileNotifier {
+    path: Option<PathBuf>,
+    // Последовательная запись, чтобы сообщения не перемешивались
+    lock: Mutex<()>,
+}
+
+impl FileNotifier {
+    pub fn to_file(path: impl Into<PathBuf>) -> Self {
+        FileNotifier {
+            path: Some(path.into()),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn to_log() -> Self {
+        FileNotifier {
+            path: None,
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for FileNotifier {
+    async fn send(&self, notification: &Notification) -> Result<(), NotifyError> {
+        let Some(path) = &self.path else {
+            info!(
+                "Уведомление для {}: {}\n{}",
+                notification.to, notification.subject, notification.body
+            );
+            return Ok(());
+        };
+
+        let entry = format!(
+            "=== {} ===\nTo: {}\nSubject: {}\n\n{}\n\n",
+            Utc::now().to_rfc3339(),
+            notification.to,
+            notification.subject,
+            notification.body
+        );
+
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await?;
+        file.write_all(entry.as_bytes()).await?;
+        Ok(())
+    }
+}
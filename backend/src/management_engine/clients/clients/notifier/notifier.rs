@@ -0,0 +1,49 @@
+use crate::management_engine::clients::clients::notifier::file::FileNotifier;
+use crate::management_engine::clients::clients::notifier::smtp::SmtpNotifier;
+use crate::management_engine::clients::traits::notifier::{Notifier, NotifyError};
+use std::env;
+use std::sync::OnceLock;
+use tracing::info;
+
+// =========================================================
+// ВЫБОР КАНАЛА УВЕДОМЛЕНИЙ
+// =========================================================
+//
+// NOTIFIER            log (по умолчанию) | file | smtp
+// NOTIFIER_FILE_PATH  файл для NOTIFIER=file ("notifications.log")
+// SMTP_*              настройки для NOTIFIER=smtp, см. smtp.rs
+
+static NOTIFIER: OnceLock<Box<dyn Notifier>> = OnceLock::new();
+
+pub fn notifier_from_env() -> Result<Box<dyn Notifier>, NotifyError> {
+    let kind = env::var("NOTIFIER").unwrap_or_else(|_| "log".to_string());
+    let notifier: Box<dyn Notifier> = match kind.as_str() {
+        "smtp" => Box::new(SmtpNotifier::from_env()?),
+        "file" => Box::new(FileNotifier::to_file(
+            env::var("NOTIFIER_FILE_PATH").unwrap_or_else(|_| "notifications.log".to_string()),
+        )),
+        "log" => Box::new(FileNotifier::to_log()),
+        other => {
+            return Err(NotifyError::Transport(format!(
+                "неизвестный NOTIFIER '{}'",
+                other
+            )));
+        }
+    };
+    info!("Канал уведомлений: {}", kind);
+    Ok(notifier)
+}
+
+/// Устанавливает канал уведомлений при старте приложения.
+pub fn init_notifier(notifier: Box<dyn Notifier>) {
+    if NOTIFIER.set(notifier).is_err() {
+        panic!("канал уведомлений уже инициализирован");
+    }
+}
+
+pub fn notifier() -> &'static dyn Notifier {
+    NOTIFIER
+        .get()
+        .expect("канал уведомлений не инициализирован: вызовите init_notifier при старте")
+        .as_ref()
+}
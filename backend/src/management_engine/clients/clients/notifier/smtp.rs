@@ -0,0 +1,82 @@
+use crate::management_engine::clients::traits::notifier::{Notification, Notifier, NotifyError};
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::env;
+
+// =========================================================
+// SMTP
+// =========================================================
+//
+// SMTP_HOST      адрес сервера (обязателен)
+// SMTP_PORT      порт (по умолчанию зависит от SMTP_TLS: 465 / 587 / 25)
+// SMTP_TLS       tls | starttls (по умолчанию) | none
+// SMTP_USERNAME  логин (необязателен)
+// SMTP_PASSWORD  пароль
+// SMTP_FROM      адрес отправителя, например "Мониторинг <noreply@example.com>"
+
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpNotifier {
+    pub fn from_env() -> Result<Self, NotifyError> {
+        let host = env::var("SMTP_HOST")
+            .map_err(|_| NotifyError::Transport("SMTP_HOST не задан".to_string()))?;
+        let from_raw = env::var("SMTP_FROM")
+            .map_err(|_| NotifyError::Transport("SMTP_FROM не задан".to_string()))?;
+        let from: Mailbox = from_raw
+            .parse()
+            .map_err(|_| NotifyError::InvalidAddress(from_raw.clone()))?;
+
+        let mut builder = match env::var("SMTP_TLS").as_deref() {
+            Ok("tls") => AsyncSmtpTransport::<Tokio1Executor>::relay(&host),
+            Err(_) | Ok("starttls") => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host),
+            Ok("none") => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host)),
+            Ok(other) => {
+                return Err(NotifyError::Transport(format!("неизвестный SMTP_TLS '{}'", other)));
+            }
+        }
+        .map_err(|e| NotifyError::Transport(e.to_string()))?;
+
+        if let Some(port) = env::var("SMTP_PORT").ok().and_then(|p| p.parse().ok()) {
+            builder = builder.port(port);
+        }
+        if let Ok(username) = env::var("SMTP_USERNAME") {
+            let password = env::var("SMTP_PASSWORD").unwrap_or_default();
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(SmtpNotifier {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+    async fn send(&self, notification: &Notification) -> Result<(), NotifyError> {
+        let to: Mailbox = notification
+            .to
+            .parse()
+            .map_err(|_| NotifyError::InvalidAddress(notification.to.clone()))?;
+
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&notification.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(notification.body.clone())
+            .map_err(|e| NotifyError::Transport(e.to_string()))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| NotifyError::Transport(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::management_engine::clients::requests::password_reset::*;
+use crate::management_engine::clients::traits::password_reset::PasswordResetClient;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+
+pub struct PgPasswordResetClient {
+    pub pool: PgPool,
+}
+
+#[async_trait]
+impl PasswordResetClient for PgPasswordResetClient {
+    async fn get_active_users_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Vec<(i32, String, String)>, sqlx::Error> {
+        sqlx::query_as(SELECT_ACTIVE_USERS_BY_EMAIL)
+            .bind(email)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn create_reset_token(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(INVALIDATE_RESET_TOKENS)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(INSERT_RESET_TOKEN)
+            .bind(user_id)
+            .bind(token_hash)
+            .bind(expires_at)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await
+    }
+
+    async fn reset_password(
+        &self,
+        token_hash: &str,
+        password_hash: &str,
+    ) -> Result<Option<(i32, String)>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let row: Option<(i32, String)> = sqlx::query_as(CONSUME_RESET_TOKEN)
+            .bind(token_hash)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some((user_id, username)) = row else {
+            return Ok(None);
+        };
+
+        sqlx::query(UPDATE_PASSWORD_HASH)
+            .bind(user_id)
+            .bind(password_hash)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(Some((user_id, username)))
+    }
+}
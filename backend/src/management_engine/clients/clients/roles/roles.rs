@@ -0,0 +1,77 @@
+use crate::management_engine::clients::requests::roles::*;
+use crate::management_engine::clients::traits::roles::RoleClient;
+use crate::management_engine::models::roles::roles::RoleWithPermissions;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgRoleClient {
+    pub pool: PgPool,
+}
+
+#[async_trait]
+impl RoleClient for PgRoleClient {
+    async fn list_roles(&self) -> Result<Vec<RoleWithPermissions>, sqlx::Error> {
+        sqlx::query_as(SELECT_ROLES).fetch_all(&self.pool).await
+    }
+
+    async fn get_role(&self, role_name: &str) -> Result<Option<RoleWithPermissions>, sqlx::Error> {
+        sqlx::query_as(SELECT_ROLE_BY_NAME)
+            .bind(role_name)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn get_role_permissions(&self, role_name: &str) -> Result<Vec<String>, sqlx::Error> {
+        let rows: Vec<(String,)> = sqlx::query_as(SELECT_ROLE_PERMISSIONS)
+            .bind(role_name)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    async fn create_role(&self, role_name: &str, permissions: &[String]) -> Result<i32, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let row: (i32,) = sqlx::query_as(INSERT_ROLE)
+            .bind(role_name)
+            .fetch_one(&mut *tx)
+            .await?;
+        sqlx::query(INSERT_ROLE_PERMISSIONS)
+            .bind(row.0)
+            .bind(permissions)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(row.0)
+    }
+
+    async fn set_role_permissions(&self, role_id: i32, permissions: &[String]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(DELETE_ROLE_PERMISSIONS)
+            .bind(role_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(INSERT_ROLE_PERMISSIONS)
+            .bind(role_id)
+            .bind(permissions)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await
+    }
+
+    async fn assign_user_role(&self, username: &str, role_name: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(UPDATE_USER_ROLE)
+            .bind(username)
+            .bind(role_name)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke_user_role(&self, username: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(CLEAR_USER_ROLE)
+            .bind(username)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
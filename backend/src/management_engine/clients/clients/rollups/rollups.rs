@@ -0,0 +1,89 @@
+use crate::management_engine::clients::requests::rollups::*;
+use crate::management_engine::clients::traits::rollups::{RollupClient, RollupUnitOfWork};
+use crate::management_engine::models::telemetry::telemetry::RollupLevel;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{PgPool, Postgres, Transaction};
+
+pub struct PgRollupClient {
+    pub pool: PgPool,
+}
+
+pub struct PgRollupUnitOfWork {
+    tx: Transaction<'static, Postgres>,
+}
+
+#[async_trait]
+impl RollupClient for PgRollupClient {
+    async fn begin(&self) -> Result<Box<dyn RollupUnitOfWork>, sqlx::Error> {
+        let tx = self.pool.begin().await?;
+        Ok(Box::new(PgRollupUnitOfWork { tx }))
+    }
+
+    async fn delete_expired_telemetry(
+        &self,
+        before: NaiveDateTime,
+        rolled_until: NaiveDateTime,
+        limit: i64,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(DELETE_EXPIRED_TELEMETRY)
+            .bind(before)
+            .bind(rolled_until)
+            .bind(limit)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_expired_rollup(&self, level: RollupLevel, before: NaiveDateTime) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(&DELETE_EXPIRED_ROLLUP.replace("{table}", level.table()))
+            .bind(before)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl RollupUnitOfWork for PgRollupUnitOfWork {
+    async fn try_lock(&mut self) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(TRY_ROLLUP_LOCK)
+            .fetch_one(&mut *self.tx)
+            .await
+    }
+
+    async fn get_watermark(&mut self, level: RollupLevel) -> Result<Option<NaiveDateTime>, sqlx::Error> {
+        sqlx::query_scalar(SELECT_ROLLUP_WATERMARK)
+            .bind(level.name())
+            .fetch_optional(&mut *self.tx)
+            .await
+    }
+
+    async fn rollup(
+        &mut self,
+        level: RollupLevel,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(&UPSERT_ROLLUP.replace("{table}", level.table()))
+            .bind(level.pg_interval())
+            .bind(from)
+            .bind(to)
+            .execute(&mut *self.tx)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn set_watermark(&mut self, level: RollupLevel, until: NaiveDateTime) -> Result<(), sqlx::Error> {
+        sqlx::query(UPSERT_ROLLUP_WATERMARK)
+            .bind(level.name())
+            .bind(until)
+            .execute(&mut *self.tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), sqlx::Error> {
+        self.tx.commit().await
+    }
+}
@@ -0,0 +1,238 @@
+use crate::management_engine::clients::requests::alerts::{
+    LOCK_ACTIVE_ALERTS, OPEN_ALERT, UPDATE_ACTIVE_ALERTS,
+};
+use crate::management_engine::clients::requests::escape_like;
+use crate::management_engine::clients::requests::telemetry::*;
+use crate::management_engine::clients::traits::alerts::{ActiveAlert, AlertUpdate, NewAlert};
+use crate::management_engine::clients::traits::mailbox::AlertMailbox;
+use crate::management_engine::clients::traits::telemetry::{
+    AggregateFilter, DeviceHealthRow, DeviceRow, MetricHealth, RecentValue, TelemetryClient,
+    TelemetryFilter, TelemetryRow, TelemetryUnitOfWork,
+};
+use crate::management_engine::models::telemetry::telemetry::{
+    AggregateRow, BucketSize, DeviceStatus, TelemetryRecord,
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+pub struct PgTelemetryClient {
+    pub pool: PgPool,
+}
+
+pub struct PgTelemetryUnitOfWork {
+    tx: Transaction<'static, Postgres>,
+}
+
+#[async_trait]
+impl TelemetryClient for PgTelemetryClient {
+    async fn begin(&self) -> Result<Box<dyn TelemetryUnitOfWork>, sqlx::Error> {
+        let tx = self.pool.begin().await?;
+        Ok(Box::new(PgTelemetryUnitOfWork { tx }))
+    }
+
+    async fn list_telemetry(
+        &self,
+        filter: &TelemetryFilter,
+        limit: i64,
+    ) -> Result<Vec<TelemetryRecord>, sqlx::Error> {
+        sqlx::query_as(SELECT_TELEMETRY_PAGE)
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(filter.device_id)
+            .bind(filter.device_name.as_deref())
+            .bind(filter.metric_type_id)
+            .bind(filter.metric_name.as_deref())
+            .bind(filter.anomaly_only)
+            .bind(filter.location.as_deref().map(escape_like))
+            .bind(filter.search.as_deref().map(escape_like))
+            .bind(filter.after.map(|(recorded_at, _)| recorded_at))
+            .bind(filter.after.map(|(_, id)| id))
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn aggregate_telemetry(
+        &self,
+        filter: &AggregateFilter,
+        bucket: BucketSize,
+    ) -> Result<Vec<AggregateRow>, sqlx::Error> {
+        let level = bucket.rollup_level();
+        sqlx::query_as(&SELECT_TELEMETRY_AGGREGATES.replace("{rollup}", level.table()))
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(bucket.pg_interval())
+            .bind(filter.device_id)
+            .bind(filter.device_name.as_deref())
+            .bind(filter.metric_type_id)
+            .bind(filter.metric_name.as_deref())
+            .bind(level.pg_interval())
+            .bind(level.name())
+            .bind(filter.rollup_since)
+            .fetch_all(&self.pool)
+            .await
+    }
+}
+
+#[async_trait]
+impl TelemetryUnitOfWork for PgTelemetryUnitOfWork {
+    async fn upsert_devices(
+        &mut self,
+        devices: &[DeviceRow],
+    ) -> Result<Vec<(String, i32, Option<String>)>, sqlx::Error> {
+        let names: Vec<&str> = devices.iter().map(|d| d.device_name.as_str()).collect();
+        let ips: Vec<&str> = devices.iter().map(|d| d.ip_address.as_str()).collect();
+        let locations: Vec<Option<&str>> = devices.iter().map(|d| d.location.as_deref()).collect();
+
+        sqlx::query_as(UPSERT_DEVICES)
+            .bind(names)
+            .bind(ips)
+            .bind(locations)
+            .fetch_all(&mut *self.tx)
+            .await
+    }
+
+    async fn insert_telemetry(&mut self, rows: &[TelemetryRow]) -> Result<Vec<Option<Uuid>>, sqlx::Error> {
+        let device_ids: Vec<i32> = rows.iter().map(|r| r.device_id).collect();
+        let metric_type_ids: Vec<i32> = rows.iter().map(|r| r.metric_type_id).collect();
+        let values: Vec<f64> = rows.iter().map(|r| r.metric_value).collect();
+        let anomalies: Vec<bool> = rows.iter().map(|r| r.is_anomaly).collect();
+        let severities: Vec<&str> = rows.iter().map(|r| r.severity.as_str()).collect();
+        let descriptions: Vec<Option<&str>> =
+            rows.iter().map(|r| r.action_description.as_deref()).collect();
+        let recorded_at: Vec<NaiveDateTime> = rows.iter().map(|r| r.recorded_at).collect();
+        let received_at: Vec<NaiveDateTime> = rows.iter().map(|r| r.received_at).collect();
+        let event_ids: Vec<Option<Uuid>> = rows.iter().map(|r| r.event_id).collect();
+        let detectors: Vec<Option<&str>> = rows.iter().map(|r| r.anomaly_detector).collect();
+        let scores: Vec<Option<f64>> = rows.iter().map(|r| r.anomaly_score).collect();
+
+        sqlx::query_scalar(INSERT_TELEMETRY_BATCH)
+            .bind(device_ids)
+            .bind(metric_type_ids)
+            .bind(values)
+            .bind(anomalies)
+            .bind(severities)
+            .bind(descriptions)
+            .bind(recorded_at)
+            .bind(received_at)
+            .bind(event_ids)
+            .bind(detectors)
+            .bind(scores)
+            .fetch_all(&mut *self.tx)
+            .await
+    }
+
+    async fn recent_values(&mut self, windows: &[(i32, i32, i32)]) -> Result<Vec<RecentValue>, sqlx::Error> {
+        let device_ids: Vec<i32> = windows.iter().map(|(d, _, _)| *d).collect();
+        let metric_type_ids: Vec<i32> = windows.iter().map(|(_, m, _)| *m).collect();
+        let sizes: Vec<i32> = windows.iter().map(|(_, _, n)| *n).collect();
+
+        sqlx::query_as(SELECT_RECENT_VALUES)
+            .bind(device_ids)
+            .bind(metric_type_ids)
+            .bind(sizes)
+            .fetch_all(&mut *self.tx)
+            .await
+    }
+
+    async fn lock_metric_health(&mut self, pairs: &[(i32, i32)]) -> Result<Vec<MetricHealth>, sqlx::Error> {
+        let device_ids: Vec<i32> = pairs.iter().map(|(d, _)| *d).collect();
+        let metric_type_ids: Vec<i32> = pairs.iter().map(|(_, m)| *m).collect();
+
+        sqlx::query_as(LOCK_METRIC_HEALTH)
+            .bind(device_ids)
+            .bind(metric_type_ids)
+            .fetch_all(&mut *self.tx)
+            .await
+    }
+
+    async fn upsert_metric_health(&mut self, health: &[MetricHealth]) -> Result<(), sqlx::Error> {
+        let device_ids: Vec<i32> = health.iter().map(|h| h.device_id).collect();
+        let metric_type_ids: Vec<i32> = health.iter().map(|h| h.metric_type_id).collect();
+        let severities: Vec<&str> = health.iter().map(|h| h.severity.as_str()).collect();
+        let streaks: Vec<i32> = health.iter().map(|h| h.streak).collect();
+
+        sqlx::query(UPSERT_METRIC_HEALTH)
+            .bind(device_ids)
+            .bind(metric_type_ids)
+            .bind(severities)
+            .bind(streaks)
+            .execute(&mut *self.tx)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_device_health(&mut self, device_ids: &[i32]) -> Result<Vec<DeviceHealthRow>, sqlx::Error> {
+        sqlx::query_as(SELECT_DEVICE_HEALTH)
+            .bind(device_ids)
+            .fetch_all(&mut *self.tx)
+            .await
+    }
+
+    async fn set_device_status(&mut self, device_id: i32, status: DeviceStatus) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(UPDATE_DEVICE_STATUS)
+            .bind(device_id)
+            .bind(status)
+            .execute(&mut *self.tx)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn lock_active_alerts(&mut self, pairs: &[(i32, i32)]) -> Result<Vec<ActiveAlert>, sqlx::Error> {
+        let device_ids: Vec<i32> = pairs.iter().map(|(d, _)| *d).collect();
+        let metric_type_ids: Vec<i32> = pairs.iter().map(|(_, m)| *m).collect();
+
+        sqlx::query_as(LOCK_ACTIVE_ALERTS)
+            .bind(device_ids)
+            .bind(metric_type_ids)
+            .fetch_all(&mut *self.tx)
+            .await
+    }
+
+    async fn open_alert(&mut self, alert: &NewAlert) -> Result<i32, sqlx::Error> {
+        sqlx::query_scalar(OPEN_ALERT)
+            .bind(alert.device_id)
+            .bind(alert.metric_type_id)
+            .bind(alert.severity)
+            .bind(alert.status)
+            .bind(alert.detector.as_deref())
+            .bind(alert.first_value)
+            .bind(alert.last_value)
+            .bind(alert.sample_count)
+            .bind(alert.healthy_streak)
+            .bind(alert.opened_at)
+            .bind(alert.last_seen_at)
+            .fetch_one(&mut *self.tx)
+            .await
+    }
+
+    async fn update_active_alerts(&mut self, updates: &[AlertUpdate]) -> Result<(), sqlx::Error> {
+        let ids: Vec<i32> = updates.iter().map(|u| u.id).collect();
+        let last_values: Vec<Option<f64>> = updates.iter().map(|u| u.last_value).collect();
+        let last_seen: Vec<Option<NaiveDateTime>> = updates.iter().map(|u| u.last_seen_at).collect();
+        let new_samples: Vec<i32> = updates.iter().map(|u| u.new_samples).collect();
+        let streaks: Vec<i32> = updates.iter().map(|u| u.healthy_streak).collect();
+        let resolved: Vec<bool> = updates.iter().map(|u| u.resolved).collect();
+
+        sqlx::query(UPDATE_ACTIVE_ALERTS)
+            .bind(ids)
+            .bind(last_values)
+            .bind(last_seen)
+            .bind(new_samples)
+            .bind(streaks)
+            .bind(resolved)
+            .execute(&mut *self.tx)
+            .await?;
+        Ok(())
+    }
+
+    fn mailbox(&mut self) -> &mut dyn AlertMailbox {
+        &mut *self.tx
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), sqlx::Error> {
+        self.tx.commit().await
+    }
+}
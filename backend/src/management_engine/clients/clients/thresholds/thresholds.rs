@@ -0,0 +1,76 @@
+use crate::management_engine::clients::requests::thresholds::*;
+use crate::management_engine::clients::traits::thresholds::{
+    ThresholdClient, ThresholdFilter, ThresholdRow, ThresholdRule,
+};
+use crate::management_engine::models::thresholds::thresholds::ThresholdView;
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgThresholdClient {
+    pub pool: PgPool,
+}
+
+#[async_trait]
+impl ThresholdClient for PgThresholdClient {
+    async fn list_thresholds(&self, filter: &ThresholdFilter) -> Result<Vec<ThresholdView>, sqlx::Error> {
+        sqlx::query_as(SELECT_THRESHOLDS)
+            .bind(filter.metric_name.as_deref())
+            .bind(filter.device_name.as_deref())
+            .bind(filter.location.as_deref())
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn get_threshold(&self, id: i32) -> Result<Option<ThresholdView>, sqlx::Error> {
+        sqlx::query_as(SELECT_THRESHOLD_BY_ID)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn get_device_id(&self, device_name: &str) -> Result<Option<i32>, sqlx::Error> {
+        sqlx::query_scalar(SELECT_DEVICE_ID_BY_NAME)
+            .bind(device_name)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn create_threshold(&self, row: &ThresholdRow, created_by: &str) -> Result<i32, sqlx::Error> {
+        sqlx::query_scalar(INSERT_THRESHOLD)
+            .bind(row.metric_type_id)
+            .bind(row.device_id)
+            .bind(row.location.as_deref())
+            .bind(row.comparison)
+            .bind(row.warning_level)
+            .bind(row.critical_level)
+            .bind(created_by)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn update_threshold(&self, id: i32, row: &ThresholdRow) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(UPDATE_THRESHOLD)
+            .bind(id)
+            .bind(row.metric_type_id)
+            .bind(row.device_id)
+            .bind(row.location.as_deref())
+            .bind(row.comparison)
+            .bind(row.warning_level)
+            .bind(row.critical_level)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn delete_threshold(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(DELETE_THRESHOLD)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn get_threshold_rules(&self) -> Result<Vec<ThresholdRule>, sqlx::Error> {
+        sqlx::query_as(SELECT_THRESHOLD_RULES).fetch_all(&self.pool).await
+    }
+}
@@ -0,0 +1,128 @@
+use crate::management_engine::clients::requests::escape_like;
+use crate::management_engine::clients::requests::users::*;
+use crate::management_engine::clients::traits::users::UserClient;
+use crate::management_engine::models::users::users::{UserProfile, UserSummary};
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+pub struct PgUserClient {
+    pub pool: PgPool,
+}
+
+#[async_trait]
+impl UserClient for PgUserClient {
+    async fn get_profile(&self, username: &str) -> Result<Option<UserProfile>, sqlx::Error> {
+        sqlx::query_as(SELECT_USER_PROFILE)
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn update_profile(
+        &self,
+        username: &str,
+        full_name: Option<&str>,
+        email: Option<&str>,
+        phone_number: Option<Option<&str>>,
+        organization: Option<Option<&str>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(UPDATE_USER_INFO)
+            .bind(username)
+            .bind(full_name)
+            .bind(email)
+            .bind(phone_number.is_some())
+            .bind(phone_number.flatten())
+            .bind(organization.is_some())
+            .bind(organization.flatten())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_password_hash(&self, user_id: i32, password_hash: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(UPDATE_PASSWORD_HASH)
+            .bind(user_id)
+            .bind(password_hash)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn revoke_user_refresh_tokens(&self, user_id: i32) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(REVOKE_USER_REFRESH_TOKENS)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn list_users(
+        &self,
+        search: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<UserSummary>, sqlx::Error> {
+        sqlx::query_as(SELECT_USERS)
+            .bind(search.map(escape_like))
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn count_users(&self, search: Option<&str>) -> Result<i64, sqlx::Error> {
+        let row: (i64,) = sqlx::query_as(COUNT_USERS)
+            .bind(search.map(escape_like))
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.0)
+    }
+
+    async fn set_user_disabled(&self, username: &str, disabled: bool) -> Result<Option<i32>, sqlx::Error> {
+        let row: Option<(i32,)> = sqlx::query_as(SET_USER_DISABLED)
+            .bind(username)
+            .bind(disabled)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|r| r.0))
+    }
+
+    async fn is_user_active(&self, username: &str) -> Result<Option<bool>, sqlx::Error> {
+        let row: Option<(bool,)> = sqlx::query_as(SELECT_USER_ACTIVE)
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|r| r.0))
+    }
+
+    async fn delete_user(&self, username: &str) -> Result<Option<i32>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let row: Option<(i32, Option<i32>)> = sqlx::query_as(SELECT_USER_FOR_DELETE)
+            .bind(username)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some((user_id, user_info_id)) = row else {
+            return Ok(None);
+        };
+
+        for statement in [
+            DETACH_USER_LOGS,
+            DETACH_USER_DEVICES,
+            DETACH_USER_THRESHOLDS,
+            DELETE_USER_MAILBOXES,
+            DELETE_USER,
+        ] {
+            sqlx::query(statement).bind(user_id).execute(&mut *tx).await?;
+        }
+        if let Some(user_info_id) = user_info_id {
+            sqlx::query(DELETE_USER_INFO)
+                .bind(user_info_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(Some(user_id))
+    }
+}
@@ -0,0 +1,138 @@
+// $1 status, $2 только незакрытые, $3 severity, $4 device_name, $5 metric_name,
+// $6 assigned_to — NULL отключает фильтр.
+pub const SELECT_ALERTS: &str = r#"
+SELECT a.id, a.device_id, d.device_name, a.metric_type_id, mt.name AS metric_name,
+       a.severity, a.status, a.detector, a.first_value, a.last_value, a.sample_count,
+       a.opened_at, a.last_seen_at,
+       ack.username AS acknowledged_by, a.acknowledged_at,
+       asg.username AS assigned_to,
+       res.username AS resolved_by, a.resolved_at
+FROM alerts a
+JOIN devices d ON d.id = a.device_id
+JOIN metric_types mt ON mt.id = a.metric_type_id
+LEFT JOIN users ack ON ack.id = a.acknowledged_by
+LEFT JOIN users asg ON asg.id = a.assigned_to
+LEFT JOIN users res ON res.id = a.resolved_by
+WHERE ($1::alert_status IS NULL OR a.status = $1)
+  AND (NOT $2::bool OR a.status <> 'resolved')
+  AND ($3::telemetry_severity IS NULL OR a.severity = $3)
+  AND ($4::text IS NULL OR d.device_name = $4)
+  AND ($5::text IS NULL OR mt.name = $5)
+  AND ($6::text IS NULL OR asg.username = $6)
+ORDER BY a.opened_at DESC, a.id DESC
+LIMIT $7 OFFSET $8
+"#;
+
+pub const COUNT_ALERTS: &str = r#"
+SELECT COUNT(*)
+FROM alerts a
+JOIN devices d ON d.id = a.device_id
+JOIN metric_types mt ON mt.id = a.metric_type_id
+LEFT JOIN users asg ON asg.id = a.assigned_to
+WHERE ($1::alert_status IS NULL OR a.status = $1)
+  AND (NOT $2::bool OR a.status <> 'resolved')
+  AND ($3::telemetry_severity IS NULL OR a.severity = $3)
+  AND ($4::text IS NULL OR d.device_name = $4)
+  AND ($5::text IS NULL OR mt.name = $5)
+  AND ($6::text IS NULL OR asg.username = $6)
+"#;
+
+pub const SELECT_ALERT_BY_ID: &str = r#"
+SELECT a.id, a.device_id, d.device_name, a.metric_type_id, mt.name AS metric_name,
+       a.severity, a.status, a.detector, a.first_value, a.last_value, a.sample_count,
+       a.opened_at, a.last_seen_at,
+       ack.username AS acknowledged_by, a.acknowledged_at,
+       asg.username AS assigned_to,
+       res.username AS resolved_by, a.resolved_at
+FROM alerts a
+JOIN devices d ON d.id = a.device_id
+JOIN metric_types mt ON mt.id = a.metric_type_id
+LEFT JOIN users ack ON ack.id = a.acknowledged_by
+LEFT JOIN users asg ON asg.id = a.assigned_to
+LEFT JOIN users res ON res.id = a.resolved_by
+WHERE a.id = $1
+"#;
+
+pub const SELECT_ALERT_EVENTS: &str = r#"
+SELECT e.id, e.event_type, u.username, e.details, e.created_at
+FROM alert_events e
+LEFT JOIN users u ON u.id = e.user_id
+WHERE e.alert_id = $1
+ORDER BY e.id
+"#;
+
+pub const SELECT_USER_ID: &str = "SELECT id FROM users WHERE username = $1";
+
+pub const ACKNOWLEDGE_ALERT: &str = r#"
+UPDATE alerts
+SET status = 'acknowledged',
+    acknowledged_by = (SELECT id FROM users WHERE username = $2),
+    acknowledged_at = now()
+WHERE id = $1 AND status = 'open'
+"#;
+
+pub const ASSIGN_ALERT: &str = r#"
+UPDATE alerts
+SET assigned_to = (SELECT id FROM users WHERE username = $2)
+WHERE id = $1 AND status <> 'resolved'
+"#;
+
+pub const RESOLVE_ALERT: &str = r#"
+UPDATE alerts
+SET status = 'resolved',
+    resolved_by = (SELECT id FROM users WHERE username = $2),
+    resolved_at = now()
+WHERE id = $1 AND status <> 'resolved'
+"#;
+
+pub const INSERT_ALERT_EVENT: &str = r#"
+INSERT INTO alert_events (alert_id, event_type, user_id, details)
+VALUES ($1, $2, (SELECT id FROM users WHERE username = $3), $4)
+"#;
+
+// Незакрытые алерты пар ($1[i], $2[i]), заблокированные до конца транзакции.
+pub const LOCK_ACTIVE_ALERTS: &str = r#"
+SELECT a.id, a.device_id, a.metric_type_id, a.severity, a.healthy_streak
+FROM alerts a
+JOIN UNNEST($1::int[], $2::int[]) AS p(device_id, metric_type_id)
+  ON p.device_id = a.device_id AND p.metric_type_id = a.metric_type_id
+WHERE a.status <> 'resolved'
+FOR UPDATE OF a
+"#;
+
+// Новый алерт и события его истории; алерт может закрыться в той же пачке.
+pub const OPEN_ALERT: &str = r#"
+WITH a AS (
+    INSERT INTO alerts
+        (device_id, metric_type_id, severity, status, detector, first_value, last_value,
+         sample_count, healthy_streak, opened_at, last_seen_at, resolved_at)
+    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
+            CASE WHEN $4 = 'resolved'::alert_status THEN now() END)
+    RETURNING id, status
+), events AS (
+    INSERT INTO alert_events (alert_id, event_type)
+    SELECT id, 'opened'::alert_event_type FROM a
+    UNION ALL
+    SELECT id, 'auto_resolved'::alert_event_type FROM a WHERE status = 'resolved'
+)
+SELECT id FROM a
+"#;
+
+// Учитывает новые измерения в незакрытых алертах; $6 — закрыть автоматически.
+pub const UPDATE_ACTIVE_ALERTS: &str = r#"
+WITH u AS (
+    UPDATE alerts a
+    SET last_value     = COALESCE(v.last_value, a.last_value),
+        last_seen_at   = COALESCE(v.last_seen_at, a.last_seen_at),
+        sample_count   = a.sample_count + v.new_samples,
+        healthy_streak = v.healthy_streak,
+        status         = CASE WHEN v.resolved THEN 'resolved'::alert_status ELSE a.status END,
+        resolved_at    = CASE WHEN v.resolved THEN now() ELSE a.resolved_at END
+    FROM UNNEST($1::int[], $2::float8[], $3::timestamp[], $4::int[], $5::int[], $6::bool[])
+        AS v(id, last_value, last_seen_at, new_samples, healthy_streak, resolved)
+    WHERE a.id = v.id
+    RETURNING a.id, v.resolved
+)
+INSERT INTO alert_events (alert_id, event_type)
+SELECT id, 'auto_resolved'::alert_event_type FROM u WHERE resolved
+"#;
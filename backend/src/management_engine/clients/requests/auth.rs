@@ -1,6 +1,6 @@
-pub const INSERT_USER: &str =
-    "INSERT INTO users (username, password_hash, role_id, user_info_id) VALUES ($1, $2, $3, $4)";
-pub const SELECT_USER_DETAILS: &str = "SELECT u.password_hash, r.role_name FROM users u JOIN roles r ON u.role_id = r.id WHERE u.username = $1";
+pub const INSERT_USER: &str = "INSERT INTO users (username, password_hash, role_id, user_info_id) VALUES ($1, $2, $3, $4) RETURNING id";
+// Пользователь без роли (роль отозвана) получает пустое имя роли и не имеет прав
+pub const SELECT_USER_DETAILS: &str = "SELECT u.id, u.password_hash, COALESCE(r.role_name, ''), u.disabled_at IS NOT NULL FROM users u LEFT JOIN roles r ON u.role_id = r.id WHERE u.username = $1";
 pub const SELECT_ROLE_ID_BY_NAME: &str = "SELECT id FROM roles WHERE role_name = $1";
 
 pub const INSERT_USER_INFO: &str = r#"
@@ -8,3 +8,62 @@ INSERT INTO user_info (full_name, email, phone_number, organization)
 VALUES ($1, $2, $3, $4)
 RETURNING id
 "#;
+
+pub const SELECT_USER_ID_BY_NAME: &str = "SELECT id FROM users WHERE username = $1";
+
+pub const INSERT_REFRESH_TOKEN: &str = r#"
+INSERT INTO refresh_tokens (user_id, family_id, token_hash, expires_at)
+VALUES ($1, $2, $3, $4)
+"#;
+
+pub const SELECT_REFRESH_TOKEN: &str = r#"
+SELECT rt.id, rt.user_id, u.username, COALESCE(r.role_name, '') AS role_name, rt.family_id, rt.expires_at, rt.rotated_at, rt.revoked_at,
+       u.disabled_at IS NOT NULL AS user_disabled
+FROM refresh_tokens rt
+JOIN users u ON rt.user_id = u.id
+LEFT JOIN roles r ON u.role_id = r.id
+WHERE rt.token_hash = $1
+"#;
+
+pub const ROTATE_REFRESH_TOKEN: &str = r#"
+UPDATE refresh_tokens
+SET rotated_at = now()
+WHERE id = $1 AND rotated_at IS NULL AND revoked_at IS NULL
+"#;
+
+pub const REVOKE_REFRESH_FAMILY: &str = r#"
+UPDATE refresh_tokens
+SET revoked_at = now()
+WHERE family_id = $1 AND revoked_at IS NULL
+"#;
+
+pub const INSERT_AUTH_LOG: &str = r#"
+INSERT INTO logs (user_id, action, details, username, ip_address)
+VALUES ($1, $2, $3, $4, $5)
+"#;
+
+/// Неудачные попытки по имени пользователя в окне $2 секунд, считая с последнего
+/// успешного входа или разблокировки. Возвращает (количество, секунд с последней попытки).
+pub const SELECT_FAILED_LOGINS_BY_USERNAME: &str = r#"
+SELECT COUNT(*), EXTRACT(EPOCH FROM (LOCALTIMESTAMP - MAX(logged_at)))::float8
+FROM logs
+WHERE action = 'login_failed'
+  AND username = $1
+  AND logged_at > GREATEST(
+      LOCALTIMESTAMP - make_interval(secs => $2),
+      COALESCE(
+          (SELECT MAX(logged_at) FROM logs
+           WHERE username = $1 AND action IN ('login_success', 'account_unlocked')),
+          '-infinity'::timestamp
+      )
+  )
+"#;
+
+/// Неудачные попытки с IP-адреса в окне $2 секунд.
+pub const SELECT_FAILED_LOGINS_BY_IP: &str = r#"
+SELECT COUNT(*), EXTRACT(EPOCH FROM (LOCALTIMESTAMP - MAX(logged_at)))::float8
+FROM logs
+WHERE action = 'login_failed'
+  AND ip_address = $1
+  AND logged_at > LOCALTIMESTAMP - make_interval(secs => $2)
+"#;
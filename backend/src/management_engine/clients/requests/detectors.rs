@@ -0,0 +1,31 @@
+pub const SELECT_DETECTORS: &str = r#"
+SELECT md.metric_type_id, mt.name AS metric_name, md.detector, md.window_size,
+       md.threshold, md.alpha, md.updated_at
+FROM metric_detectors md
+JOIN metric_types mt ON mt.id = md.metric_type_id
+ORDER BY mt.name, md.detector
+"#;
+
+pub const SELECT_METRIC_DETECTORS: &str = r#"
+SELECT md.metric_type_id, mt.name AS metric_name, md.detector, md.window_size,
+       md.threshold, md.alpha, md.updated_at
+FROM metric_detectors md
+JOIN metric_types mt ON mt.id = md.metric_type_id
+WHERE md.metric_type_id = $1
+ORDER BY md.detector
+"#;
+
+pub const DELETE_METRIC_DETECTORS: &str = "DELETE FROM metric_detectors WHERE metric_type_id = $1";
+
+pub const INSERT_METRIC_DETECTORS: &str = r#"
+INSERT INTO metric_detectors (metric_type_id, detector, window_size, threshold, alpha)
+SELECT $1, d.detector::anomaly_detector, d.window_size, d.threshold, d.alpha
+FROM UNNEST($2::text[], $3::int[], $4::float8[], $5::float8[])
+    AS d(detector, window_size, threshold, alpha)
+"#;
+
+// Все детекторы для проверки телеметрии.
+pub const SELECT_DETECTOR_RULES: &str = r#"
+SELECT metric_type_id, detector, window_size, threshold, alpha
+FROM metric_detectors
+"#;
@@ -0,0 +1,125 @@
+// ------------------ Почтовый ящик ------------------
+// $1 username, $2 только непрочитанные
+pub const SELECT_MAILBOX: &str = r#"
+SELECT m.id, m.title, m.message, m.alert_id, m.read_status, m.created_at
+FROM mailboxes m
+JOIN users u ON u.id = m.user_id
+WHERE u.username = $1
+  AND (NOT $2::bool OR NOT m.read_status)
+ORDER BY m.id DESC
+LIMIT $3 OFFSET $4
+"#;
+
+pub const COUNT_MAILBOX: &str = r#"
+SELECT COUNT(*)
+FROM mailboxes m
+JOIN users u ON u.id = m.user_id
+WHERE u.username = $1
+  AND (NOT $2::bool OR NOT m.read_status)
+"#;
+
+pub const COUNT_UNREAD: &str = r#"
+SELECT COUNT(*)
+FROM mailboxes m
+JOIN users u ON u.id = m.user_id
+WHERE u.username = $1 AND NOT m.read_status
+"#;
+
+// Меняет только сообщения владельца, у которых статус отличается от $3
+pub const MARK_MAILBOX: &str = r#"
+UPDATE mailboxes m
+SET read_status = $3
+FROM users u
+WHERE u.id = m.user_id AND u.username = $1
+  AND m.id = ANY($2::int[])
+  AND m.read_status <> $3
+"#;
+
+pub const DELETE_MAILBOX: &str = r#"
+DELETE FROM mailboxes m
+USING users u
+WHERE u.id = m.user_id AND u.username = $1
+  AND m.id = ANY($2::int[])
+"#;
+
+// ------------------ Подписки ------------------
+// Подписки пользователя и его роли
+pub const SELECT_USER_SUBSCRIPTIONS: &str = r#"
+SELECT s.id, r.role_name, d.device_name, s.location, s.min_severity, s.created_at
+FROM alert_subscriptions s
+JOIN users u ON u.id = s.user_id OR u.role_id = s.role_id
+LEFT JOIN roles r ON r.id = s.role_id
+LEFT JOIN devices d ON d.id = s.device_id
+WHERE u.username = $1
+ORDER BY s.id
+"#;
+
+pub const SELECT_ROLE_SUBSCRIPTIONS: &str = r#"
+SELECT s.id, r.role_name, d.device_name, s.location, s.min_severity, s.created_at
+FROM alert_subscriptions s
+JOIN roles r ON r.id = s.role_id
+LEFT JOIN devices d ON d.id = s.device_id
+WHERE r.role_name = $1
+ORDER BY s.id
+"#;
+
+pub const SELECT_SUBSCRIPTION_BY_ID: &str = r#"
+SELECT s.id, r.role_name, d.device_name, s.location, s.min_severity, s.created_at
+FROM alert_subscriptions s
+LEFT JOIN roles r ON r.id = s.role_id
+LEFT JOIN devices d ON d.id = s.device_id
+WHERE s.id = $1
+"#;
+
+pub const SELECT_DEVICE_ID_BY_NAME: &str = "SELECT id FROM devices WHERE device_name = $1";
+
+pub const SELECT_ROLE_ID_BY_NAME: &str = "SELECT id FROM roles WHERE role_name = $1";
+
+// $1 username или $2 role_id — подписчик задаётся одним из них
+pub const INSERT_SUBSCRIPTION: &str = r#"
+INSERT INTO alert_subscriptions (user_id, role_id, device_id, location, min_severity)
+VALUES ((SELECT id FROM users WHERE username = $1), $2, $3, $4, $5)
+RETURNING id
+"#;
+
+pub const DELETE_USER_SUBSCRIPTION: &str = r#"
+DELETE FROM alert_subscriptions
+WHERE id = $1 AND user_id = (SELECT id FROM users WHERE username = $2)
+"#;
+
+pub const DELETE_ROLE_SUBSCRIPTION: &str = r#"
+DELETE FROM alert_subscriptions
+WHERE id = $1 AND role_id = (SELECT id FROM roles WHERE role_name = $2)
+"#;
+
+// ------------------ Рассылка ------------------
+// События ($1[i] alert_id, $2[i] заголовок) доставляются активным
+// пользователям с подходящей подпиской — своей или своей роли — и
+// получателю $5 напрямую. Инициатор $3 сообщение не получает; $4 —
+// дополнительная строка сообщения.
+pub const NOTIFY_ALERT_SUBSCRIBERS: &str = r#"
+INSERT INTO mailboxes (user_id, title, message, alert_id)
+SELECT u.id,
+       left(v.title || ': ' || mt.name || ' на ' || d.device_name, 255),
+       format('Алерт #%s: метрика %s на устройстве %s (%s), уровень %s, последнее значение %s.',
+              a.id, mt.name, d.device_name, COALESCE(d.location, 'расположение не указано'),
+              a.severity, a.last_value)
+           || COALESCE(E'\nПользователь: ' || $3, '')
+           || COALESCE(E'\n' || $4, ''),
+       a.id
+FROM UNNEST($1::int[], $2::text[]) WITH ORDINALITY AS v(alert_id, title, n)
+JOIN alerts a ON a.id = v.alert_id
+JOIN devices d ON d.id = a.device_id
+JOIN metric_types mt ON mt.id = a.metric_type_id
+JOIN users u ON u.disabled_at IS NULL
+WHERE ($3::text IS NULL OR u.username <> $3)
+  AND (u.username = $5 OR EXISTS (
+        SELECT 1
+        FROM alert_subscriptions s
+        WHERE (s.user_id = u.id OR s.role_id = u.role_id)
+          AND (s.device_id IS NULL OR s.device_id = a.device_id)
+          AND (s.location IS NULL OR s.location = d.location)
+          AND s.min_severity <= a.severity
+      ))
+ORDER BY v.n, u.id
+"#;
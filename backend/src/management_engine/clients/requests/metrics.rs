@@ -0,0 +1,11 @@
+pub const SELECT_METRIC_TYPE_ID_BY_NAME: &str = "SELECT id FROM metric_types WHERE name = $1";
+
+pub const SELECT_METRIC_TYPE_ID: &str = "SELECT id FROM metric_types WHERE id = $1";
+
+// Конкурентная регистрация одного имени возвращает один и тот же id
+pub const INSERT_METRIC_TYPE: &str = r#"
+INSERT INTO metric_types (name, description)
+VALUES ($1, $2)
+ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+RETURNING id
+"#;
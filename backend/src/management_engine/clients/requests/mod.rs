@@ -1 +1,62 @@
+pub mod alerts;
 pub mod auth;
+pub mod detectors;
+pub mod mailbox;
+pub mod metrics;
+pub mod password_reset;
+pub mod roles;
+pub mod rollups;
+pub mod telemetry;
+pub mod thresholds;
+pub mod users;
+
+/// Экранирует `\`, `%` и `_` для поиска подстроки через
+/// `ILIKE '%' || $n || '%' ESCAPE '\'`.
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_like_wildcards() {
+        assert_eq!(escape_like("ЦОД-1"), "ЦОД-1");
+        assert_eq!(escape_like("100%"), "100\\%");
+        assert_eq!(escape_like("if_rx"), "if\\_rx");
+        assert_eq!(escape_like("C:\\temp"), "C:\\\\temp");
+        assert_eq!(escape_like("%_\\"), "\\%\\_\\\\");
+    }
+
+    #[actix_web::test]
+    #[ignore = "requires DATABASE_URL"]
+    async fn escaped_pattern_matches_literally() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL");
+        let pool = sqlx::PgPool::connect(&url).await.expect("подключение к DATABASE_URL");
+
+        for (text, search, expected) in [
+            ("загрузка 100%", "100%", true),
+            ("загрузка 1000", "100%", false),
+            ("if_rx", "f_r", true),
+            ("ifxrx", "f_r", false),
+            ("C:\\temp", "C:\\t", true),
+            ("Router-01", "router", true),
+        ] {
+            let (matched,): (bool,) = sqlx::query_as(r#"SELECT $1 ILIKE '%' || $2 || '%' ESCAPE '\'"#)
+                .bind(text)
+                .bind(escape_like(search))
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(matched, expected, "{:?} ILIKE {:?}", text, search);
+        }
+    }
+}
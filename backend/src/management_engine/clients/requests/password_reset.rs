@@ -0,0 +1,35 @@
+// Email не уникален: токен выдаётся каждой активной учётной записи с этим адресом
+pub const SELECT_ACTIVE_USERS_BY_EMAIL: &str = r#"
+SELECT u.id, u.username, ui.email
+FROM users u
+JOIN user_info ui ON u.user_info_id = ui.id
+WHERE lower(ui.email) = $1 AND u.disabled_at IS NULL
+"#;
+
+// Новый запрос сброса делает недействительными предыдущие токены пользователя
+pub const INVALIDATE_RESET_TOKENS: &str = r#"
+UPDATE password_reset_tokens
+SET used_at = now()
+WHERE user_id = $1 AND used_at IS NULL
+"#;
+
+pub const INSERT_RESET_TOKEN: &str = r#"
+INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+VALUES ($1, $2, $3)
+"#;
+
+// Помечает токен использованным. Строка возвращается только один раз
+// и только для действующего токена активного пользователя.
+pub const CONSUME_RESET_TOKEN: &str = r#"
+UPDATE password_reset_tokens prt
+SET used_at = now()
+FROM users u
+WHERE prt.token_hash = $1
+  AND prt.used_at IS NULL
+  AND prt.expires_at > LOCALTIMESTAMP
+  AND u.id = prt.user_id
+  AND u.disabled_at IS NULL
+RETURNING prt.user_id, u.username
+"#;
+
+pub const UPDATE_PASSWORD_HASH: &str = "UPDATE users SET password_hash = $2 WHERE id = $1";
@@ -0,0 +1,44 @@
+pub const SELECT_ROLES: &str = r#"
+SELECT r.id, r.role_name,
+       COALESCE(array_agg(rp.permission ORDER BY rp.permission)
+                FILTER (WHERE rp.permission IS NOT NULL), '{}')::text[] AS permissions
+FROM roles r
+LEFT JOIN role_permissions rp ON rp.role_id = r.id
+GROUP BY r.id, r.role_name
+ORDER BY r.id
+"#;
+
+pub const SELECT_ROLE_BY_NAME: &str = r#"
+SELECT r.id, r.role_name,
+       COALESCE(array_agg(rp.permission ORDER BY rp.permission)
+                FILTER (WHERE rp.permission IS NOT NULL), '{}')::text[] AS permissions
+FROM roles r
+LEFT JOIN role_permissions rp ON rp.role_id = r.id
+WHERE r.role_name = $1
+GROUP BY r.id, r.role_name
+"#;
+
+pub const SELECT_ROLE_PERMISSIONS: &str = r#"
+SELECT rp.permission
+FROM role_permissions rp
+JOIN roles r ON rp.role_id = r.id
+WHERE r.role_name = $1
+ORDER BY rp.permission
+"#;
+
+pub const INSERT_ROLE: &str = "INSERT INTO roles (role_name) VALUES ($1) RETURNING id";
+
+pub const DELETE_ROLE_PERMISSIONS: &str = "DELETE FROM role_permissions WHERE role_id = $1";
+
+pub const INSERT_ROLE_PERMISSIONS: &str = r#"
+INSERT INTO role_permissions (role_id, permission)
+SELECT $1, unnest($2::text[])
+ON CONFLICT DO NOTHING
+"#;
+
+pub const UPDATE_USER_ROLE: &str = r#"
+UPDATE users SET role_id = (SELECT id FROM roles WHERE role_name = $2)
+WHERE username = $1
+"#;
+
+pub const CLEAR_USER_ROLE: &str = "UPDATE users SET role_id = NULL WHERE username = $1";
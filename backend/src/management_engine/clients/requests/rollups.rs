@@ -0,0 +1,72 @@
+// {table} заменяется на таблицу уровня (RollupLevel::table).
+
+// Одна фоновая задача на все экземпляры backend.
+pub const TRY_ROLLUP_LOCK: &str = r#"
+SELECT pg_try_advisory_xact_lock(hashtext('telemetry_rollup'))
+"#;
+
+pub const SELECT_ROLLUP_WATERMARK: &str = r#"
+SELECT rolled_until FROM telemetry_rollup_state WHERE level = $1
+"#;
+
+pub const UPSERT_ROLLUP_WATERMARK: &str = r#"
+INSERT INTO telemetry_rollup_state (level, rolled_until)
+VALUES ($1, $2)
+ON CONFLICT (level) DO UPDATE SET rolled_until = EXCLUDED.rolled_until
+"#;
+
+// Пересчитывает из сырых строк все корзины, в которые попали строки,
+// принятые в [$2, $3). Опоздавшие события обновляют уже готовые корзины.
+// $1 — размер корзины уровня ('1 hour').
+pub const UPSERT_ROLLUP: &str = r#"
+WITH affected AS (
+    SELECT DISTINCT
+        device_id,
+        metric_type_id,
+        date_bin($1::interval, recorded_at, TIMESTAMP '2000-01-01') AS bucket
+    FROM telemetry_data
+    WHERE received_at >= $2 AND received_at < $3
+      AND device_id IS NOT NULL AND metric_type_id IS NOT NULL
+)
+INSERT INTO {table}
+    (device_id, metric_type_id, bucket, count, sum, min, max, p95, anomaly_count)
+SELECT
+    a.device_id,
+    a.metric_type_id,
+    a.bucket,
+    COUNT(*),
+    SUM(t.metric_value)::float8,
+    MIN(t.metric_value)::float8,
+    MAX(t.metric_value)::float8,
+    percentile_cont(0.95) WITHIN GROUP (ORDER BY t.metric_value),
+    COUNT(*) FILTER (WHERE t.is_anomaly)
+FROM affected a
+JOIN telemetry_data t
+    ON t.device_id = a.device_id
+   AND t.metric_type_id = a.metric_type_id
+   AND t.recorded_at >= a.bucket
+   AND t.recorded_at < a.bucket + $1::interval
+GROUP BY a.device_id, a.metric_type_id, a.bucket
+ON CONFLICT (device_id, metric_type_id, bucket) DO UPDATE SET
+    count = EXCLUDED.count,
+    sum = EXCLUDED.sum,
+    min = EXCLUDED.min,
+    max = EXCLUDED.max,
+    p95 = EXCLUDED.p95,
+    anomaly_count = EXCLUDED.anomaly_count
+"#;
+
+// Сырые строки старше $1, уже учтённые во всех уровнях (received_at < $2).
+// Удаляются порциями по $3, чтобы не держать длинную блокировку.
+pub const DELETE_EXPIRED_TELEMETRY: &str = r#"
+DELETE FROM telemetry_data
+WHERE id IN (
+    SELECT id FROM telemetry_data
+    WHERE recorded_at < $1 AND received_at < $2
+    LIMIT $3
+)
+"#;
+
+pub const DELETE_EXPIRED_ROLLUP: &str = r#"
+DELETE FROM {table} WHERE bucket < $1
+"#;
@@ -0,0 +1,223 @@
+// Один запрос на все устройства пачки. DO UPDATE нужен, чтобы RETURNING
+// вернул id и для уже существующих устройств.
+pub const UPSERT_DEVICES: &str = r#"
+INSERT INTO devices (device_name, ip_address, location)
+SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[])
+ON CONFLICT (device_name) DO UPDATE SET device_name = EXCLUDED.device_name
+RETURNING device_name, id, location
+"#;
+
+// Повтор event_id пропускается; RETURNING показывает, что реально вставлено.
+pub const INSERT_TELEMETRY_BATCH: &str = r#"
+INSERT INTO telemetry_data
+    (device_id, metric_type_id, metric_value, is_anomaly, severity, action_description,
+     recorded_at, received_at, event_id, anomaly_detector, anomaly_score)
+SELECT * FROM UNNEST(
+    $1::int[], $2::int[], $3::float8[], $4::bool[], $5::text[]::telemetry_severity[], $6::text[],
+    $7::timestamp[], $8::timestamp[], $9::uuid[], $10::text[], $11::float8[]
+)
+ON CONFLICT (event_id) DO NOTHING
+RETURNING event_id
+"#;
+
+// Последние $3 значений каждой пары ($1[i], $2[i]) — окно детекторов аномалий.
+pub const SELECT_RECENT_VALUES: &str = r#"
+SELECT p.device_id, p.metric_type_id, h.metric_value, h.recorded_at
+FROM UNNEST($1::int[], $2::int[], $3::int[]) AS p(device_id, metric_type_id, window_size)
+CROSS JOIN LATERAL (
+    SELECT t.metric_value::float8 AS metric_value, t.recorded_at
+    FROM telemetry_data t
+    WHERE t.device_id = p.device_id AND t.metric_type_id = p.metric_type_id
+    ORDER BY t.recorded_at DESC
+    LIMIT p.window_size
+) h
+"#;
+
+// Состояние пар устройство/метрика из пачки, заблокированное до конца транзакции.
+pub const LOCK_METRIC_HEALTH: &str = r#"
+SELECT h.device_id, h.metric_type_id, h.severity, h.streak
+FROM device_metric_health h
+JOIN UNNEST($1::int[], $2::int[]) AS p(device_id, metric_type_id)
+    ON h.device_id = p.device_id AND h.metric_type_id = p.metric_type_id
+ORDER BY h.device_id, h.metric_type_id
+FOR UPDATE OF h
+"#;
+
+pub const UPSERT_METRIC_HEALTH: &str = r#"
+INSERT INTO device_metric_health (device_id, metric_type_id, severity, streak, updated_at)
+SELECT d, m, s::telemetry_severity, k, now()
+FROM UNNEST($1::int[], $2::int[], $3::text[], $4::int[]) AS p(d, m, s, k)
+ON CONFLICT (device_id, metric_type_id) DO UPDATE SET
+    severity = EXCLUDED.severity,
+    streak = EXCLUDED.streak,
+    updated_at = EXCLUDED.updated_at
+"#;
+
+// Текущий статус устройств и состояние всех их метрик.
+pub const SELECT_DEVICE_HEALTH: &str = r#"
+SELECT d.id, d.device_name, d.status, h.severity, h.streak, h.updated_at
+FROM devices d
+JOIN device_metric_health h ON h.device_id = d.id
+WHERE d.id = ANY($1)
+ORDER BY d.id
+"#;
+
+pub const UPDATE_DEVICE_STATUS: &str = r#"
+UPDATE devices SET status = $2, status_changed_at = now()
+WHERE id = $1 AND status <> 'inactive'
+"#;
+
+// Страница телеметрии, новые записи первыми. Ключ страницы — (recorded_at, id):
+// $8/$9 — подстроки поиска, экранированные escape_like;
+// $10/$11 — последняя запись предыдущей страницы или NULL.
+pub const SELECT_TELEMETRY_PAGE: &str = r#"
+SELECT
+    t.id,
+    t.device_id,
+    d.device_name,
+    d.ip_address,
+    d.location,
+    t.metric_type_id,
+    m.name AS metric_name,
+    t.metric_value::float8 AS metric_value,
+    COALESCE(t.is_anomaly, false) AS is_anomaly,
+    t.severity,
+    t.anomaly_detector,
+    t.anomaly_score,
+    t.action_description,
+    t.recorded_at,
+    t.received_at
+FROM telemetry_data t
+JOIN devices d ON t.device_id = d.id
+LEFT JOIN metric_types m ON t.metric_type_id = m.id
+WHERE ($1::timestamp IS NULL OR t.recorded_at >= $1)
+  AND ($2::timestamp IS NULL OR t.recorded_at < $2)
+  AND ($3::int IS NULL OR t.device_id = $3)
+  AND ($4::text IS NULL OR d.device_name = $4)
+  AND ($5::int IS NULL OR t.metric_type_id = $5)
+  AND ($6::text IS NULL OR m.name = $6)
+  AND (NOT $7::bool OR t.is_anomaly)
+  AND ($8::text IS NULL OR d.location ILIKE '%' || $8 || '%' ESCAPE '\')
+  AND ($9::text IS NULL OR t.action_description ILIKE '%' || $9 || '%' ESCAPE '\')
+  AND ($10::timestamp IS NULL OR (t.recorded_at, t.id) < ($10, $11::int))
+ORDER BY t.recorded_at DESC, t.id DESC
+LIMIT $12
+"#;
+
+// Агрегаты по корзинам времени для каждой пары устройство/метрика.
+// Корзины выравниваются от 2000-01-01 00:00 UTC, пустые корзины внутри
+// интервала возвращаются с count = 0, чтобы графики были непрерывными.
+// $1/$2 — интервал [from, to), $3 — размер корзины ('5 minutes'),
+// $8/$9 — размер и имя уровня агрегатов, {rollup} — его таблица,
+// $10 — время, раньше которого корзины {rollup} удалены по сроку хранения.
+//
+// Корзины от $10 до границы, уже учтённой фоновой задачей, берутся из
+// {rollup}, остальное досчитывается по сырым строкам, которые могут жить
+// дольше агрегатов. p95 точен, только если корзина совпадает с уровнем
+// агрегатов; иначе это максимум p95 входящих корзин.
+pub const SELECT_TELEMETRY_AGGREGATES: &str = r#"
+WITH boundary AS (
+    SELECT LEAST($2::timestamp, date_bin(
+        $8::interval,
+        COALESCE(
+            (SELECT rolled_until FROM telemetry_rollup_state WHERE level = $9),
+            TIMESTAMP '2000-01-01'
+        ),
+        TIMESTAMP '2000-01-01'
+    )) AS ts,
+    -- Первая целая корзина уровня, не старше $10
+    COALESCE(
+        date_bin($8::interval, $10::timestamp + $8::interval - INTERVAL '1 microsecond', TIMESTAMP '2000-01-01'),
+        TIMESTAMP '-infinity'
+    ) AS retained_from
+),
+parts AS (
+    SELECT
+        r.device_id,
+        r.metric_type_id,
+        date_bin($3::interval, r.bucket, TIMESTAMP '2000-01-01') AS bucket,
+        r.count,
+        r.sum,
+        r.min,
+        r.max,
+        r.p95,
+        r.anomaly_count
+    FROM {rollup} r
+    JOIN devices d ON r.device_id = d.id
+    JOIN metric_types m ON r.metric_type_id = m.id
+    CROSS JOIN boundary b
+    WHERE r.bucket >= date_bin($8::interval, $1::timestamp, TIMESTAMP '2000-01-01')
+      AND r.bucket >= b.retained_from
+      AND r.bucket < b.ts
+      AND ($4::int IS NULL OR r.device_id = $4)
+      AND ($5::text IS NULL OR d.device_name = $5)
+      AND ($6::int IS NULL OR r.metric_type_id = $6)
+      AND ($7::text IS NULL OR m.name = $7)
+    UNION ALL
+    SELECT
+        t.device_id,
+        t.metric_type_id,
+        date_bin($3::interval, t.recorded_at, TIMESTAMP '2000-01-01'),
+        COUNT(*),
+        SUM(t.metric_value)::float8,
+        MIN(t.metric_value)::float8,
+        MAX(t.metric_value)::float8,
+        percentile_cont(0.95) WITHIN GROUP (ORDER BY t.metric_value),
+        COUNT(*) FILTER (WHERE t.is_anomaly)
+    FROM telemetry_data t
+    JOIN devices d ON t.device_id = d.id
+    LEFT JOIN metric_types m ON t.metric_type_id = m.id
+    CROSS JOIN boundary b
+    WHERE t.recorded_at >= $1::timestamp AND t.recorded_at < $2
+      AND (t.recorded_at >= b.ts OR t.recorded_at < b.retained_from)
+      AND t.metric_type_id IS NOT NULL
+      AND ($4::int IS NULL OR t.device_id = $4)
+      AND ($5::text IS NULL OR d.device_name = $5)
+      AND ($6::int IS NULL OR t.metric_type_id = $6)
+      AND ($7::text IS NULL OR m.name = $7)
+    GROUP BY 1, 2, 3
+),
+agg AS (
+    SELECT
+        device_id,
+        metric_type_id,
+        bucket,
+        SUM(count)::bigint AS count,
+        MIN(min) AS min,
+        MAX(max) AS max,
+        SUM(sum) / SUM(count) AS avg,
+        MAX(p95) AS p95,
+        SUM(anomaly_count)::bigint AS anomaly_count
+    FROM parts
+    GROUP BY 1, 2, 3
+),
+series AS (
+    SELECT DISTINCT device_id, metric_type_id FROM agg
+),
+buckets AS (
+    SELECT generate_series(
+        date_bin($3::interval, $1::timestamp, TIMESTAMP '2000-01-01'),
+        $2::timestamp - INTERVAL '1 microsecond',
+        $3::interval
+    ) AS bucket
+)
+SELECT
+    s.device_id,
+    d.device_name,
+    s.metric_type_id,
+    m.name AS metric_name,
+    b.bucket,
+    COALESCE(a.count, 0) AS count,
+    a.min,
+    a.max,
+    a.avg,
+    a.p95,
+    COALESCE(a.anomaly_count, 0) AS anomaly_count
+FROM series s
+CROSS JOIN buckets b
+JOIN devices d ON s.device_id = d.id
+JOIN metric_types m ON s.metric_type_id = m.id
+LEFT JOIN agg a
+    ON a.device_id = s.device_id AND a.metric_type_id = s.metric_type_id AND a.bucket = b.bucket
+ORDER BY d.device_name, m.name, b.bucket
+"#;
@@ -0,0 +1,52 @@
+// $1 metric_name, $2 device_name, $3 location — NULL отключает фильтр.
+pub const SELECT_THRESHOLDS: &str = r#"
+SELECT t.id, t.metric_type_id, mt.name AS metric_name, t.device_id, d.device_name, t.location,
+       t.comparison, t.warning_level::float8, t.critical_level::float8,
+       u.username AS created_by, t.created_at, t.updated_at
+FROM thresholds t
+JOIN metric_types mt ON mt.id = t.metric_type_id
+LEFT JOIN devices d ON d.id = t.device_id
+LEFT JOIN users u ON u.id = t.created_by
+WHERE ($1::text IS NULL OR mt.name = $1)
+  AND ($2::text IS NULL OR d.device_name = $2)
+  AND ($3::text IS NULL OR t.location = $3)
+ORDER BY mt.name, t.device_id NULLS FIRST, t.location NULLS FIRST, t.comparison
+"#;
+
+pub const SELECT_THRESHOLD_BY_ID: &str = r#"
+SELECT t.id, t.metric_type_id, mt.name AS metric_name, t.device_id, d.device_name, t.location,
+       t.comparison, t.warning_level::float8, t.critical_level::float8,
+       u.username AS created_by, t.created_at, t.updated_at
+FROM thresholds t
+JOIN metric_types mt ON mt.id = t.metric_type_id
+LEFT JOIN devices d ON d.id = t.device_id
+LEFT JOIN users u ON u.id = t.created_by
+WHERE t.id = $1
+"#;
+
+pub const SELECT_DEVICE_ID_BY_NAME: &str = "SELECT id FROM devices WHERE device_name = $1";
+
+pub const INSERT_THRESHOLD: &str = r#"
+INSERT INTO thresholds
+    (metric_type_id, device_id, location, comparison, warning_level, critical_level, created_by)
+VALUES ($1, $2, $3, $4, $5, $6, (SELECT id FROM users WHERE username = $7))
+RETURNING id
+"#;
+
+pub const UPDATE_THRESHOLD: &str = r#"
+UPDATE thresholds
+SET metric_type_id = $2, device_id = $3, location = $4, comparison = $5,
+    warning_level = $6, critical_level = $7, updated_at = now()
+WHERE id = $1
+"#;
+
+pub const DELETE_THRESHOLD: &str = "DELETE FROM thresholds WHERE id = $1";
+
+// Все пороги для оценки телеметрии.
+pub const SELECT_THRESHOLD_RULES: &str = r#"
+SELECT metric_type_id, device_id, location, comparison,
+       warning_level::float8, critical_level::float8
+FROM thresholds
+WHERE metric_type_id IS NOT NULL
+  AND (warning_level IS NOT NULL OR critical_level IS NOT NULL)
+"#;
@@ -0,0 +1,72 @@
+pub const SELECT_USER_PROFILE: &str = r#"
+SELECT u.id, u.username, r.role_name, ui.full_name, ui.email, ui.phone_number, ui.organization, u.created_at
+FROM users u
+LEFT JOIN roles r ON u.role_id = r.id
+LEFT JOIN user_info ui ON u.user_info_id = ui.id
+WHERE u.username = $1
+"#;
+
+// $4/$6 — менять ли phone_number/organization (значение NULL очищает поле)
+pub const UPDATE_USER_INFO: &str = r#"
+UPDATE user_info SET
+    full_name    = COALESCE($2, full_name),
+    email        = COALESCE($3, email),
+    phone_number = CASE WHEN $4 THEN $5 ELSE phone_number END,
+    organization = CASE WHEN $6 THEN $7 ELSE organization END
+WHERE id = (SELECT user_info_id FROM users WHERE username = $1)
+"#;
+
+pub const UPDATE_PASSWORD_HASH: &str = "UPDATE users SET password_hash = $2 WHERE id = $1";
+
+pub const REVOKE_USER_REFRESH_TOKENS: &str = r#"
+UPDATE refresh_tokens
+SET revoked_at = now()
+WHERE user_id = $1 AND revoked_at IS NULL
+"#;
+
+// $1 — подстрока поиска, экранированная escape_like, или NULL
+pub const SELECT_USERS: &str = r#"
+SELECT u.id, u.username, r.role_name, ui.full_name, ui.email, ui.organization, u.created_at, u.disabled_at
+FROM users u
+LEFT JOIN roles r ON u.role_id = r.id
+LEFT JOIN user_info ui ON u.user_info_id = ui.id
+WHERE $1::text IS NULL
+   OR u.username ILIKE '%' || $1 || '%' ESCAPE '\'
+   OR ui.full_name ILIKE '%' || $1 || '%' ESCAPE '\'
+   OR ui.email ILIKE '%' || $1 || '%' ESCAPE '\'
+   OR ui.organization ILIKE '%' || $1 || '%' ESCAPE '\'
+ORDER BY u.id
+LIMIT $2 OFFSET $3
+"#;
+
+pub const COUNT_USERS: &str = r#"
+SELECT COUNT(*)
+FROM users u
+LEFT JOIN user_info ui ON u.user_info_id = ui.id
+WHERE $1::text IS NULL
+   OR u.username ILIKE '%' || $1 || '%' ESCAPE '\'
+   OR ui.full_name ILIKE '%' || $1 || '%' ESCAPE '\'
+   OR ui.email ILIKE '%' || $1 || '%' ESCAPE '\'
+   OR ui.organization ILIKE '%' || $1 || '%' ESCAPE '\'
+"#;
+
+pub const SET_USER_DISABLED: &str = r#"
+UPDATE users
+SET disabled_at = CASE WHEN $2 THEN COALESCE(disabled_at, LOCALTIMESTAMP) ELSE NULL END
+WHERE username = $1
+RETURNING id
+"#;
+
+pub const SELECT_USER_ACTIVE: &str =
+    "SELECT disabled_at IS NULL FROM users WHERE username = $1";
+
+// ------------------ Удаление пользователя ------------------
+pub const SELECT_USER_FOR_DELETE: &str =
+    "SELECT id, user_info_id FROM users WHERE username = $1 FOR UPDATE";
+pub const DETACH_USER_LOGS: &str = "UPDATE logs SET user_id = NULL WHERE user_id = $1";
+pub const DETACH_USER_DEVICES: &str = "UPDATE devices SET added_by = NULL WHERE added_by = $1";
+pub const DETACH_USER_THRESHOLDS: &str =
+    "UPDATE thresholds SET created_by = NULL WHERE created_by = $1";
+pub const DELETE_USER_MAILBOXES: &str = "DELETE FROM mailboxes WHERE user_id = $1";
+pub const DELETE_USER: &str = "DELETE FROM users WHERE id = $1";
+pub const DELETE_USER_INFO: &str = "DELETE FROM user_info WHERE id = $1";
@@ -0,0 +1,83 @@
+use crate::management_engine::models::alerts::alerts::{AlertEvent, AlertEventType, AlertStatus, AlertView};
+use crate::management_engine::models::telemetry::telemetry::Severity;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+/// Фильтры списка алертов; `None` отключает фильтр.
+#[derive(Debug, Clone, Default)]
+pub struct AlertFilter {
+    pub status: Option<AlertStatus>,
+    pub active_only: bool,
+    pub severity: Option<Severity>,
+    pub device_name: Option<String>,
+    pub metric_name: Option<String>,
+    pub assigned_to: Option<String>,
+}
+
+/// Незакрытый алерт пары устройство/метрика.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ActiveAlert {
+    pub id: i32,
+    pub device_id: i32,
+    pub metric_type_id: i32,
+    pub severity: Severity,
+    pub healthy_streak: i32,
+}
+
+/// Алерт, открытый пачкой телеметрии.
+#[derive(Debug, Clone)]
+pub struct NewAlert {
+    pub device_id: i32,
+    pub metric_type_id: i32,
+    pub severity: Severity,
+    pub status: AlertStatus,
+    pub detector: Option<String>,
+    pub first_value: f64,
+    pub last_value: f64,
+    pub sample_count: i32,
+    pub healthy_streak: i32,
+    pub opened_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+}
+
+/// Изменения незакрытого алерта после пачки телеметрии.
+#[derive(Debug, Clone)]
+pub struct AlertUpdate {
+    pub id: i32,
+    /// `None`, если новых аномальных измерений не было
+    pub last_value: Option<f64>,
+    pub last_seen_at: Option<NaiveDateTime>,
+    pub new_samples: i32,
+    pub healthy_streak: i32,
+    pub resolved: bool,
+}
+
+#[async_trait]
+pub trait AlertClient {
+    async fn list_alerts(&self, filter: &AlertFilter, limit: i64, offset: i64) -> Result<Vec<AlertView>, sqlx::Error>;
+
+    async fn count_alerts(&self, filter: &AlertFilter) -> Result<i64, sqlx::Error>;
+
+    async fn get_alert(&self, id: i32) -> Result<Option<AlertView>, sqlx::Error>;
+
+    async fn get_alert_events(&self, id: i32) -> Result<Vec<AlertEvent>, sqlx::Error>;
+
+    async fn user_exists(&self, username: &str) -> Result<bool, sqlx::Error>;
+
+    /// Переводит открытый алерт в acknowledged. Возвращает `false`, если алерт не открыт.
+    async fn acknowledge_alert(&self, id: i32, username: &str) -> Result<bool, sqlx::Error>;
+
+    /// Возвращает `false`, если алерт уже закрыт.
+    async fn assign_alert(&self, id: i32, assignee: Option<&str>, username: &str) -> Result<bool, sqlx::Error>;
+
+    /// Возвращает `false`, если алерт уже закрыт.
+    async fn resolve_alert(&self, id: i32, username: &str, comment: Option<&str>) -> Result<bool, sqlx::Error>;
+
+    async fn add_alert_event(
+        &self,
+        id: i32,
+        event_type: AlertEventType,
+        username: &str,
+        details: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
+}
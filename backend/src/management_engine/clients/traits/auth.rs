@@ -1,27 +1,79 @@
+use crate::management_engine::models::auth::auth::RefreshToken;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 
+/// Набор операций, выполняемых в одной транзакции.
+/// Без вызова `commit` все изменения откатываются при удалении объекта.
 #[async_trait]
-pub trait AuthClient {
+pub trait AuthUnitOfWork: Send {
     async fn create_user(
-        &self,
+        &mut self,
         username: &str,
         password_hash: &str,
         role_id: i32,
         user_info_id: i32,
-    ) -> Result<(), sqlx::Error>;
+    ) -> Result<i32, sqlx::Error>;
 
     async fn create_user_info(
-        &self,
+        &mut self,
         full_name: &str,
         email: &str,
         phone_number: Option<&str>,
         organization: Option<&str>,
     ) -> Result<i32, sqlx::Error>;
 
+    async fn commit(self: Box<Self>) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait]
+pub trait AuthClient {
+    async fn begin(&self) -> Result<Box<dyn AuthUnitOfWork>, sqlx::Error>;
+
+    /// (id, password_hash, role_name, отключён ли пользователь)
     async fn get_user_details(
         &self,
         username: &str,
-    ) -> Result<Option<(String, String)>, sqlx::Error>;
+    ) -> Result<Option<(i32, String, String, bool)>, sqlx::Error>;
 
     async fn get_role_id(&self, role_name: &str) -> Result<Option<i32>, sqlx::Error>;
+
+    async fn get_user_id(&self, username: &str) -> Result<Option<i32>, sqlx::Error>;
+
+    async fn create_refresh_token(
+        &self,
+        user_id: i32,
+        family_id: &str,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, sqlx::Error>;
+
+    /// Помечает токен использованным. Возвращает `false`, если токен уже был
+    /// ротирован или отозван (повторное использование).
+    async fn rotate_refresh_token(&self, id: i32) -> Result<bool, sqlx::Error>;
+
+    async fn revoke_refresh_family(&self, family_id: &str) -> Result<u64, sqlx::Error>;
+
+    async fn log_auth_event(
+        &self,
+        user_id: Option<i32>,
+        action: &str,
+        details: Option<&str>,
+        username: &str,
+        ip_address: Option<&str>,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Возвращает (число неудачных попыток, секунд с последней попытки).
+    async fn get_failed_logins_by_username(
+        &self,
+        username: &str,
+        window_secs: f64,
+    ) -> Result<(i64, Option<f64>), sqlx::Error>;
+
+    async fn get_failed_logins_by_ip(
+        &self,
+        ip_address: &str,
+        window_secs: f64,
+    ) -> Result<(i64, Option<f64>), sqlx::Error>;
 }
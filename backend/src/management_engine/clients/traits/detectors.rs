@@ -0,0 +1,24 @@
+use crate::management_engine::models::thresholds::detectors::{DetectorConfig, DetectorKind};
+use async_trait::async_trait;
+
+/// Детектор метрики в виде, нужном для записи и проверки телеметрии.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DetectorRule {
+    pub metric_type_id: i32,
+    pub detector: DetectorKind,
+    pub window_size: i32,
+    pub threshold: f64,
+    pub alpha: Option<f64>,
+}
+
+#[async_trait]
+pub trait DetectorClient {
+    async fn list_detectors(&self) -> Result<Vec<DetectorConfig>, sqlx::Error>;
+
+    async fn get_metric_detectors(&self, metric_type_id: i32) -> Result<Vec<DetectorConfig>, sqlx::Error>;
+
+    /// Заменяет набор детекторов метрики целиком.
+    async fn set_metric_detectors(&self, metric_type_id: i32, rules: &[DetectorRule]) -> Result<(), sqlx::Error>;
+
+    async fn get_detector_rules(&self) -> Result<Vec<DetectorRule>, sqlx::Error>;
+}
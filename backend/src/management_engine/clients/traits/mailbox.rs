@@ -0,0 +1,73 @@
+use crate::management_engine::models::alerts::alerts::AlertEventType;
+use crate::management_engine::models::mailbox::mailbox::{MailboxMessage, SubscriptionView};
+use crate::management_engine::models::telemetry::telemetry::Severity;
+use async_trait::async_trait;
+
+/// Подписка, готовая к вставке. Подписчик задаётся одним из `username`
+/// и `role_id`, область — не более чем одним из `device_id` и `location`.
+#[derive(Debug, Clone)]
+pub struct NewSubscription {
+    pub username: Option<String>,
+    pub role_id: Option<i32>,
+    pub device_id: Option<i32>,
+    pub location: Option<String>,
+    pub min_severity: Severity,
+}
+
+/// События алертов для рассылки подписчикам.
+#[derive(Debug, Clone, Copy)]
+pub struct AlertNotice<'a> {
+    pub events: &'a [(i32, AlertEventType)],
+    /// Пользователь, совершивший действие; `None` для действий системы
+    pub actor: Option<&'a str>,
+    /// Дополнительная строка сообщения
+    pub note: Option<&'a str>,
+    /// Получает сообщение независимо от подписок
+    pub recipient: Option<&'a str>,
+}
+
+/// Рассылка событий алертов в транзакции, изменившей алерты.
+#[async_trait]
+pub trait AlertMailbox: Send {
+    /// Возвращает число доставленных сообщений.
+    async fn notify_alert_subscribers(&mut self, notice: &AlertNotice<'_>) -> Result<u64, sqlx::Error>;
+}
+
+#[async_trait]
+pub trait MailboxClient {
+    async fn list_messages(
+        &self,
+        username: &str,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<MailboxMessage>, sqlx::Error>;
+
+    async fn count_messages(&self, username: &str, unread_only: bool) -> Result<i64, sqlx::Error>;
+
+    async fn count_unread(&self, username: &str) -> Result<i64, sqlx::Error>;
+
+    /// Возвращает число сообщений, у которых статус изменился.
+    async fn mark_messages(&self, username: &str, ids: &[i32], read: bool) -> Result<u64, sqlx::Error>;
+
+    async fn delete_messages(&self, username: &str, ids: &[i32]) -> Result<u64, sqlx::Error>;
+
+    /// Подписки пользователя вместе с подписками его роли.
+    async fn list_user_subscriptions(&self, username: &str) -> Result<Vec<SubscriptionView>, sqlx::Error>;
+
+    async fn list_role_subscriptions(&self, role_name: &str) -> Result<Vec<SubscriptionView>, sqlx::Error>;
+
+    async fn get_subscription(&self, id: i32) -> Result<Option<SubscriptionView>, sqlx::Error>;
+
+    async fn get_device_id(&self, device_name: &str) -> Result<Option<i32>, sqlx::Error>;
+
+    async fn get_role_id(&self, role_name: &str) -> Result<Option<i32>, sqlx::Error>;
+
+    async fn create_subscription(&self, subscription: &NewSubscription) -> Result<i32, sqlx::Error>;
+
+    /// Возвращает `false`, если у пользователя нет такой подписки.
+    async fn delete_user_subscription(&self, id: i32, username: &str) -> Result<bool, sqlx::Error>;
+
+    /// Возвращает `false`, если у роли нет такой подписки.
+    async fn delete_role_subscription(&self, id: i32, role_name: &str) -> Result<bool, sqlx::Error>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait MetricClient {
+    async fn get_metric_type_id(&self, name: &str) -> Result<Option<i32>, sqlx::Error>;
+
+    async fn metric_type_exists(&self, id: i32) -> Result<bool, sqlx::Error>;
+
+    /// Регистрирует метрику (или возвращает id уже существующей).
+    async fn register_metric_type(&self, name: &str, description: Option<&str>) -> Result<i32, sqlx::Error>;
+}
@@ -1,2 +1,13 @@
+pub mod alerts;
 pub mod auth;
+pub mod detectors;
 pub mod general;
+pub mod mailbox;
+pub mod metrics;
+pub mod notifier;
+pub mod password_reset;
+pub mod roles;
+pub mod rollups;
+pub mod telemetry;
+pub mod thresholds;
+pub mod users;
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+
+/// Сообщение пользователю (письмо, уведомление).
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum NotifyError {
+    #[error("некорректный адрес '{0}'")]
+    InvalidAddress(String),
+    #[error("ошибка отправки: {0}")]
+    Transport(String),
+    #[error("ошибка записи: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Канал доставки уведомлений.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, notification: &Notification) -> Result<(), NotifyError>;
+}
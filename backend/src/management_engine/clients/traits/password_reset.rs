@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+#[async_trait]
+pub trait PasswordResetClient {
+    /// (id, username, email) активных пользователей с указанным email.
+    async fn get_active_users_by_email(
+        &self,
+        email: &str,
+    ) -> Result<Vec<(i32, String, String)>, sqlx::Error>;
+
+    /// Сохраняет новый токен, аннулируя предыдущие неиспользованные токены пользователя.
+    async fn create_reset_token(
+        &self,
+        user_id: i32,
+        token_hash: &str,
+        expires_at: NaiveDateTime,
+    ) -> Result<(), sqlx::Error>;
+
+    /// Погашает токен и устанавливает новый пароль в одной транзакции.
+    /// Возвращает (user_id, username) или `None`, если токен недействителен.
+    async fn reset_password(
+        &self,
+        token_hash: &str,
+        password_hash: &str,
+    ) -> Result<Option<(i32, String)>, sqlx::Error>;
+}
@@ -0,0 +1,23 @@
+use crate::management_engine::models::roles::roles::RoleWithPermissions;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait RoleClient {
+    async fn list_roles(&self) -> Result<Vec<RoleWithPermissions>, sqlx::Error>;
+
+    async fn get_role(&self, role_name: &str) -> Result<Option<RoleWithPermissions>, sqlx::Error>;
+
+    async fn get_role_permissions(&self, role_name: &str) -> Result<Vec<String>, sqlx::Error>;
+
+    /// Создаёт роль вместе с набором прав в одной транзакции.
+    async fn create_role(&self, role_name: &str, permissions: &[String]) -> Result<i32, sqlx::Error>;
+
+    /// Заменяет набор прав роли целиком.
+    async fn set_role_permissions(&self, role_id: i32, permissions: &[String]) -> Result<(), sqlx::Error>;
+
+    /// Возвращает `false`, если пользователь не найден.
+    async fn assign_user_role(&self, username: &str, role_name: &str) -> Result<bool, sqlx::Error>;
+
+    /// Возвращает `false`, если пользователь не найден.
+    async fn revoke_user_role(&self, username: &str) -> Result<bool, sqlx::Error>;
+}
@@ -0,0 +1,42 @@
+use crate::management_engine::models::telemetry::telemetry::RollupLevel;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+/// Обновление агрегатов в одной транзакции.
+/// Без вызова `commit` все изменения откатываются при удалении объекта.
+#[async_trait]
+pub trait RollupUnitOfWork: Send {
+    /// `false`, если агрегаты уже обновляет другой экземпляр.
+    async fn try_lock(&mut self) -> Result<bool, sqlx::Error>;
+
+    /// Граница по received_at, до которой строки уже учтены.
+    async fn get_watermark(&mut self, level: RollupLevel) -> Result<Option<NaiveDateTime>, sqlx::Error>;
+
+    /// Пересчитывает корзины, затронутые строками с received_at в [from, to).
+    async fn rollup(
+        &mut self,
+        level: RollupLevel,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn set_watermark(&mut self, level: RollupLevel, until: NaiveDateTime) -> Result<(), sqlx::Error>;
+
+    async fn commit(self: Box<Self>) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait]
+pub trait RollupClient {
+    async fn begin(&self) -> Result<Box<dyn RollupUnitOfWork>, sqlx::Error>;
+
+    /// Удаляет до `limit` сырых строк, записанных раньше `before`
+    /// и принятых раньше `rolled_until`.
+    async fn delete_expired_telemetry(
+        &self,
+        before: NaiveDateTime,
+        rolled_until: NaiveDateTime,
+        limit: i64,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn delete_expired_rollup(&self, level: RollupLevel, before: NaiveDateTime) -> Result<u64, sqlx::Error>;
+}
@@ -0,0 +1,162 @@
+use crate::management_engine::models::telemetry::telemetry::{
+    AggregateRow, BucketSize, DeviceStatus, Severity, TelemetryRecord,
+};
+use crate::management_engine::clients::traits::alerts::{ActiveAlert, AlertUpdate, NewAlert};
+use crate::management_engine::clients::traits::mailbox::AlertMailbox;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+/// Устройство из пачки телеметрии.
+#[derive(Debug, Clone)]
+pub struct DeviceRow {
+    pub device_name: String,
+    pub ip_address: String,
+    pub location: Option<String>,
+}
+
+/// Строка telemetry_data, готовая к вставке.
+#[derive(Debug, Clone)]
+pub struct TelemetryRow {
+    pub device_id: i32,
+    pub metric_type_id: i32,
+    pub metric_value: f64,
+    pub is_anomaly: bool,
+    pub severity: Severity,
+    pub action_description: Option<String>,
+    /// Время измерения у источника
+    pub recorded_at: NaiveDateTime,
+    /// Время приёма сервером
+    pub received_at: NaiveDateTime,
+    pub event_id: Option<Uuid>,
+    /// Что пометило строку аномальной
+    pub anomaly_detector: Option<&'static str>,
+    pub anomaly_score: Option<f64>,
+}
+
+/// Одно из последних сохранённых значений пары устройство/метрика.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RecentValue {
+    pub device_id: i32,
+    pub metric_type_id: i32,
+    pub metric_value: f64,
+    pub recorded_at: NaiveDateTime,
+}
+
+/// Сколько измерений подряд пара устройство/метрика держит уровень `severity`.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MetricHealth {
+    pub device_id: i32,
+    pub metric_type_id: i32,
+    pub severity: Severity,
+    pub streak: i32,
+}
+
+/// Статус устройства и состояние одной из его метрик.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DeviceHealthRow {
+    pub id: i32,
+    pub device_name: String,
+    pub status: DeviceStatus,
+    pub severity: Severity,
+    pub streak: i32,
+    /// Когда метрика последний раз присылала значения
+    pub updated_at: NaiveDateTime,
+}
+
+/// Фильтр выборки телеметрии. `None` — без ограничения.
+#[derive(Debug, Default)]
+pub struct TelemetryFilter {
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+    pub device_id: Option<i32>,
+    pub device_name: Option<String>,
+    pub metric_type_id: Option<i32>,
+    pub metric_name: Option<String>,
+    pub anomaly_only: bool,
+    pub location: Option<String>,
+    pub search: Option<String>,
+    /// (recorded_at, id) последней записи предыдущей страницы
+    pub after: Option<(NaiveDateTime, i32)>,
+}
+
+/// Фильтр агрегации: интервал [from, to) и необязательные устройство и метрика.
+#[derive(Debug)]
+pub struct AggregateFilter {
+    pub from: NaiveDateTime,
+    pub to: NaiveDateTime,
+    pub device_id: Option<i32>,
+    pub device_name: Option<String>,
+    pub metric_type_id: Option<i32>,
+    pub metric_name: Option<String>,
+    /// Корзины агрегатов раньше этого времени удалены по сроку хранения
+    /// и досчитываются по сырым строкам
+    pub rollup_since: Option<NaiveDateTime>,
+}
+
+/// Запись пачки телеметрии в одной транзакции.
+/// Без вызова `commit` все изменения откатываются при удалении объекта.
+#[async_trait]
+pub trait TelemetryUnitOfWork: Send {
+    /// Создаёт отсутствующие устройства и возвращает (device_name, id, location) для всех.
+    /// Имена в `devices` должны быть уникальны. location берётся из БД:
+    /// у уже известных устройств она не перезаписывается.
+    async fn upsert_devices(
+        &mut self,
+        devices: &[DeviceRow],
+    ) -> Result<Vec<(String, i32, Option<String>)>, sqlx::Error>;
+
+    /// Вставляет строки, пропуская уже записанные event_id.
+    /// Возвращает event_id каждой вставленной строки.
+    async fn insert_telemetry(&mut self, rows: &[TelemetryRow]) -> Result<Vec<Option<Uuid>>, sqlx::Error>;
+
+    /// До `window_size` последних значений каждой пары (device_id, metric_type_id, window_size),
+    /// новые первыми.
+    async fn recent_values(&mut self, windows: &[(i32, i32, i32)]) -> Result<Vec<RecentValue>, sqlx::Error>;
+
+    /// Состояние пар (device_id, metric_type_id), заблокированное до конца транзакции.
+    async fn lock_metric_health(&mut self, pairs: &[(i32, i32)]) -> Result<Vec<MetricHealth>, sqlx::Error>;
+
+    async fn upsert_metric_health(&mut self, health: &[MetricHealth]) -> Result<(), sqlx::Error>;
+
+    /// Строки состояния всех метрик устройств.
+    async fn get_device_health(&mut self, device_ids: &[i32]) -> Result<Vec<DeviceHealthRow>, sqlx::Error>;
+
+    /// Меняет статус, если устройство не отключено вручную (inactive).
+    async fn set_device_status(&mut self, device_id: i32, status: DeviceStatus) -> Result<bool, sqlx::Error>;
+
+    /// Незакрытые алерты пар (device_id, metric_type_id), заблокированные до конца транзакции.
+    async fn lock_active_alerts(&mut self, pairs: &[(i32, i32)]) -> Result<Vec<ActiveAlert>, sqlx::Error>;
+
+    /// Создаёт алерт вместе с событиями истории и возвращает его id.
+    async fn open_alert(&mut self, alert: &NewAlert) -> Result<i32, sqlx::Error>;
+
+    async fn update_active_alerts(&mut self, updates: &[AlertUpdate]) -> Result<(), sqlx::Error>;
+
+    /// Рассылка событий алертов в этой же транзакции.
+    fn mailbox(&mut self) -> &mut dyn AlertMailbox;
+
+    async fn commit(self: Box<Self>) -> Result<(), sqlx::Error>;
+}
+
+#[async_trait]
+pub trait TelemetryClient {
+    async fn begin(&self) -> Result<Box<dyn TelemetryUnitOfWork>, sqlx::Error>;
+
+    /// Записи по фильтру, новые первыми.
+    async fn list_telemetry(
+        &self,
+        filter: &TelemetryFilter,
+        limit: i64,
+    ) -> Result<Vec<TelemetryRecord>, sqlx::Error>;
+
+    /// Агрегаты по корзинам `bucket` с заполненными пропусками,
+    /// упорядоченные по устройству, метрике и времени. Данные берутся из
+    /// самого крупного уровня агрегатов, кратного `bucket`, и сырых строк,
+    /// ещё не учтённых в нём.
+    async fn aggregate_telemetry(
+        &self,
+        filter: &AggregateFilter,
+        bucket: BucketSize,
+    ) -> Result<Vec<AggregateRow>, sqlx::Error>;
+}
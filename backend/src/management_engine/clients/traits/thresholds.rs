@@ -0,0 +1,51 @@
+use crate::management_engine::models::thresholds::thresholds::{Comparison, ThresholdView};
+use async_trait::async_trait;
+
+/// Фильтры списка порогов; `None` отключает фильтр.
+#[derive(Debug, Clone, Default)]
+pub struct ThresholdFilter {
+    pub metric_name: Option<String>,
+    pub device_name: Option<String>,
+    pub location: Option<String>,
+}
+
+/// Порог, готовый к записи в thresholds.
+#[derive(Debug, Clone)]
+pub struct ThresholdRow {
+    pub metric_type_id: i32,
+    pub device_id: Option<i32>,
+    pub location: Option<String>,
+    pub comparison: Comparison,
+    pub warning_level: Option<f64>,
+    pub critical_level: Option<f64>,
+}
+
+/// Порог в виде, нужном для оценки телеметрии.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ThresholdRule {
+    pub metric_type_id: i32,
+    pub device_id: Option<i32>,
+    pub location: Option<String>,
+    pub comparison: Comparison,
+    pub warning_level: Option<f64>,
+    pub critical_level: Option<f64>,
+}
+
+#[async_trait]
+pub trait ThresholdClient {
+    async fn list_thresholds(&self, filter: &ThresholdFilter) -> Result<Vec<ThresholdView>, sqlx::Error>;
+
+    async fn get_threshold(&self, id: i32) -> Result<Option<ThresholdView>, sqlx::Error>;
+
+    async fn get_device_id(&self, device_name: &str) -> Result<Option<i32>, sqlx::Error>;
+
+    async fn create_threshold(&self, row: &ThresholdRow, created_by: &str) -> Result<i32, sqlx::Error>;
+
+    /// Возвращает `false`, если порог не найден.
+    async fn update_threshold(&self, id: i32, row: &ThresholdRow) -> Result<bool, sqlx::Error>;
+
+    /// Возвращает `false`, если порог не найден.
+    async fn delete_threshold(&self, id: i32) -> Result<bool, sqlx::Error>;
+
+    async fn get_threshold_rules(&self) -> Result<Vec<ThresholdRule>, sqlx::Error>;
+}
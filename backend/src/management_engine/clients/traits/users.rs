@@ -0,0 +1,41 @@
+use crate::management_engine::models::users::users::{UserProfile, UserSummary};
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait UserClient {
+    async fn get_profile(&self, username: &str) -> Result<Option<UserProfile>, sqlx::Error>;
+
+    /// `None` — поле не меняется; для phone_number/organization `Some(None)` очищает поле.
+    async fn update_profile(
+        &self,
+        username: &str,
+        full_name: Option<&str>,
+        email: Option<&str>,
+        phone_number: Option<Option<&str>>,
+        organization: Option<Option<&str>>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn update_password_hash(&self, user_id: i32, password_hash: &str) -> Result<(), sqlx::Error>;
+
+    /// Отзывает все активные refresh-токены пользователя.
+    async fn revoke_user_refresh_tokens(&self, user_id: i32) -> Result<u64, sqlx::Error>;
+
+    async fn list_users(
+        &self,
+        search: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<UserSummary>, sqlx::Error>;
+
+    async fn count_users(&self, search: Option<&str>) -> Result<i64, sqlx::Error>;
+
+    /// Возвращает id пользователя или `None`, если он не найден.
+    async fn set_user_disabled(&self, username: &str, disabled: bool) -> Result<Option<i32>, sqlx::Error>;
+
+    /// `None` — пользователь не найден, `Some(false)` — учётная запись отключена.
+    async fn is_user_active(&self, username: &str) -> Result<Option<bool>, sqlx::Error>;
+
+    /// Удаляет пользователя и его user_info, отвязывая записи, которые на него ссылаются.
+    /// Возвращает id удалённого пользователя.
+    async fn delete_user(&self, username: &str) -> Result<Option<i32>, sqlx::Error>;
+}
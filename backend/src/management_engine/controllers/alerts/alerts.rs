@@ -0,0 +1,218 @@
+use crate::management_engine::clients::clients::alerts::alerts::PgAlertClient;
+use crate::management_engine::clients::traits::alerts::{AlertClient, AlertFilter};
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::controllers::telemetry::query::non_empty;
+use crate::management_engine::models::alerts::alerts::{
+    AlertCommentRequest, AlertDetail, AlertEventType, AlertListResponse, AlertQuery, AlertStatus,
+    AlertView, AssignAlertRequest, ResolveAlertRequest,
+};
+use actix_web::web;
+use tracing::{error, info};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+const MAX_COMMENT_LEN: usize = 2000;
+
+pub async fn list_alerts_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    query: &AlertQuery,
+) -> Result<AlertListResponse, AppError> {
+    let client = PgAlertClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let filter = AlertFilter {
+        status: query.status,
+        active_only: query.active.unwrap_or(false),
+        severity: query.severity,
+        device_name: non_empty(&query.device_name),
+        metric_name: non_empty(&query.metric_name),
+        assigned_to: non_empty(&query.assigned_to),
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let items = client.list_alerts(&filter, limit, offset).await.map_err(|e| {
+        error!("Ошибка получения списка алертов: {:?}", e);
+        AppError::from(e)
+    })?;
+    let total = client.count_alerts(&filter).await.map_err(|e| {
+        error!("Ошибка подсчёта алертов: {:?}", e);
+        AppError::from(e)
+    })?;
+
+    Ok(AlertListResponse { items, total })
+}
+
+pub async fn get_alert_logic(pool: &web::Data<sqlx::PgPool>, id: i32) -> Result<AlertDetail, AppError> {
+    let client = PgAlertClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    alert_detail(&client, id).await
+}
+
+/// Переводит открытый алерт в acknowledged; повторное подтверждение ничего не меняет.
+pub async fn acknowledge_alert_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    id: i32,
+    username: &str,
+) -> Result<AlertDetail, AppError> {
+    let client = PgAlertClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let alert = fetch_alert(&client, id).await?;
+    match alert.status {
+        AlertStatus::Resolved => return Err(AppError::AlertResolved(id)),
+        AlertStatus::Acknowledged => return alert_detail(&client, id).await,
+        AlertStatus::Open => {}
+    }
+
+    match client.acknowledge_alert(id, username).await {
+        Ok(true) => info!("Пользователь {} подтвердил алерт #{}", username, id),
+        // Алерт успели подтвердить или закрыть параллельно
+        Ok(false) => {}
+        Err(e) => {
+            error!("Ошибка подтверждения алерта #{}: {:?}", id, e);
+            return Err(e.into());
+        }
+    }
+    alert_detail(&client, id).await
+}
+
+pub async fn assign_alert_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    id: i32,
+    req: &AssignAlertRequest,
+    username: &str,
+) -> Result<AlertDetail, AppError> {
+    let client = PgAlertClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let assignee = non_empty(&req.username);
+    if let Some(assignee) = &assignee {
+        match client.user_exists(assignee).await {
+            Ok(true) => {}
+            Ok(false) => return Err(AppError::UserNotFound(assignee.clone())),
+            Err(e) => {
+                error!("Ошибка поиска пользователя {}: {:?}", assignee, e);
+                return Err(e.into());
+            }
+        }
+    }
+
+    match client.assign_alert(id, assignee.as_deref(), username).await {
+        Ok(true) => {
+            info!(
+                "Пользователь {} назначил алерт #{} на {}",
+                username,
+                id,
+                assignee.as_deref().unwrap_or("никого")
+            );
+            alert_detail(&client, id).await
+        }
+        Ok(false) => Err(closed_or_missing(&client, id).await),
+        Err(e) => {
+            error!("Ошибка назначения алерта #{}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+pub async fn comment_alert_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    id: i32,
+    req: &AlertCommentRequest,
+    username: &str,
+) -> Result<AlertDetail, AppError> {
+    let client = PgAlertClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let comment = validate_comment(Some(&req.comment))?.ok_or_else(|| {
+        AppError::Validation(vec![FieldError::new(
+            "comment",
+            "required",
+            "Поле comment обязательно",
+        )])
+    })?;
+    fetch_alert(&client, id).await?;
+
+    if let Err(e) = client
+        .add_alert_event(id, AlertEventType::Commented, username, Some(&comment))
+        .await
+    {
+        error!("Ошибка добавления комментария к алерту #{}: {:?}", id, e);
+        return Err(e.into());
+    }
+
+    info!("Пользователь {} прокомментировал алерт #{}", username, id);
+    alert_detail(&client, id).await
+}
+
+pub async fn resolve_alert_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    id: i32,
+    req: &ResolveAlertRequest,
+    username: &str,
+) -> Result<AlertDetail, AppError> {
+    let client = PgAlertClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let comment = validate_comment(req.comment.as_deref())?;
+    match client.resolve_alert(id, username, comment.as_deref()).await {
+        Ok(true) => {
+            info!("Пользователь {} закрыл алерт #{}", username, id);
+            alert_detail(&client, id).await
+        }
+        Ok(false) => Err(closed_or_missing(&client, id).await),
+        Err(e) => {
+            error!("Ошибка закрытия алерта #{}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Комментарий без пробелов по краям; пустой — `None`.
+fn validate_comment(comment: Option<&str>) -> Result<Option<String>, AppError> {
+    let comment = comment.map(str::trim).filter(|c| !c.is_empty());
+    if comment.is_some_and(|c| c.chars().count() > MAX_COMMENT_LEN) {
+        return Err(AppError::Validation(vec![FieldError::new(
+            "comment",
+            "too_long",
+            format!("Поле comment не должно превышать {} символов", MAX_COMMENT_LEN),
+        )]));
+    }
+    Ok(comment.map(str::to_string))
+}
+
+async fn fetch_alert(client: &PgAlertClient, id: i32) -> Result<AlertView, AppError> {
+    match client.get_alert(id).await {
+        Ok(Some(alert)) => Ok(alert),
+        Ok(None) => Err(AppError::AlertNotFound(id)),
+        Err(e) => {
+            error!("Ошибка получения алерта #{}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+async fn alert_detail(client: &PgAlertClient, id: i32) -> Result<AlertDetail, AppError> {
+    let alert = fetch_alert(client, id).await?;
+    let history = client.get_alert_events(id).await.map_err(|e| {
+        error!("Ошибка получения истории алерта #{}: {:?}", id, e);
+        AppError::from(e)
+    })?;
+    Ok(AlertDetail { alert, history })
+}
+
+/// Ошибка для изменения, не затронувшего алерт: он закрыт или не существует.
+async fn closed_or_missing(client: &PgAlertClient, id: i32) -> AppError {
+    match fetch_alert(client, id).await {
+        Ok(_) => AppError::AlertResolved(id),
+        Err(e) => e,
+    }
+}
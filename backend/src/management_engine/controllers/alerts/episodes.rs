@@ -0,0 +1,460 @@
+use crate::management_engine::clients::traits::alerts::{AlertUpdate, NewAlert};
+use crate::management_engine::clients::traits::mailbox::AlertNotice;
+use crate::management_engine::clients::traits::telemetry::{TelemetryRow, TelemetryUnitOfWork};
+use crate::management_engine::models::alerts::alerts::{AlertEventType, AlertStatus};
+use crate::management_engine::models::telemetry::telemetry::Severity;
+use chrono::NaiveDateTime;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::sync::OnceLock;
+use tracing::info;
+
+static ALERT_CONFIG: OnceLock<AlertConfig> = OnceLock::new();
+
+// =========================================================
+// ЭПИЗОДЫ АЛЕРТОВ
+// =========================================================
+//
+// ALERT_AUTO_RESOLVE_AFTER  нормальных измерений подряд, после которых
+//                           незакрытый алерт закрывается автоматически (5)
+//
+// Измерение открывает алерт уровня severity, если оно выше порога warning
+// или critical; аномалия детектора при нормальном уровне считается warning.
+// Пока алерт пары устройство/метрика этого уровня не закрыт, новые такие
+// измерения учитываются в нём (sample_count, last_value). Любое
+// тревожное измерение обнуляет серию нормальных у всех алертов пары.
+// Открытие и автоматическое закрытие рассылаются подписчикам.
+
+#[derive(Debug, Clone)]
+pub struct AlertConfig {
+    pub auto_resolve_after: i32,
+}
+
+impl AlertConfig {
+    pub fn from_env() -> Self {
+        AlertConfig {
+            auto_resolve_after: env::var("ALERT_AUTO_RESOLVE_AFTER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5)
+                .max(1),
+        }
+    }
+}
+
+pub fn alert_config() -> &'static AlertConfig {
+    ALERT_CONFIG.get_or_init(AlertConfig::from_env)
+}
+
+/// Уровень алерта, который открывает или продлевает строка; `None` — строка в норме.
+fn alert_severity(row: &TelemetryRow) -> Option<Severity> {
+    match row.severity {
+        Severity::Ok if row.is_anomaly => Some(Severity::Warning),
+        Severity::Ok => None,
+        severity => Some(severity),
+    }
+}
+
+/// Незакрытый алерт пары в процессе обработки пачки.
+struct Episode {
+    id: Option<i32>,
+    severity: Severity,
+    detector: Option<String>,
+    first_value: f64,
+    last_value: Option<f64>,
+    opened_at: NaiveDateTime,
+    last_seen_at: Option<NaiveDateTime>,
+    new_samples: i32,
+    healthy_streak: i32,
+    resolved: bool,
+    changed: bool,
+}
+
+/// Открывает, продлевает и автоматически закрывает алерты по вставленным
+/// строкам. Вызывается после `update_device_health`: запись в
+/// device_metric_health уже упорядочила параллельные пачки по парам.
+pub async fn track_alerts(
+    uow: &mut Box<dyn TelemetryUnitOfWork>,
+    rows: &[&TelemetryRow],
+) -> Result<(), sqlx::Error> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let config = alert_config();
+
+    let pairs: Vec<(i32, i32)> = rows
+        .iter()
+        .map(|r| (r.device_id, r.metric_type_id))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let mut episodes: BTreeMap<(i32, i32), Vec<Episode>> = BTreeMap::new();
+    for alert in uow.lock_active_alerts(&pairs).await? {
+        episodes
+            .entry((alert.device_id, alert.metric_type_id))
+            .or_default()
+            .push(Episode {
+                id: Some(alert.id),
+                severity: alert.severity,
+                detector: None,
+                first_value: 0.0,
+                last_value: None,
+                opened_at: NaiveDateTime::default(),
+                last_seen_at: None,
+                new_samples: 0,
+                healthy_streak: alert.healthy_streak,
+                resolved: false,
+                changed: false,
+            });
+    }
+
+    let mut ordered = rows.to_vec();
+    ordered.sort_by_key(|r| r.recorded_at);
+    for row in ordered {
+        let pair = episodes.entry((row.device_id, row.metric_type_id)).or_default();
+        let active = pair.iter_mut().filter(|e| !e.resolved);
+
+        let Some(severity) = alert_severity(row) else {
+            for episode in active {
+                episode.healthy_streak += 1;
+                episode.resolved = episode.healthy_streak >= config.auto_resolve_after;
+                episode.changed = true;
+            }
+            continue;
+        };
+
+        let mut deduplicated = false;
+        for episode in active {
+            episode.healthy_streak = 0;
+            episode.changed = true;
+            if episode.severity == severity {
+                episode.last_value = Some(row.metric_value);
+                episode.last_seen_at = Some(row.recorded_at);
+                episode.new_samples += 1;
+                deduplicated = true;
+            }
+        }
+        if !deduplicated {
+            pair.push(Episode {
+                id: None,
+                severity,
+                detector: row.anomaly_detector.map(str::to_string),
+                first_value: row.metric_value,
+                last_value: Some(row.metric_value),
+                opened_at: row.recorded_at,
+                last_seen_at: Some(row.recorded_at),
+                new_samples: 1,
+                healthy_streak: 0,
+                resolved: false,
+                changed: true,
+            });
+        }
+    }
+
+    // Сначала закрываются и продлеваются существующие алерты: пачка может
+    // закрыть алерт и открыть новый того же уровня, а незакрытый алерт
+    // эпизода может быть только один.
+    let mut updates = Vec::new();
+    let mut new_alerts = Vec::new();
+    let mut notices = Vec::new();
+    for ((device_id, metric_type_id), pair) in episodes {
+        for episode in pair.into_iter().filter(|e| e.changed) {
+            match episode.id {
+                Some(id) => {
+                    if episode.resolved {
+                        info!("Алерт #{} закрыт автоматически", id);
+                        notices.push((id, AlertEventType::AutoResolved));
+                    }
+                    updates.push(AlertUpdate {
+                        id,
+                        last_value: episode.last_value,
+                        last_seen_at: episode.last_seen_at,
+                        new_samples: episode.new_samples,
+                        healthy_streak: episode.healthy_streak,
+                        resolved: episode.resolved,
+                    });
+                }
+                None => new_alerts.push(NewAlert {
+                    device_id,
+                    metric_type_id,
+                    severity: episode.severity,
+                    status: if episode.resolved {
+                        AlertStatus::Resolved
+                    } else {
+                        AlertStatus::Open
+                    },
+                    detector: episode.detector,
+                    first_value: episode.first_value,
+                    last_value: episode.last_value.unwrap_or(episode.first_value),
+                    sample_count: episode.new_samples,
+                    healthy_streak: episode.healthy_streak,
+                    opened_at: episode.opened_at,
+                    last_seen_at: episode.last_seen_at.unwrap_or(episode.opened_at),
+                }),
+            }
+        }
+    }
+    if !updates.is_empty() {
+        uow.update_active_alerts(&updates).await?;
+    }
+
+    // Эпизоды пары идут по времени: закрытый в этой же пачке вставляется
+    // раньше открытого после него.
+    for alert in &new_alerts {
+        let id = uow.open_alert(alert).await?;
+        info!(
+            "Открыт алерт #{}: устройство {}, метрика {}, уровень {}",
+            id,
+            alert.device_id,
+            alert.metric_type_id,
+            alert.severity.as_str()
+        );
+        notices.push((id, AlertEventType::Opened));
+        if alert.status == AlertStatus::Resolved {
+            notices.push((id, AlertEventType::AutoResolved));
+        }
+    }
+    if !notices.is_empty() {
+        let notice = AlertNotice {
+            events: &notices,
+            actor: None,
+            note: None,
+            recipient: None,
+        };
+        let delivered = uow.mailbox().notify_alert_subscribers(&notice).await?;
+        if delivered > 0 {
+            info!("Разослано {} сообщений о {} событиях алертов", delivered, notices.len());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::management_engine::clients::traits::alerts::ActiveAlert;
+    use crate::management_engine::clients::traits::mailbox::AlertMailbox;
+    use crate::management_engine::clients::traits::telemetry::{
+        DeviceHealthRow, DeviceRow, MetricHealth, RecentValue,
+    };
+    use crate::management_engine::models::telemetry::telemetry::DeviceStatus;
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    struct StoredAlert {
+        id: i32,
+        device_id: i32,
+        metric_type_id: i32,
+        severity: Severity,
+        healthy_streak: i32,
+        resolved: bool,
+    }
+
+    type Store = Arc<Mutex<Vec<StoredAlert>>>;
+
+    /// Алерты в памяти с тем же ограничением, что alerts_active_episode_key.
+    /// Остальные методы ничего не хранят и возвращают пустой результат.
+    struct FakeUnitOfWork {
+        alerts: Store,
+        mailbox: FakeMailbox,
+    }
+
+    /// Запоминает разосланные события.
+    #[derive(Default)]
+    struct FakeMailbox {
+        events: Arc<Mutex<Vec<(i32, AlertEventType)>>>,
+    }
+
+    #[async_trait]
+    impl AlertMailbox for FakeMailbox {
+        async fn notify_alert_subscribers(&mut self, notice: &AlertNotice<'_>) -> Result<u64, sqlx::Error> {
+            self.events.lock().unwrap().extend_from_slice(notice.events);
+            Ok(notice.events.len() as u64)
+        }
+    }
+
+    #[async_trait]
+    impl TelemetryUnitOfWork for FakeUnitOfWork {
+        async fn upsert_devices(
+            &mut self,
+            _devices: &[DeviceRow],
+        ) -> Result<Vec<(String, i32, Option<String>)>, sqlx::Error> {
+            Ok(Default::default())
+        }
+
+        async fn insert_telemetry(&mut self, _rows: &[TelemetryRow]) -> Result<Vec<Option<Uuid>>, sqlx::Error> {
+            Ok(Default::default())
+        }
+
+        async fn recent_values(&mut self, _windows: &[(i32, i32, i32)]) -> Result<Vec<RecentValue>, sqlx::Error> {
+            Ok(Default::default())
+        }
+
+        async fn lock_metric_health(&mut self, _pairs: &[(i32, i32)]) -> Result<Vec<MetricHealth>, sqlx::Error> {
+            Ok(Default::default())
+        }
+
+        async fn upsert_metric_health(&mut self, _health: &[MetricHealth]) -> Result<(), sqlx::Error> {
+            Ok(())
+        }
+
+        async fn get_device_health(&mut self, _device_ids: &[i32]) -> Result<Vec<DeviceHealthRow>, sqlx::Error> {
+            Ok(Default::default())
+        }
+
+        async fn set_device_status(&mut self, _device_id: i32, _status: DeviceStatus) -> Result<bool, sqlx::Error> {
+            Ok(Default::default())
+        }
+
+        async fn lock_active_alerts(&mut self, pairs: &[(i32, i32)]) -> Result<Vec<ActiveAlert>, sqlx::Error> {
+            Ok(self
+                .alerts
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|a| !a.resolved && pairs.contains(&(a.device_id, a.metric_type_id)))
+                .map(|a| ActiveAlert {
+                    id: a.id,
+                    device_id: a.device_id,
+                    metric_type_id: a.metric_type_id,
+                    severity: a.severity,
+                    healthy_streak: a.healthy_streak,
+                })
+                .collect())
+        }
+
+        async fn open_alert(&mut self, alert: &NewAlert) -> Result<i32, sqlx::Error> {
+            let mut alerts = self.alerts.lock().unwrap();
+            let resolved = alert.status == AlertStatus::Resolved;
+            let conflict = !resolved
+                && alerts.iter().any(|a| {
+                    !a.resolved
+                        && a.device_id == alert.device_id
+                        && a.metric_type_id == alert.metric_type_id
+                        && a.severity == alert.severity
+                });
+            if conflict {
+                return Err(sqlx::Error::Protocol("alerts_active_episode_key".into()));
+            }
+            let id = alerts.len() as i32 + 1;
+            alerts.push(StoredAlert {
+                id,
+                device_id: alert.device_id,
+                metric_type_id: alert.metric_type_id,
+                severity: alert.severity,
+                healthy_streak: alert.healthy_streak,
+                resolved,
+            });
+            Ok(id)
+        }
+
+        async fn update_active_alerts(&mut self, updates: &[AlertUpdate]) -> Result<(), sqlx::Error> {
+            let mut alerts = self.alerts.lock().unwrap();
+            for update in updates {
+                let alert = alerts.iter_mut().find(|a| a.id == update.id).unwrap();
+                alert.healthy_streak = update.healthy_streak;
+                alert.resolved |= update.resolved;
+            }
+            Ok(())
+        }
+
+        fn mailbox(&mut self) -> &mut dyn AlertMailbox {
+            &mut self.mailbox
+        }
+
+        async fn commit(self: Box<Self>) -> Result<(), sqlx::Error> {
+            Ok(())
+        }
+    }
+
+    fn row(minute: i64, severity: Severity) -> TelemetryRow {
+        let at = NaiveDateTime::default() + Duration::minutes(minute);
+        TelemetryRow {
+            device_id: 1,
+            metric_type_id: 1,
+            metric_value: if severity == Severity::Ok { 10.0 } else { 95.0 },
+            is_anomaly: severity != Severity::Ok,
+            severity,
+            action_description: None,
+            recorded_at: at,
+            received_at: at,
+            event_id: None,
+            anomaly_detector: None,
+            anomaly_score: None,
+        }
+    }
+
+    /// Тревожное измерение, `healthy` нормальных и снова тревожное того же уровня.
+    fn reopening_batch(start: i64, healthy: i32) -> Vec<TelemetryRow> {
+        let mut rows = vec![row(start, Severity::Critical)];
+        rows.extend((1..=healthy as i64).map(|i| row(start + i, Severity::Ok)));
+        rows.push(row(start + healthy as i64 + 1, Severity::Critical));
+        rows
+    }
+
+    /// Возвращает разосланные события.
+    async fn track(store: &Store, rows: &[TelemetryRow]) -> Result<Vec<(i32, AlertEventType)>, sqlx::Error> {
+        let mailbox = FakeMailbox::default();
+        let events = mailbox.events.clone();
+        let mut uow: Box<dyn TelemetryUnitOfWork> = Box::new(FakeUnitOfWork {
+            alerts: store.clone(),
+            mailbox,
+        });
+        let refs: Vec<&TelemetryRow> = rows.iter().collect();
+        track_alerts(&mut uow, &refs).await?;
+        Ok(events.lock().unwrap().clone())
+    }
+
+    /// (id, закрыт) всех алертов хранилища.
+    fn states(store: &Store) -> Vec<(i32, bool)> {
+        store.lock().unwrap().iter().map(|a| (a.id, a.resolved)).collect()
+    }
+
+    #[actix_web::test]
+    async fn reopens_episode_resolved_in_same_batch() {
+        let store = Store::default();
+        let healthy = alert_config().auto_resolve_after;
+        track(&store, &reopening_batch(0, healthy)).await.unwrap();
+
+        assert_eq!(states(&store), vec![(1, true), (2, false)]);
+    }
+
+    #[actix_web::test]
+    async fn notifies_opened_and_auto_resolved_episodes() {
+        let store = Store::default();
+        let healthy = alert_config().auto_resolve_after;
+        let events = track(&store, &reopening_batch(0, healthy)).await.unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                (1, AlertEventType::Opened),
+                (1, AlertEventType::AutoResolved),
+                (2, AlertEventType::Opened),
+            ]
+        );
+    }
+
+    #[actix_web::test]
+    async fn reopens_stored_episode_resolved_by_batch() {
+        let store = Store::default();
+        let healthy = alert_config().auto_resolve_after;
+        let rows = reopening_batch(0, healthy);
+        track(&store, &rows[..1]).await.unwrap();
+        assert_eq!(states(&store), vec![(1, false)]);
+
+        track(&store, &rows[1..]).await.unwrap();
+        assert_eq!(states(&store), vec![(1, true), (2, false)]);
+    }
+
+    #[actix_web::test]
+    async fn keeps_episode_open_below_resolve_streak() {
+        let store = Store::default();
+        let healthy = alert_config().auto_resolve_after - 1;
+        track(&store, &reopening_batch(0, healthy)).await.unwrap();
+
+        assert_eq!(states(&store), vec![(1, false)]);
+    }
+}
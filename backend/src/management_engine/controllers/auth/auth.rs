@@ -1,156 +1,663 @@
 use crate::management_engine::clients::traits::auth::AuthClient;
-use crate::management_engine::models::auth::auth::{AuthRequest, Claims, TokenResponse};
+use crate::management_engine::models::auth::auth::{
+    Claims, LoginRequest, RefreshRequest, RegisterRequest, TokenResponse,
+};
 use crate::management_engine::clients::clients::auth::auth::PgAuthClient;
+use crate::management_engine::clients::clients::roles::roles::PgRoleClient;
+use crate::management_engine::clients::traits::roles::RoleClient;
+use crate::management_engine::controllers::auth::keys::signing_keys;
+use crate::management_engine::controllers::auth::lockout::{
+    LOG_ACCOUNT_UNLOCKED, LOG_LOGIN_FAILED, LOG_LOGIN_SUCCESS, ensure_login_allowed,
+    record_login_attempt,
+};
+use crate::management_engine::controllers::auth::validation::validate_registration;
+use crate::management_engine::controllers::errors::AppError;
 use actix_web::web;
 use bcrypt::{hash, verify};
 use chrono::Utc;
-use jsonwebtoken::{EncodingKey, Header, encode};
-use tracing::{error, info};
+use sha2::{Digest, Sha256};
+use tracing::{error, info, warn};
+use uuid::Uuid;
 
+/// Роль новых пользователей. Набор ролей и их прав управляется
+/// через /admin/roles.
 const DEFAULT_ROLE: &str = "оператор";
 
 
-pub const SECRET_KEY: &str = "your_super_secret_key_change_me";
+/// Время жизни access-токена
+const ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+/// Время жизни refresh-токена
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
 
 pub async fn register_logic(
     pool: &web::Data<sqlx::PgPool>,
-    req: &AuthRequest,
-) -> Result<TokenResponse, String> {
+    req: &RegisterRequest,
+) -> Result<TokenResponse, AppError> {
     let client = PgAuthClient {
         pool: pool.get_ref().clone(),
     };
 
     info!("Регистрация пользователя: {}", req.username);
 
-    // Проверка существующего пользователя
-    match client.get_user_details(&req.username).await {
-        Ok(Some(_)) => {
-            info!("Пользователь {} уже существует", req.username);
-            return Err("Пользователь уже существует".to_string());
-        }
-        Err(e) => {
-            error!("Ошибка проверки существующего пользователя: {:?}", e);
-            return Err("Ошибка базы данных".to_string());
+    // Проверка и нормализация всех полей
+    let valid = match validate_registration(req) {
+        Ok(valid) => valid,
+        Err(errors) => {
+            info!("Ошибки валидации регистрации {}: {:?}", req.username, errors);
+            return Err(AppError::Validation(errors));
         }
-        _ => {}
-    }
+    };
 
     // Получение роли
     let role_id = match client.get_role_id(DEFAULT_ROLE).await {
         Ok(Some(id)) => id,
         Ok(None) => {
-            error!("Роль '{}' не найдена", DEFAULT_ROLE);
-            return Err("Роль не найдена".to_string());
+            error!("Роль по умолчанию '{}' не найдена", DEFAULT_ROLE);
+            return Err(AppError::Internal(format!("роль '{}' не найдена", DEFAULT_ROLE)));
         }
         Err(e) => {
             error!("Ошибка получения роли: {:?}", e);
-            return Err("Ошибка базы данных".to_string());
+            return Err(e.into());
         }
     };
 
-    // Проверка обязательных полей user_info
-    let full_name = req.full_name.as_ref().ok_or("Поле full_name обязательно")?;
-    let email = req.email.as_ref().ok_or("Поле email обязательно")?;
+    // Хэширование пароля (до начала транзакции)
+    let hashed = match hash(&req.password, 12) {
+        Ok(h) => h,
+        Err(e) => {
+            error!("Ошибка хэширования пароля: {:?}", e);
+            return Err(AppError::Internal(e.to_string()));
+        }
+    };
 
-    info!("Создание user_info для {} / {}", full_name, email);
-    let user_info_id = match client
+    // user_info и users создаются в одной транзакции: при любой ошибке
+    // транзакция откатывается при удалении `uow`
+    let mut uow = match client.begin().await {
+        Ok(uow) => uow,
+        Err(e) => {
+            error!("Ошибка начала транзакции: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    info!("Создание user_info для {} / {}", valid.full_name, valid.email);
+    let user_info_id = match uow
         .create_user_info(
-            full_name,
-            email,
-            req.phone_number.as_deref(),
-            req.organization.as_deref(),
+            &valid.full_name,
+            &valid.email,
+            valid.phone_number.as_deref(),
+            valid.organization.as_deref(),
         )
         .await
     {
         Ok(id) => id,
         Err(e) => {
             error!("Ошибка создания user_info: {:?}", e);
-            return Err("Ошибка создания информации о пользователе".to_string());
+            return Err(e.into());
         }
     };
 
-    // Хэширование пароля
-    let hashed = match hash(&req.password, 12) {
-        Ok(h) => h,
+    // Уникальность username обеспечивает ограничение в БД
+    info!("Создание пользователя {}", req.username);
+    let user_id = match uow
+        .create_user(&valid.username, &hashed, role_id, user_info_id)
+        .await
+    {
+        Ok(id) => id,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            info!("Пользователь {} уже существует", req.username);
+            return Err(AppError::UserExists);
+        }
         Err(e) => {
-            error!("Ошибка хэширования пароля: {:?}", e);
-            return Err("Ошибка хэширования пароля".to_string());
+            error!("Ошибка создания пользователя: {:?}", e);
+            return Err(e.into());
         }
     };
 
-    // Создание пользователя
-    info!("Создание пользователя {}", req.username);
-    if let Err(e) = client
-        .create_user(&req.username, &hashed, role_id, user_info_id)
-        .await
-    {
-        error!("Ошибка создания пользователя: {:?}", e);
-        return Err("Ошибка создания пользователя".to_string());
+    if let Err(e) = uow.commit().await {
+        error!("Ошибка фиксации транзакции регистрации: {:?}", e);
+        return Err(e.into());
     }
 
-    let token =
-        generate_token(&req.username, DEFAULT_ROLE).map_err(|_| "Ошибка генерации токена")?;
+    let roles = PgRoleClient {
+        pool: client.pool.clone(),
+    };
+    let resp = issue_tokens(&client, &roles, user_id, &req.username, DEFAULT_ROLE, None).await?;
 
     info!("Пользователь {} успешно зарегистрирован", req.username);
-    Ok(TokenResponse { token })
+    Ok(resp)
 }
 
 pub async fn login_logic(
     pool: &web::Data<sqlx::PgPool>,
-    req: &AuthRequest,
-) -> Result<TokenResponse, String> {
+    req: &LoginRequest,
+    ip_address: Option<&str>,
+) -> Result<TokenResponse, AppError> {
     let client = PgAuthClient {
         pool: pool.get_ref().clone(),
     };
 
-    info!("Попытка входа пользователя: {}", req.username);
+    info!("Попытка входа пользователя: {} с IP {:?}", req.username, ip_address);
 
-    let user_opt: Option<(String, String)> = match client.get_user_details(&req.username).await {
+    // Блокировка после серии неудачных попыток (по имени и по IP)
+    ensure_login_allowed(&client, &req.username, ip_address).await?;
+
+    let user_opt: Option<(i32, String, String, bool)> = match client.get_user_details(&req.username).await {
         Ok(opt) => opt,
         Err(e) => {
             error!("Ошибка обращения к базе данных: {:?}", e);
-            return Err("Ошибка обращения к базе данных".to_string());
+            return Err(e.into());
         }
     };
 
-    if let Some((hashed, role)) = user_opt {
+    let mut user_id = None;
+    if let Some((id, hashed, role, disabled)) = user_opt {
+        user_id = Some(id);
         match verify(&req.password, &hashed) {
+            // Об отключении сообщаем только после проверки пароля
+            Ok(true) if disabled => {
+                info!("Вход отключённого пользователя {} отклонён", req.username);
+                return Err(AppError::AccountDisabled);
+            }
             Ok(true) => {
-                let token =
-                    generate_token(&req.username, &role).map_err(|_| "Ошибка генерации токена")?;
+                let roles = PgRoleClient {
+                    pool: client.pool.clone(),
+                };
+                let resp = issue_tokens(&client, &roles, id, &req.username, &role, None).await?;
+                record_login_attempt(&client, Some(id), LOG_LOGIN_SUCCESS, &req.username, ip_address)
+                    .await;
                 info!("Пользователь {} успешно вошел", req.username);
-                return Ok(TokenResponse { token });
-            }
-            Ok(false) => {
-                info!("Неверный пароль для пользователя {}", req.username);
+                return Ok(resp);
             }
+            Ok(false) => {}
             Err(e) => {
                 error!("Ошибка проверки пароля: {:?}", e);
-                return Err("Ошибка проверки пароля".to_string());
+                return Err(AppError::Internal(e.to_string()));
             }
         }
     } else {
         info!("Пользователь {} не найден", req.username);
     }
 
-    Err("Неверные учетные данные".to_string())
+    record_login_attempt(&client, user_id, LOG_LOGIN_FAILED, &req.username, ip_address).await;
+    Err(AppError::InvalidCredentials)
+}
+
+pub async fn refresh_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    req: &RefreshRequest,
+) -> Result<TokenResponse, AppError> {
+    let client = PgAuthClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let token_hash = hash_token(&req.refresh_token);
+    let stored = match client.get_refresh_token(&token_hash).await {
+        Ok(Some(t)) => t,
+        Ok(None) => {
+            info!("Refresh-токен не найден");
+            return Err(AppError::InvalidRefreshToken);
+        }
+        Err(e) => {
+            error!("Ошибка получения refresh-токена: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    if stored.revoked_at.is_some() {
+        info!("Refresh-токен семейства {} отозван", stored.family_id);
+        return Err(AppError::InvalidRefreshToken);
+    }
+
+    if stored.user_disabled {
+        info!("Refresh-токен отключённого пользователя {} отклонён", stored.username);
+        return Err(AppError::AccountDisabled);
+    }
+
+    if stored.expires_at <= Utc::now().naive_utc() {
+        info!("Refresh-токен пользователя {} истёк", stored.username);
+        return Err(AppError::RefreshTokenExpired);
+    }
+
+    // Повторное использование уже ротированного токена — признак кражи:
+    // отзываем всё семейство
+    let rotated = match client.rotate_refresh_token(stored.id).await {
+        Ok(rotated) => rotated && stored.rotated_at.is_none(),
+        Err(e) => {
+            error!("Ошибка ротации refresh-токена: {:?}", e);
+            return Err(e.into());
+        }
+    };
+    if !rotated {
+        warn!(
+            "Повторное использование refresh-токена пользователя {}, семейство {} отозвано",
+            stored.username, stored.family_id
+        );
+        if let Err(e) = client.revoke_refresh_family(&stored.family_id).await {
+            error!("Ошибка отзыва семейства refresh-токенов: {:?}", e);
+        }
+        return Err(AppError::RefreshTokenReused);
+    }
+
+    let roles = PgRoleClient {
+        pool: client.pool.clone(),
+    };
+    let resp = issue_tokens(
+        &client,
+        &roles,
+        stored.user_id,
+        &stored.username,
+        &stored.role_name,
+        Some(&stored.family_id),
+    )
+    .await?;
+
+    info!("Токены пользователя {} обновлены", stored.username);
+    Ok(resp)
+}
+
+pub async fn logout_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    req: &RefreshRequest,
+) -> Result<(), AppError> {
+    let client = PgAuthClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let token_hash = hash_token(&req.refresh_token);
+    let stored = match client.get_refresh_token(&token_hash).await {
+        Ok(Some(t)) => t,
+        Ok(None) => return Err(AppError::InvalidRefreshToken),
+        Err(e) => {
+            error!("Ошибка получения refresh-токена: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    match client.revoke_refresh_family(&stored.family_id).await {
+        Ok(count) => {
+            info!(
+                "Пользователь {} вышел, отозвано {} refresh-токенов",
+                stored.username, count
+            );
+            Ok(())
+        }
+        Err(e) => {
+            error!("Ошибка отзыва семейства refresh-токенов: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Выдаёт пару access/refresh. Без `family_id` начинается новое семейство (новый вход).
+/// Пустая `role` — роль снята: токен выдаётся без прав.
+async fn issue_tokens(
+    client: &(impl AuthClient + Sync),
+    roles: &(impl RoleClient + Sync),
+    user_id: i32,
+    username: &str,
+    role: &str,
+    family_id: Option<&str>,
+) -> Result<TokenResponse, AppError> {
+    // Права берутся из БД при каждой выдаче, поэтому изменения роли
+    // применяются не позже следующего refresh
+    let permissions = match roles.get_role_permissions(role).await {
+        Ok(permissions) => permissions,
+        Err(e) => {
+            error!("Ошибка получения прав роли '{}': {:?}", role, e);
+            return Err(e.into());
+        }
+    };
+
+    let token = generate_token(username, role, permissions).map_err(|e| {
+        error!("Ошибка генерации токена: {:?}", e);
+        AppError::Internal(e.to_string())
+    })?;
+
+    let family_id = family_id
+        .map(str::to_owned)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let refresh_token = format!(
+        "{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    );
+    let expires_at = (Utc::now() + chrono::Duration::days(REFRESH_TOKEN_TTL_DAYS)).naive_utc();
+
+    if let Err(e) = client
+        .create_refresh_token(
+            user_id,
+            &family_id,
+            &hash_token(&refresh_token),
+            expires_at,
+        )
+        .await
+    {
+        error!("Ошибка сохранения refresh-токена: {:?}", e);
+        return Err(e.into());
+    }
+
+    Ok(TokenResponse {
+        token,
+        refresh_token,
+        expires_in: ACCESS_TOKEN_TTL_MINUTES * 60,
+    })
+}
+
+/// SHA-256 хэш токена в hex. В БД хранятся только хэши refresh-токенов
+/// и токенов сброса пароля.
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
 }
 
-fn generate_token(username: &str, role: &str) -> Result<String, jsonwebtoken::errors::Error> {
+fn generate_token(
+    username: &str,
+    role: &str,
+    permissions: Vec<String>,
+) -> Result<String, jsonwebtoken::errors::Error> {
     let expiration = Utc::now()
-        .checked_add_signed(chrono::Duration::hours(24))
+        .checked_add_signed(chrono::Duration::minutes(ACCESS_TOKEN_TTL_MINUTES))
         .expect("valid timestamp")
         .timestamp() as usize;
 
     let claims = Claims {
         sub: username.to_owned(),
         role: role.to_owned(),
+        permissions,
         exp: expiration,
     };
 
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(SECRET_KEY.as_ref()),
-    )
+    signing_keys().encode(&claims)
+}
+
+pub fn decode_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    signing_keys().decode::<Claims>(token)
+}
+
+/// Снимает блокировку входа: неудачные попытки до этой записи больше не учитываются.
+pub async fn unlock_account_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    admin: &str,
+) -> Result<(), AppError> {
+    let client = PgAuthClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let user_id = match client.get_user_id(username).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Err(AppError::UserNotFound(username.to_string())),
+        Err(e) => {
+            error!("Ошибка обращения к базе данных: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    let details = format!("Разблокирован администратором {}", admin);
+    if let Err(e) = client
+        .log_auth_event(Some(user_id), LOG_ACCOUNT_UNLOCKED, Some(&details), username, None)
+        .await
+    {
+        error!("Ошибка записи разблокировки в logs: {:?}", e);
+        return Err(e.into());
+    }
+
+    info!("Пользователь {} разблокирован администратором {}", username, admin);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::management_engine::clients::clients::users::users::PgUserClient;
+    use crate::management_engine::clients::traits::auth::AuthUnitOfWork;
+    use crate::management_engine::clients::traits::users::UserClient;
+    use crate::management_engine::controllers::auth::keys::init_test_signing_keys;
+    use crate::management_engine::controllers::auth::permissions::{TELEMETRY_READ, TELEMETRY_WRITE};
+    use crate::management_engine::models::auth::auth::{LoginRequest, RefreshRequest, RefreshToken, RegisterRequest};
+    use crate::management_engine::models::roles::roles::RoleWithPermissions;
+    use async_trait::async_trait;
+    use chrono::NaiveDateTime;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    const PASSWORD: &str = "Passw0rd-test";
+
+    /// Записывает выданные refresh-токены: (user_id, family_id).
+    #[derive(Default)]
+    struct FakeAuthClient {
+        refresh_tokens: Mutex<Vec<(i32, String)>>,
+    }
+
+    #[async_trait]
+    impl AuthClient for FakeAuthClient {
+        async fn begin(&self) -> Result<Box<dyn AuthUnitOfWork>, sqlx::Error> {
+            // Транзакции в этих тестах не нужны
+            Err(sqlx::Error::PoolClosed)
+        }
+
+        async fn get_user_details(
+            &self,
+            _username: &str,
+        ) -> Result<Option<(i32, String, String, bool)>, sqlx::Error> {
+            Ok(None)
+        }
+
+        async fn get_role_id(&self, _role_name: &str) -> Result<Option<i32>, sqlx::Error> {
+            Ok(None)
+        }
+
+        async fn get_user_id(&self, _username: &str) -> Result<Option<i32>, sqlx::Error> {
+            Ok(None)
+        }
+
+        async fn create_refresh_token(
+            &self,
+            user_id: i32,
+            family_id: &str,
+            _token_hash: &str,
+            _expires_at: NaiveDateTime,
+        ) -> Result<(), sqlx::Error> {
+            self.refresh_tokens.lock().unwrap().push((user_id, family_id.to_string()));
+            Ok(())
+        }
+
+        async fn get_refresh_token(&self, _token_hash: &str) -> Result<Option<RefreshToken>, sqlx::Error> {
+            Ok(None)
+        }
+
+        async fn rotate_refresh_token(&self, _id: i32) -> Result<bool, sqlx::Error> {
+            Ok(false)
+        }
+
+        async fn revoke_refresh_family(&self, _family_id: &str) -> Result<u64, sqlx::Error> {
+            Ok(0)
+        }
+
+        async fn log_auth_event(
+            &self,
+            _user_id: Option<i32>,
+            _action: &str,
+            _details: Option<&str>,
+            _username: &str,
+            _ip_address: Option<&str>,
+        ) -> Result<(), sqlx::Error> {
+            Ok(())
+        }
+
+        async fn get_failed_logins_by_username(
+            &self,
+            _username: &str,
+            _window_secs: f64,
+        ) -> Result<(i64, Option<f64>), sqlx::Error> {
+            Ok((0, None))
+        }
+
+        async fn get_failed_logins_by_ip(
+            &self,
+            _ip_address: &str,
+            _window_secs: f64,
+        ) -> Result<(i64, Option<f64>), sqlx::Error> {
+            Ok((0, None))
+        }
+    }
+
+    /// Права ролей в памяти; как и SELECT_ROLE_PERMISSIONS, для
+    /// неизвестной или пустой роли возвращает пустой список.
+    #[derive(Default)]
+    struct FakeRoleClient {
+        permissions: HashMap<String, Vec<String>>,
+    }
+
+    #[async_trait]
+    impl RoleClient for FakeRoleClient {
+        async fn list_roles(&self) -> Result<Vec<RoleWithPermissions>, sqlx::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn get_role(&self, _role_name: &str) -> Result<Option<RoleWithPermissions>, sqlx::Error> {
+            Ok(None)
+        }
+
+        async fn get_role_permissions(&self, role_name: &str) -> Result<Vec<String>, sqlx::Error> {
+            Ok(self.permissions.get(role_name).cloned().unwrap_or_default())
+        }
+
+        async fn create_role(&self, _role_name: &str, _permissions: &[String]) -> Result<i32, sqlx::Error> {
+            Ok(0)
+        }
+
+        async fn set_role_permissions(&self, _role_id: i32, _permissions: &[String]) -> Result<(), sqlx::Error> {
+            Ok(())
+        }
+
+        async fn assign_user_role(&self, _username: &str, _role_name: &str) -> Result<bool, sqlx::Error> {
+            Ok(false)
+        }
+
+        async fn revoke_user_role(&self, _username: &str) -> Result<bool, sqlx::Error> {
+            Ok(false)
+        }
+    }
+
+    fn operator_roles() -> FakeRoleClient {
+        FakeRoleClient {
+            permissions: HashMap::from([(
+                DEFAULT_ROLE.to_string(),
+                vec![TELEMETRY_READ.to_string(), TELEMETRY_WRITE.to_string()],
+            )]),
+        }
+    }
+
+    #[actix_web::test]
+    async fn issues_tokens_with_role_permissions() {
+        init_test_signing_keys();
+        let client = FakeAuthClient::default();
+
+        let tokens = issue_tokens(&client, &operator_roles(), 7, "user", DEFAULT_ROLE, None)
+            .await
+            .unwrap();
+
+        let claims = decode_token(&tokens.token).unwrap();
+        assert_eq!(claims.sub, "user");
+        assert_eq!(claims.role, DEFAULT_ROLE);
+        assert_eq!(claims.permissions, [TELEMETRY_READ, TELEMETRY_WRITE]);
+        let stored = client.refresh_tokens.lock().unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].0, 7);
+    }
+
+    #[actix_web::test]
+    async fn issues_tokens_without_permissions_after_role_revoked() {
+        init_test_signing_keys();
+        let client = FakeAuthClient::default();
+
+        // Снятая роль приходит из БД пустой строкой (LEFT JOIN roles)
+        let tokens = issue_tokens(&client, &operator_roles(), 7, "user", "", None)
+            .await
+            .expect("выдача токенов без роли");
+
+        let claims = decode_token(&tokens.token).unwrap();
+        assert_eq!(claims.role, "");
+        assert!(claims.permissions.is_empty());
+        assert_eq!(client.refresh_tokens.lock().unwrap().len(), 1);
+    }
+
+    #[actix_web::test]
+    async fn refresh_keeps_token_family() {
+        init_test_signing_keys();
+        let client = FakeAuthClient::default();
+        let roles = operator_roles();
+
+        issue_tokens(&client, &roles, 7, "user", "", Some("family-1")).await.unwrap();
+        issue_tokens(&client, &roles, 7, "user", "", None).await.unwrap();
+
+        let stored = client.refresh_tokens.lock().unwrap();
+        assert_eq!(stored[0].1, "family-1");
+        assert_ne!(stored[1].1, "family-1");
+    }
+
+    #[actix_web::test]
+    #[ignore = "requires DATABASE_URL"]
+    async fn login_and_refresh_after_role_revoked() {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL");
+        let pool = web::Data::new(sqlx::PgPool::connect(&url).await.expect("подключение к DATABASE_URL"));
+        init_test_signing_keys();
+
+        let username = format!("revoked_{}", &Uuid::new_v4().simple().to_string()[..8]);
+        register_logic(
+            &pool,
+            &RegisterRequest {
+                username: username.clone(),
+                password: PASSWORD.to_string(),
+                full_name: "Тестовый Пользователь".to_string(),
+                email: format!("{}@example.com", username),
+                phone_number: None,
+                organization: None,
+            },
+        )
+        .await
+        .expect("регистрация");
+
+        let role_client = PgRoleClient {
+            pool: pool.get_ref().clone(),
+        };
+        assert!(role_client.revoke_user_role(&username).await.unwrap());
+
+        let login = login_logic(
+            &pool,
+            &LoginRequest {
+                username: username.clone(),
+                password: PASSWORD.to_string(),
+            },
+            None,
+        )
+        .await;
+        let refreshed = match &login {
+            Ok(tokens) => Some(
+                refresh_logic(
+                    &pool,
+                    &RefreshRequest {
+                        refresh_token: tokens.refresh_token.clone(),
+                    },
+                )
+                .await,
+            ),
+            Err(_) => None,
+        };
+
+        let user_client = PgUserClient {
+            pool: pool.get_ref().clone(),
+        };
+        user_client.delete_user(&username).await.unwrap();
+
+        let login = login.expect("вход без роли");
+        let claims = decode_token(&login.token).unwrap();
+        assert_eq!(claims.role, "");
+        assert!(claims.permissions.is_empty());
+        refreshed.unwrap().expect("обновление токена без роли");
+    }
 }
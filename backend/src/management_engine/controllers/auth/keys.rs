@@ -0,0 +1,371 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, decode_header, encode,
+};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::info;
+
+// =========================================================
+// КОНФИГУРАЦИЯ КЛЮЧЕЙ ПОДПИСИ JWT
+// =========================================================
+//
+// JWT_ALGORITHM       HS256 (по умолчанию) | RS256 | EdDSA
+// JWT_ACTIVE_KID      kid ключа, которым подписываются новые токены ("default")
+// JWT_SECRET          секрет для HS256, не короче MIN_SECRET_LEN байт
+// JWT_PREVIOUS_SECRETS  старые HS256-секреты "kid:secret,kid:secret" (только проверка);
+//                     допускается только при JWT_ALGORITHM=HS256
+// JWT_KEYS_DIR        каталог с PEM-файлами для RS256/EdDSA ("keys"):
+//                       <kid>.private.pem — закрытый ключ активного kid
+//                       <kid>.public.pem  — открытые ключи; все они принимаются при
+//                                           проверке и публикуются в JWKS
+//
+// Ротация для RS256/EdDSA: новый kid получает пару PEM-файлов и становится
+// JWT_ACTIVE_KID, а <kid>.public.pem старого ключа остаётся в каталоге, пока
+// не истекут подписанные им токены.
+
+static SIGNING_KEYS: OnceLock<SigningKeys> = OnceLock::new();
+
+/// Минимальная длина HS256-секрета: не меньше размера выхода SHA-256.
+const MIN_SECRET_LEN: usize = 32;
+
+/// DER-префикс SubjectPublicKeyInfo для Ed25519 (OID 1.3.101.112)
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeyError {
+    #[error("переменная окружения {0} не задана")]
+    MissingVar(&'static str),
+    #[error("неподдерживаемый алгоритм JWT: {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("некорректная запись в JWT_PREVIOUS_SECRETS: {0}")]
+    InvalidSecretEntry(String),
+    #[error("JWT_PREVIOUS_SECRETS допустим только для HS256, для {0:?} старые открытые ключи остаются в JWT_KEYS_DIR")]
+    PreviousSecretsNotSupported(Algorithm),
+    #[error("секрет ключа '{0}' короче {MIN_SECRET_LEN} байт")]
+    WeakSecret(String),
+    #[error("ошибка чтения {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+    #[error("в {0} не найден открытый ключ")]
+    NoPublicKey(PathBuf),
+    #[error("ключ {0} не является ключом RSA или Ed25519")]
+    UnsupportedKey(PathBuf),
+    #[error("ключ {0} не соответствует алгоритму {1:?}")]
+    AlgorithmMismatch(String, Algorithm),
+    #[error("для активного kid '{0}' не найден открытый ключ")]
+    MissingActivePublicKey(String),
+    #[error("некорректный ключ {0}: {1}")]
+    Jwt(PathBuf, jsonwebtoken::errors::Error),
+}
+
+struct VerificationKey {
+    algorithm: Algorithm,
+    key: DecodingKey,
+}
+
+pub struct SigningKeys {
+    active_kid: String,
+    algorithm: Algorithm,
+    encoding: EncodingKey,
+    verification: HashMap<String, VerificationKey>,
+    jwks: JwkSet,
+}
+
+fn ensure_strong_secret(kid: &str, secret: &str) -> Result<(), KeyError> {
+    if secret.len() < MIN_SECRET_LEN {
+        return Err(KeyError::WeakSecret(kid.to_string()));
+    }
+    Ok(())
+}
+
+/// Разбирает JWT_PREVIOUS_SECRETS в пары (kid, секрет).
+fn parse_previous_secrets(algorithm: Algorithm, value: &str) -> Result<Vec<(&str, &str)>, KeyError> {
+    let mut secrets = Vec::new();
+    for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        if algorithm != Algorithm::HS256 {
+            return Err(KeyError::PreviousSecretsNotSupported(algorithm));
+        }
+        let (kid, secret) = entry
+            .split_once(':')
+            .ok_or_else(|| KeyError::InvalidSecretEntry(entry.to_string()))?;
+        ensure_strong_secret(kid, secret)?;
+        secrets.push((kid, secret));
+    }
+    Ok(secrets)
+}
+
+impl SigningKeys {
+    pub fn from_env() -> Result<Self, KeyError> {
+        let algorithm = match env::var("JWT_ALGORITHM").as_deref() {
+            Err(_) | Ok("HS256") => Algorithm::HS256,
+            Ok("RS256") => Algorithm::RS256,
+            Ok("EdDSA") => Algorithm::EdDSA,
+            Ok(other) => return Err(KeyError::UnsupportedAlgorithm(other.to_string())),
+        };
+        let active_kid = env::var("JWT_ACTIVE_KID").unwrap_or_else(|_| "default".to_string());
+
+        let mut verification = HashMap::new();
+        let mut jwks = JwkSet { keys: Vec::new() };
+
+        if let Ok(previous) = env::var("JWT_PREVIOUS_SECRETS") {
+            for (kid, secret) in parse_previous_secrets(algorithm, &previous)? {
+                verification.insert(
+                    kid.to_string(),
+                    VerificationKey {
+                        algorithm: Algorithm::HS256,
+                        key: DecodingKey::from_secret(secret.as_bytes()),
+                    },
+                );
+            }
+        }
+
+        let encoding = match algorithm {
+            Algorithm::HS256 => {
+                let secret = env::var("JWT_SECRET").map_err(|_| KeyError::MissingVar("JWT_SECRET"))?;
+                ensure_strong_secret(&active_kid, &secret)?;
+                verification.insert(
+                    active_kid.clone(),
+                    VerificationKey {
+                        algorithm,
+                        key: DecodingKey::from_secret(secret.as_bytes()),
+                    },
+                );
+                EncodingKey::from_secret(secret.as_bytes())
+            }
+            _ => {
+                let dir = PathBuf::from(env::var("JWT_KEYS_DIR").unwrap_or_else(|_| "keys".to_string()));
+                for (kid, jwk) in load_public_keys(&dir)? {
+                    let key_algorithm = jwk_algorithm(&jwk);
+                    let key = DecodingKey::from_jwk(&jwk)
+                        .map_err(|e| KeyError::Jwt(dir.join(format!("{kid}.public.pem")), e))?;
+                    verification.insert(
+                        kid,
+                        VerificationKey {
+                            algorithm: key_algorithm,
+                            key,
+                        },
+                    );
+                    jwks.keys.push(jwk);
+                }
+
+                match verification.get(&active_kid) {
+                    None => return Err(KeyError::MissingActivePublicKey(active_kid)),
+                    Some(k) if k.algorithm != algorithm => {
+                        return Err(KeyError::AlgorithmMismatch(active_kid, algorithm));
+                    }
+                    Some(_) => {}
+                }
+
+                let private_path = dir.join(format!("{active_kid}.private.pem"));
+                let pem = fs::read(&private_path).map_err(|e| KeyError::Io(private_path.clone(), e))?;
+                let key = if algorithm == Algorithm::RS256 {
+                    EncodingKey::from_rsa_pem(&pem)
+                } else {
+                    EncodingKey::from_ed_pem(&pem)
+                };
+                key.map_err(|e| KeyError::Jwt(private_path, e))?
+            }
+        };
+
+        info!(
+            "Ключи JWT загружены: алгоритм {:?}, активный kid '{}', ключей проверки {}",
+            algorithm,
+            active_kid,
+            verification.len()
+        );
+
+        Ok(SigningKeys {
+            active_kid,
+            algorithm,
+            encoding,
+            verification,
+            jwks,
+        })
+    }
+
+    /// Подписывает claims активным ключом, указывая его `kid` в заголовке.
+    pub fn encode<T: Serialize>(&self, claims: &T) -> Result<String, jsonwebtoken::errors::Error> {
+        let mut header = Header::new(self.algorithm);
+        header.kid = Some(self.active_kid.clone());
+        encode(&header, claims, &self.encoding)
+    }
+
+    /// Проверяет токен ключом, указанным в `kid`. Токены без `kid`
+    /// проверяются активным ключом.
+    pub fn decode<T: DeserializeOwned + Clone>(
+        &self,
+        token: &str,
+    ) -> Result<T, jsonwebtoken::errors::Error> {
+        let header = decode_header(token)?;
+        let kid = header.kid.as_deref().unwrap_or(&self.active_kid);
+        let key = self
+            .verification
+            .get(kid)
+            .ok_or_else(|| jsonwebtoken::errors::Error::from(ErrorKind::InvalidSignature))?;
+
+        decode::<T>(token, &key.key, &Validation::new(key.algorithm)).map(|data| data.claims)
+    }
+
+    /// Открытые ключи для `/.well-known/jwks.json`. Для HS256 набор пуст.
+    pub fn jwks(&self) -> &JwkSet {
+        &self.jwks
+    }
+}
+
+/// Устанавливает набор ключей при старте приложения.
+pub fn init_signing_keys(keys: SigningKeys) {
+    if SIGNING_KEYS.set(keys).is_err() {
+        panic!("ключи JWT уже инициализированы");
+    }
+}
+
+/// HS256-набор для тестов; если ключи уже заданы, оставляет их.
+#[cfg(test)]
+pub fn init_test_signing_keys() {
+    SIGNING_KEYS.get_or_init(|| {
+        let secret = b"test-secret-of-at-least-thirty-two-bytes";
+        SigningKeys {
+            active_kid: "test".to_string(),
+            algorithm: Algorithm::HS256,
+            encoding: EncodingKey::from_secret(secret),
+            verification: HashMap::from([(
+                "test".to_string(),
+                VerificationKey {
+                    algorithm: Algorithm::HS256,
+                    key: DecodingKey::from_secret(secret),
+                },
+            )]),
+            jwks: JwkSet { keys: Vec::new() },
+        }
+    });
+}
+
+pub fn signing_keys() -> &'static SigningKeys {
+    SIGNING_KEYS
+        .get()
+        .expect("ключи JWT не инициализированы: вызовите init_signing_keys при старте")
+}
+
+// ------------------ Загрузка открытых ключей из PEM ------------------
+fn load_public_keys(dir: &Path) -> Result<Vec<(String, Jwk)>, KeyError> {
+    let entries = fs::read_dir(dir).map_err(|e| KeyError::Io(dir.to_path_buf(), e))?;
+    let mut keys = Vec::new();
+
+    for entry in entries {
+        let path = entry.map_err(|e| KeyError::Io(dir.to_path_buf(), e))?.path();
+        let Some(kid) = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_suffix(".public.pem"))
+        else {
+            continue;
+        };
+
+        let pem = fs::read(&path).map_err(|e| KeyError::Io(path.clone(), e))?;
+        let spki = rustls_pemfile::public_keys(&mut pem.as_slice())
+            .next()
+            .ok_or_else(|| KeyError::NoPublicKey(path.clone()))?
+            .map_err(|e| KeyError::Io(path.clone(), e))?;
+
+        let jwk = public_key_to_jwk(kid, spki.as_ref()).ok_or(KeyError::UnsupportedKey(path.clone()))?;
+        keys.push((kid.to_string(), jwk));
+    }
+
+    Ok(keys)
+}
+
+fn public_key_to_jwk(kid: &str, spki: &[u8]) -> Option<Jwk> {
+    let (key_algorithm, algorithm) = if let Ok(rsa_key) = rsa::RsaPublicKey::from_public_key_der(spki) {
+        (
+            KeyAlgorithm::RS256,
+            AlgorithmParameters::RSA(RSAKeyParameters {
+                key_type: RSAKeyType::RSA,
+                n: URL_SAFE_NO_PAD.encode(rsa_key.n().to_bytes_be()),
+                e: URL_SAFE_NO_PAD.encode(rsa_key.e().to_bytes_be()),
+            }),
+        )
+    } else if spki.len() == ED25519_SPKI_PREFIX.len() + 32 && spki.starts_with(&ED25519_SPKI_PREFIX) {
+        (
+            KeyAlgorithm::EdDSA,
+            AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: URL_SAFE_NO_PAD.encode(&spki[ED25519_SPKI_PREFIX.len()..]),
+            }),
+        )
+    } else {
+        return None;
+    };
+
+    Some(Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm),
+            key_id: Some(kid.to_string()),
+            ..Default::default()
+        },
+        algorithm,
+    })
+}
+
+fn jwk_algorithm(jwk: &Jwk) -> Algorithm {
+    match jwk.algorithm {
+        AlgorithmParameters::OctetKeyPair(_) => Algorithm::EdDSA,
+        _ => Algorithm::RS256,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "old-secret-of-at-least-thirty-two-bytes";
+
+    #[test]
+    fn parses_previous_secrets() {
+        let value = format!(" 2024-01:{SECRET}, ,2024-02:{SECRET}x ");
+        let secrets = parse_previous_secrets(Algorithm::HS256, &value).unwrap();
+        let expected = format!("{SECRET}x");
+        assert_eq!(secrets, [("2024-01", SECRET), ("2024-02", expected.as_str())]);
+    }
+
+    #[test]
+    fn rejects_malformed_previous_secrets() {
+        assert!(matches!(
+            parse_previous_secrets(Algorithm::HS256, SECRET),
+            Err(KeyError::InvalidSecretEntry(_))
+        ));
+        assert!(matches!(
+            parse_previous_secrets(Algorithm::HS256, "old:short"),
+            Err(KeyError::WeakSecret(kid)) if kid == "old"
+        ));
+    }
+
+    #[test]
+    fn rejects_previous_secrets_for_asymmetric_keys() {
+        let value = format!("old:{SECRET}");
+        for algorithm in [Algorithm::RS256, Algorithm::EdDSA] {
+            assert!(matches!(
+                parse_previous_secrets(algorithm, &value),
+                Err(KeyError::PreviousSecretsNotSupported(a)) if a == algorithm
+            ));
+            // Пустая переменная ничего не задаёт
+            assert!(parse_previous_secrets(algorithm, " ").unwrap().is_empty());
+        }
+    }
+}
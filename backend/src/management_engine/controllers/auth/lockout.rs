@@ -0,0 +1,283 @@
+use crate::management_engine::clients::clients::auth::auth::PgAuthClient;
+use crate::management_engine::clients::traits::auth::AuthClient;
+use crate::management_engine::controllers::errors::AppError;
+use actix_web::HttpRequest;
+use std::env;
+use std::net::IpAddr;
+use std::sync::OnceLock;
+use tracing::{error, warn};
+
+// Действия в таблице logs
+pub const LOG_LOGIN_FAILED: &str = "login_failed";
+pub const LOG_LOGIN_SUCCESS: &str = "login_success";
+pub const LOG_ACCOUNT_UNLOCKED: &str = "account_unlocked";
+
+static LOCKOUT_POLICY: OnceLock<LockoutPolicy> = OnceLock::new();
+
+// =========================================================
+// ПОЛИТИКА БЛОКИРОВКИ ВХОДА
+// =========================================================
+//
+// LOGIN_MAX_USER_FAILURES  неудачных попыток на имя до блокировки (5)
+// LOGIN_MAX_IP_FAILURES    неудачных попыток с одного IP до блокировки (20)
+// LOGIN_LOCKOUT_BASE_SECS  длительность первой блокировки (30)
+// LOGIN_LOCKOUT_MAX_SECS   максимальная длительность блокировки (900)
+// LOGIN_FAILURE_WINDOW_SECS окно учёта неудачных попыток (3600)
+// LOGIN_TRUSTED_PROXIES    IP обратных прокси через запятую, которым
+//                          доверяется X-Forwarded-For (пусто)
+//
+// Каждая следующая неудачная попытка сверх порога удваивает блокировку.
+// Без доверенных прокси IP клиента — адрес TCP-соединения: заголовки
+// X-Forwarded-For и Forwarded задаёт сам клиент.
+
+#[derive(Debug, Clone)]
+pub struct LockoutPolicy {
+    pub max_user_failures: i64,
+    pub max_ip_failures: i64,
+    pub base_lockout_secs: f64,
+    pub max_lockout_secs: f64,
+    pub failure_window_secs: f64,
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl LockoutPolicy {
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str, default: T) -> T {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        LockoutPolicy {
+            max_user_failures: var("LOGIN_MAX_USER_FAILURES", 5),
+            max_ip_failures: var("LOGIN_MAX_IP_FAILURES", 20),
+            base_lockout_secs: var("LOGIN_LOCKOUT_BASE_SECS", 30.0),
+            max_lockout_secs: var("LOGIN_LOCKOUT_MAX_SECS", 900.0),
+            failure_window_secs: var("LOGIN_FAILURE_WINDOW_SECS", 3600.0),
+            trusted_proxies: env::var("LOGIN_TRUSTED_PROXIES")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .filter_map(|v| match v.parse() {
+                    Ok(ip) => Some(ip),
+                    Err(_) => {
+                        warn!("LOGIN_TRUSTED_PROXIES: некорректный IP '{}' пропущен", v);
+                        None
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// IP клиента: адрес соединения, а за доверенным прокси — ближайший
+    /// к нему недоверенный адрес из X-Forwarded-For.
+    pub fn client_ip(&self, req: &HttpRequest) -> Option<IpAddr> {
+        let peer = req.peer_addr()?.ip();
+        if !self.trusted_proxies.contains(&peer) {
+            return Some(peer);
+        }
+        let forwarded: Vec<IpAddr> = req
+            .headers()
+            .get_all("x-forwarded-for")
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(|ip| ip.trim().parse())
+            .collect::<Result<_, _>>()
+            .unwrap_or_default();
+        // Левые адреса цепочки задаёт клиент, правые дописаны прокси
+        forwarded
+            .into_iter()
+            .rev()
+            .find(|ip| !self.trusted_proxies.contains(ip))
+            .or(Some(peer))
+    }
+
+    /// Сколько секунд ещё действует блокировка после `failures` неудачных попыток,
+    /// последняя из которых была `since_last` секунд назад.
+    pub fn remaining_lockout(&self, failures: i64, max_failures: i64, since_last: f64) -> Option<u64> {
+        if failures < max_failures {
+            return None;
+        }
+        let exponent = (failures - max_failures).min(30) as i32;
+        let lockout = (self.base_lockout_secs * 2f64.powi(exponent)).min(self.max_lockout_secs);
+        let remaining = lockout - since_last;
+        (remaining > 0.0).then(|| remaining.ceil() as u64)
+    }
+}
+
+pub fn lockout_policy() -> &'static LockoutPolicy {
+    LOCKOUT_POLICY.get_or_init(LockoutPolicy::from_env)
+}
+
+/// Проверяет, не заблокирован ли вход для имени пользователя или IP.
+pub async fn ensure_login_allowed(
+    client: &PgAuthClient,
+    username: &str,
+    ip_address: Option<&str>,
+) -> Result<(), AppError> {
+    let policy = lockout_policy();
+
+    let (failures, since_last) = client
+        .get_failed_logins_by_username(username, policy.failure_window_secs)
+        .await
+        .map_err(|e| {
+            error!("Ошибка подсчёта неудачных попыток входа: {:?}", e);
+            AppError::from(e)
+        })?;
+    if let Some(retry_after) =
+        policy.remaining_lockout(failures, policy.max_user_failures, since_last.unwrap_or(0.0))
+    {
+        warn!(
+            "Вход для {} заблокирован на {} с после {} неудачных попыток",
+            username, retry_after, failures
+        );
+        return Err(AppError::TooManyAttempts { retry_after });
+    }
+
+    if let Some(ip) = ip_address {
+        let (failures, since_last) = client
+            .get_failed_logins_by_ip(ip, policy.failure_window_secs)
+            .await
+            .map_err(|e| {
+                error!("Ошибка подсчёта неудачных попыток входа: {:?}", e);
+                AppError::from(e)
+            })?;
+        if let Some(retry_after) =
+            policy.remaining_lockout(failures, policy.max_ip_failures, since_last.unwrap_or(0.0))
+        {
+            warn!(
+                "Вход с IP {} заблокирован на {} с после {} неудачных попыток",
+                ip, retry_after, failures
+            );
+            return Err(AppError::TooManyAttempts { retry_after });
+        }
+    }
+
+    Ok(())
+}
+
+/// Пишет попытку входа в logs. Ошибка записи не прерывает вход.
+pub async fn record_login_attempt(
+    client: &PgAuthClient,
+    user_id: Option<i32>,
+    action: &str,
+    username: &str,
+    ip_address: Option<&str>,
+) {
+    if let Err(e) = client
+        .log_auth_event(user_id, action, None, username, ip_address)
+        .await
+    {
+        error!("Ошибка записи попытки входа в logs: {:?}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    const PROXY: &str = "10.0.0.1";
+    const EDGE_PROXY: &str = "10.0.0.2";
+
+    fn policy() -> LockoutPolicy {
+        LockoutPolicy {
+            max_user_failures: 5,
+            max_ip_failures: 20,
+            base_lockout_secs: 30.0,
+            max_lockout_secs: 900.0,
+            failure_window_secs: 3600.0,
+            trusted_proxies: vec![PROXY.parse().unwrap(), EDGE_PROXY.parse().unwrap()],
+        }
+    }
+
+    fn request(peer: &str, forwarded_for: Option<&str>) -> HttpRequest {
+        let mut req = TestRequest::default().peer_addr(format!("{}:40000", peer).parse().unwrap());
+        if let Some(chain) = forwarded_for {
+            req = req.insert_header(("x-forwarded-for", chain));
+        }
+        req.to_http_request()
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    // =========================================================
+    // IP КЛИЕНТА
+    // =========================================================
+
+    #[test]
+    fn ignores_forwarded_for_from_untrusted_peer() {
+        let req = request("203.0.113.7", Some("198.51.100.1"));
+        assert_eq!(policy().client_ip(&req), ip("203.0.113.7"));
+    }
+
+    #[test]
+    fn ignores_forwarded_for_without_trusted_proxies() {
+        let policy = LockoutPolicy {
+            trusted_proxies: Vec::new(),
+            ..policy()
+        };
+        let req = request(PROXY, Some("198.51.100.1"));
+        assert_eq!(policy.client_ip(&req), ip(PROXY));
+    }
+
+    #[test]
+    fn takes_client_behind_trusted_proxy() {
+        let req = request(PROXY, Some("198.51.100.1"));
+        assert_eq!(policy().client_ip(&req), ip("198.51.100.1"));
+    }
+
+    #[test]
+    fn skips_trusted_proxies_in_chain() {
+        // Клиент подставил 1.2.3.4, реальный адрес дописал внешний прокси
+        let req = request(PROXY, Some(&format!("1.2.3.4, 198.51.100.1, {}", EDGE_PROXY)));
+        assert_eq!(policy().client_ip(&req), ip("198.51.100.1"));
+    }
+
+    #[test]
+    fn falls_back_to_peer_on_bad_chain() {
+        for chain in ["", "unknown", "198.51.100.1, garbage", &format!("{}, {}", EDGE_PROXY, PROXY)] {
+            let req = request(PROXY, Some(chain));
+            assert_eq!(policy().client_ip(&req), ip(PROXY), "цепочка {:?}", chain);
+        }
+        assert_eq!(policy().client_ip(&request(PROXY, None)), ip(PROXY));
+    }
+
+    // =========================================================
+    // ДЛИТЕЛЬНОСТЬ БЛОКИРОВКИ
+    // =========================================================
+
+    #[test]
+    fn allows_login_below_threshold() {
+        assert_eq!(policy().remaining_lockout(4, 5, 0.0), None);
+    }
+
+    #[test]
+    fn doubles_lockout_per_failure() {
+        let policy = policy();
+        assert_eq!(policy.remaining_lockout(5, 5, 0.0), Some(30));
+        assert_eq!(policy.remaining_lockout(6, 5, 0.0), Some(60));
+        assert_eq!(policy.remaining_lockout(8, 5, 0.0), Some(240));
+    }
+
+    #[test]
+    fn caps_lockout_at_maximum() {
+        let policy = policy();
+        assert_eq!(policy.remaining_lockout(10, 5, 0.0), Some(900));
+        // Показатель степени ограничен, переполнения нет
+        assert_eq!(policy.remaining_lockout(i64::MAX, 5, 0.0), Some(900));
+    }
+
+    #[test]
+    fn counts_down_from_last_failure() {
+        let policy = policy();
+        assert_eq!(policy.remaining_lockout(5, 5, 29.5), Some(1));
+        assert_eq!(policy.remaining_lockout(5, 5, 30.0), None);
+        assert_eq!(policy.remaining_lockout(10, 5, 899.0), Some(1));
+        assert_eq!(policy.remaining_lockout(10, 5, 1000.0), None);
+    }
+}
@@ -1 +1,246 @@
+use crate::management_engine::clients::clients::users::users::PgUserClient;
+use crate::management_engine::clients::traits::users::UserClient;
+use crate::management_engine::controllers::auth::auth::decode_token;
+use crate::management_engine::controllers::errors::{AppError, ErrorBody};
+use actix_web::body::BoxBody;
+use actix_web::dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::http::StatusCode;
+use actix_web::http::header::{AUTHORIZATION, HeaderMap};
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest, HttpResponse, ResponseError, web};
+use jsonwebtoken::errors::ErrorKind;
+use std::future::{Future, Ready, ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use sqlx::PgPool;
+use tracing::{error, info};
 
+// =========================================================
+// ОШИБКИ АВТОРИЗАЦИИ
+// =========================================================
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("Отсутствует заголовок Authorization: Bearer <token>")]
+    MissingToken,
+    #[error("Срок действия токена истёк")]
+    ExpiredToken,
+    #[error("Некорректный токен")]
+    MalformedToken,
+    #[error("Неверная подпись токена")]
+    InvalidSignature,
+    #[error("Недостаточно прав для выполнения операции")]
+    Forbidden,
+    #[error("Учётная запись отключена или удалена")]
+    AccountDisabled,
+}
+
+impl AuthError {
+    fn code(&self) -> &'static str {
+        match self {
+            AuthError::MissingToken => "missing_token",
+            AuthError::ExpiredToken => "token_expired",
+            AuthError::MalformedToken => "token_malformed",
+            AuthError::InvalidSignature => "token_invalid_signature",
+            AuthError::Forbidden => "forbidden",
+            AuthError::AccountDisabled => "account_disabled",
+        }
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for AuthError {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        match err.kind() {
+            ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
+            ErrorKind::InvalidSignature => AuthError::InvalidSignature,
+            _ => AuthError::MalformedToken,
+        }
+    }
+}
+
+impl ResponseError for AuthError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AuthError::Forbidden => StatusCode::FORBIDDEN,
+            _ => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        let mut resp = HttpResponse::build(self.status_code());
+        if self.status_code() == StatusCode::UNAUTHORIZED {
+            resp.insert_header((
+                actix_web::http::header::WWW_AUTHENTICATE,
+                format!("Bearer error=\"{}\"", self.code()),
+            ));
+        }
+        resp.json(ErrorBody {
+            code: self.code(),
+            message: self.to_string(),
+            errors: Vec::new(),
+        })
+    }
+}
+
+// =========================================================
+// АУТЕНТИФИЦИРОВАННЫЙ ПОЛЬЗОВАТЕЛЬ (extractor)
+// =========================================================
+
+/// Пользователь, извлечённый из проверенного Bearer-токена.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub username: String,
+    pub role: String,
+    pub permissions: Vec<String>,
+}
+
+impl AuthenticatedUser {
+    fn from_headers(headers: &HeaderMap) -> Result<Self, AuthError> {
+        let token = headers
+            .get(AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or(AuthError::MissingToken)?;
+
+        let claims = decode_token(token)?;
+        Ok(AuthenticatedUser {
+            username: claims.sub,
+            role: claims.role,
+            permissions: claims.permissions,
+        })
+    }
+
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.iter().any(|p| p == permission)
+    }
+
+    /// Токен остаётся валидным до истечения срока, поэтому отключение
+    /// и удаление пользователя проверяются по БД на каждом запросе.
+    async fn ensure_active(self, pool: Option<web::Data<PgPool>>) -> Result<Self, Error> {
+        let Some(pool) = pool else {
+            error!("PgPool не зарегистрирован в app_data");
+            return Err(AppError::Internal("PgPool не зарегистрирован".to_string()).into());
+        };
+        let client = PgUserClient {
+            pool: pool.get_ref().clone(),
+        };
+
+        match client.is_user_active(&self.username).await {
+            Ok(Some(true)) => Ok(self),
+            Ok(_) => {
+                info!("Токен отключённого или удалённого пользователя {} отклонён", self.username);
+                Err(AuthError::AccountDisabled.into())
+            }
+            Err(e) => {
+                error!("Ошибка проверки статуса пользователя {}: {:?}", self.username, e);
+                Err(AppError::from(e).into())
+            }
+        }
+    }
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        // Если маршрут обёрнут в RequirePermissions, пользователь уже проверен
+        if let Some(user) = req.extensions().get::<AuthenticatedUser>() {
+            let user = user.clone();
+            return Box::pin(async move { Ok(user) });
+        }
+        let user = AuthenticatedUser::from_headers(req.headers());
+        let pool = req.app_data::<web::Data<PgPool>>().cloned();
+        Box::pin(async move { user?.ensure_active(pool).await })
+    }
+}
+
+// =========================================================
+// MIDDLEWARE: ПРОВЕРКА ПРАВ НА МАРШРУТЕ
+// =========================================================
+
+/// Требует валидный Bearer-токен, в котором есть все перечисленные права.
+///
+/// Используется в атрибуте маршрута:
+/// `#[get("/path", wrap = "RequirePermissions::all(&[TELEMETRY_READ])")]`
+pub struct RequirePermissions {
+    permissions: Rc<Vec<&'static str>>,
+}
+
+impl RequirePermissions {
+    pub fn all(permissions: &[&'static str]) -> Self {
+        RequirePermissions {
+            permissions: Rc::new(permissions.to_vec()),
+        }
+    }
+
+    /// Только валидный токен активного пользователя, без проверки прав.
+    pub fn authenticated() -> Self {
+        RequirePermissions::all(&[])
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequirePermissions
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequirePermissionsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequirePermissionsMiddleware {
+            service: Rc::new(service),
+            permissions: self.permissions.clone(),
+        }))
+    }
+}
+
+pub struct RequirePermissionsMiddleware<S> {
+    service: Rc<S>,
+    permissions: Rc<Vec<&'static str>>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequirePermissionsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let user = match AuthenticatedUser::from_headers(req.headers()) {
+            Ok(user) => user,
+            Err(err) => {
+                info!("Отказ в доступе к {}: {}", req.path(), err);
+                return Box::pin(async move { Err(err.into()) });
+            }
+        };
+
+        if !self.permissions.iter().all(|p| user.has_permission(p)) {
+            info!(
+                "Пользователь {} с ролью '{}' не имеет доступа к {}",
+                user.username,
+                user.role,
+                req.path()
+            );
+            return Box::pin(async move { Err(AuthError::Forbidden.into()) });
+        }
+
+        let pool = req.app_data::<web::Data<PgPool>>().cloned();
+        let service = self.service.clone();
+        Box::pin(async move {
+            let user = user.ensure_active(pool).await?;
+            req.extensions_mut().insert(user);
+            service.call(req).await
+        })
+    }
+}
@@ -1,2 +1,7 @@
 pub mod auth;
+pub mod keys;
+pub mod lockout;
 pub mod middleware;
+pub mod password_reset;
+pub mod permissions;
+pub mod validation;
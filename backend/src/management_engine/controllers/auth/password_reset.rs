@@ -0,0 +1,188 @@
+use crate::management_engine::clients::clients::auth::auth::PgAuthClient;
+use crate::management_engine::clients::clients::notifier::notifier::notifier;
+use crate::management_engine::clients::clients::password_reset::password_reset::PgPasswordResetClient;
+use crate::management_engine::clients::clients::users::users::PgUserClient;
+use crate::management_engine::clients::traits::auth::AuthClient;
+use crate::management_engine::clients::traits::notifier::Notification;
+use crate::management_engine::clients::traits::password_reset::PasswordResetClient;
+use crate::management_engine::clients::traits::users::UserClient;
+use crate::management_engine::controllers::auth::auth::hash_token;
+use crate::management_engine::controllers::auth::validation::{normalize_email, password_policy};
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::models::auth::auth::{ForgotPasswordRequest, ResetPasswordRequest};
+use actix_web::web;
+use bcrypt::hash;
+use chrono::Utc;
+use std::env;
+use std::sync::OnceLock;
+use tracing::{error, info};
+use uuid::Uuid;
+
+// Действия в таблице logs
+pub const LOG_PASSWORD_RESET_REQUESTED: &str = "password_reset_requested";
+pub const LOG_PASSWORD_RESET: &str = "password_reset";
+
+static RESET_CONFIG: OnceLock<PasswordResetConfig> = OnceLock::new();
+
+// =========================================================
+// НАСТРОЙКИ СБРОСА ПАРОЛЯ
+// =========================================================
+//
+// PASSWORD_RESET_TTL_MINUTES  время жизни токена (30)
+// PASSWORD_RESET_URL          страница сброса во фронтенде; токен
+//                             добавляется параметром ?token=
+//                             ("http://localhost:5173/reset-password")
+
+#[derive(Debug, Clone)]
+pub struct PasswordResetConfig {
+    pub ttl_minutes: i64,
+    pub reset_url: String,
+}
+
+impl PasswordResetConfig {
+    pub fn from_env() -> Self {
+        PasswordResetConfig {
+            ttl_minutes: env::var("PASSWORD_RESET_TTL_MINUTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            reset_url: env::var("PASSWORD_RESET_URL")
+                .unwrap_or_else(|_| "http://localhost:5173/reset-password".to_string()),
+        }
+    }
+}
+
+pub fn password_reset_config() -> &'static PasswordResetConfig {
+    RESET_CONFIG.get_or_init(PasswordResetConfig::from_env)
+}
+
+/// Выдаёт токены сброса всем активным учётным записям с указанным email.
+/// Результат не зависит от того, найден ли адрес, чтобы нельзя было
+/// перебором узнать зарегистрированные email.
+pub async fn forgot_password_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    req: &ForgotPasswordRequest,
+) -> Result<(), AppError> {
+    let auth_client = PgAuthClient {
+        pool: pool.get_ref().clone(),
+    };
+    let client = PgPasswordResetClient {
+        pool: pool.get_ref().clone(),
+    };
+    let config = password_reset_config();
+
+    let email = normalize_email(&req.email);
+    let users = match client.get_active_users_by_email(&email).await {
+        Ok(users) => users,
+        Err(e) => {
+            error!("Ошибка поиска пользователей по email: {:?}", e);
+            return Err(e.into());
+        }
+    };
+    if users.is_empty() {
+        info!("Запрос сброса пароля для неизвестного email");
+        return Ok(());
+    }
+
+    for (user_id, username, email) in users {
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let expires_at = (Utc::now() + chrono::Duration::minutes(config.ttl_minutes)).naive_utc();
+
+        if let Err(e) = client
+            .create_reset_token(user_id, &hash_token(&token), expires_at)
+            .await
+        {
+            error!("Ошибка сохранения токена сброса для {}: {:?}", username, e);
+            return Err(e.into());
+        }
+
+        if let Err(e) = auth_client
+            .log_auth_event(Some(user_id), LOG_PASSWORD_RESET_REQUESTED, None, &username, None)
+            .await
+        {
+            error!("Ошибка записи запроса сброса в logs: {:?}", e);
+        }
+
+        let notification = Notification {
+            to: email,
+            subject: "Сброс пароля".to_string(),
+            body: format!(
+                "Здравствуйте, {username}!\n\n\
+                 Для учётной записи {username} запрошен сброс пароля.\n\
+                 Перейдите по ссылке, чтобы задать новый пароль:\n\
+                 {url}?token={token}\n\n\
+                 Ссылка действует {ttl} мин и может быть использована один раз.\n\
+                 Если вы не запрашивали сброс, просто проигнорируйте это письмо.",
+                url = config.reset_url,
+                ttl = config.ttl_minutes,
+            ),
+        };
+
+        // Отправка в фоне: время ответа не должно выдавать, найден ли адрес
+        tokio::spawn(async move {
+            match notifier().send(&notification).await {
+                Ok(()) => info!("Письмо для сброса пароля {} отправлено", username),
+                Err(e) => error!("Ошибка отправки письма для сброса пароля {}: {}", username, e),
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Устанавливает новый пароль по токену и завершает все сессии пользователя.
+pub async fn reset_password_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    req: &ResetPasswordRequest,
+) -> Result<(), AppError> {
+    let auth_client = PgAuthClient {
+        pool: pool.get_ref().clone(),
+    };
+    let client = PgPasswordResetClient {
+        pool: pool.get_ref().clone(),
+    };
+    let user_client = PgUserClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let errors = password_policy().check("new_password", &req.new_password);
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let new_hash = hash(&req.new_password, 12).map_err(|e| {
+        error!("Ошибка хэширования пароля: {:?}", e);
+        AppError::Internal(e.to_string())
+    })?;
+
+    let (user_id, username) = match client.reset_password(&hash_token(&req.token), &new_hash).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            info!("Недействительный или использованный токен сброса пароля");
+            return Err(AppError::Validation(vec![FieldError::new(
+                "token",
+                "invalid_token",
+                "Ссылка для сброса пароля недействительна или устарела",
+            )]));
+        }
+        Err(e) => {
+            error!("Ошибка сброса пароля: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    if let Err(e) = user_client.revoke_user_refresh_tokens(user_id).await {
+        error!("Ошибка отзыва refresh-токенов {}: {:?}", username, e);
+        return Err(e.into());
+    }
+
+    if let Err(e) = auth_client
+        .log_auth_event(Some(user_id), LOG_PASSWORD_RESET, None, &username, None)
+        .await
+    {
+        error!("Ошибка записи сброса пароля в logs: {:?}", e);
+    }
+
+    info!("Пароль пользователя {} сброшен по токену", username);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+// =========================================================
+// ПРАВА ДОСТУПА
+// =========================================================
+//
+// Права назначаются ролям (таблица role_permissions) и попадают
+// в JWT при выдаче токена.
+
+pub const TELEMETRY_READ: &str = "telemetry:read";
+pub const TELEMETRY_WRITE: &str = "telemetry:write";
+pub const THRESHOLDS_READ: &str = "thresholds:read";
+pub const THRESHOLDS_WRITE: &str = "thresholds:write";
+pub const ALERTS_READ: &str = "alerts:read";
+pub const ALERTS_MANAGE: &str = "alerts:manage";
+pub const USERS_MANAGE: &str = "users:manage";
+pub const ROLES_MANAGE: &str = "roles:manage";
+
+/// Все известные права. Роль можно создать только с правами из этого списка.
+pub const ALL_PERMISSIONS: &[&str] = &[
+    TELEMETRY_READ,
+    TELEMETRY_WRITE,
+    THRESHOLDS_READ,
+    THRESHOLDS_WRITE,
+    ALERTS_READ,
+    ALERTS_MANAGE,
+    USERS_MANAGE,
+    ROLES_MANAGE,
+];
+
+pub fn is_known_permission(permission: &str) -> bool {
+    ALL_PERMISSIONS.contains(&permission)
+}
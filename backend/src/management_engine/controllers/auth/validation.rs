@@ -0,0 +1,493 @@
+use crate::management_engine::controllers::errors::FieldError;
+use crate::management_engine::models::auth::auth::RegisterRequest;
+use crate::management_engine::models::users::users::UpdateProfileRequest;
+use regex::Regex;
+use std::env;
+use std::sync::{LazyLock, OnceLock};
+
+static USERNAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z0-9_.\-]{3,50}$").expect("valid regex"));
+static EMAIL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[a-z0-9.!#$%&'*+/=?^_`{|}~\-]+@[a-z0-9](?:[a-z0-9\-]*[a-z0-9])?(?:\.[a-z0-9](?:[a-z0-9\-]*[a-z0-9])?)+$")
+        .expect("valid regex")
+});
+static PHONE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\+[1-9][0-9]{9,14}$").expect("valid regex"));
+
+static PASSWORD_POLICY: OnceLock<PasswordPolicy> = OnceLock::new();
+
+const MAX_FULL_NAME_LEN: usize = 150;
+const MAX_EMAIL_LEN: usize = 150;
+const MAX_ORGANIZATION_LEN: usize = 150;
+/// bcrypt учитывает только первые 72 байта пароля
+const BCRYPT_MAX_PASSWORD_BYTES: usize = 72;
+
+// =========================================================
+// ПАРОЛЬНАЯ ПОЛИТИКА
+// =========================================================
+//
+// PASSWORD_MIN_LENGTH      минимальная длина (8)
+// PASSWORD_REQUIRE_UPPER   нужна заглавная буква (true)
+// PASSWORD_REQUIRE_LOWER   нужна строчная буква (true)
+// PASSWORD_REQUIRE_DIGIT   нужна цифра (true)
+// PASSWORD_REQUIRE_SPECIAL нужен спецсимвол (false)
+
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_upper: bool,
+    pub require_lower: bool,
+    pub require_digit: bool,
+    pub require_special: bool,
+}
+
+impl PasswordPolicy {
+    pub fn from_env() -> Self {
+        fn flag(name: &str, default: bool) -> bool {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        PasswordPolicy {
+            min_length: env::var("PASSWORD_MIN_LENGTH")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(8),
+            require_upper: flag("PASSWORD_REQUIRE_UPPER", true),
+            require_lower: flag("PASSWORD_REQUIRE_LOWER", true),
+            require_digit: flag("PASSWORD_REQUIRE_DIGIT", true),
+            require_special: flag("PASSWORD_REQUIRE_SPECIAL", false),
+        }
+    }
+
+    /// Возвращает все нарушения политики для поля `field`.
+    pub fn check(&self, field: &'static str, password: &str) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
+        if password.chars().count() < self.min_length {
+            errors.push(FieldError::new(
+                field,
+                "too_short",
+                format!("Пароль должен содержать не менее {} символов", self.min_length),
+            ));
+        }
+        if password.len() > BCRYPT_MAX_PASSWORD_BYTES {
+            errors.push(FieldError::new(
+                field,
+                "too_long",
+                format!("Пароль не должен превышать {} байт", BCRYPT_MAX_PASSWORD_BYTES),
+            ));
+        }
+        if self.require_upper && !password.chars().any(char::is_uppercase) {
+            errors.push(FieldError::new(
+                field,
+                "missing_uppercase",
+                "Пароль должен содержать заглавную букву",
+            ));
+        }
+        if self.require_lower && !password.chars().any(char::is_lowercase) {
+            errors.push(FieldError::new(
+                field,
+                "missing_lowercase",
+                "Пароль должен содержать строчную букву",
+            ));
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            errors.push(FieldError::new(
+                field,
+                "missing_digit",
+                "Пароль должен содержать цифру",
+            ));
+        }
+        if self.require_special && password.chars().all(char::is_alphanumeric) {
+            errors.push(FieldError::new(
+                field,
+                "missing_special",
+                "Пароль должен содержать специальный символ",
+            ));
+        }
+
+        errors
+    }
+}
+
+pub fn password_policy() -> &'static PasswordPolicy {
+    PASSWORD_POLICY.get_or_init(PasswordPolicy::from_env)
+}
+
+// =========================================================
+// ВАЛИДАЦИЯ РЕГИСТРАЦИИ
+// =========================================================
+
+/// Проверенные и нормализованные данные регистрации.
+#[derive(Debug)]
+pub struct ValidRegistration {
+    pub username: String,
+    pub full_name: String,
+    pub email: String,
+    pub phone_number: Option<String>,
+    pub organization: Option<String>,
+}
+
+/// Проверяет все поля сразу и возвращает полный список ошибок.
+pub fn validate_registration(req: &RegisterRequest) -> Result<ValidRegistration, Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    if !USERNAME_RE.is_match(&req.username) {
+        errors.push(FieldError::new(
+            "username",
+            "invalid_format",
+            "Имя пользователя: 3–50 символов, латиница, цифры, '_', '.', '-'",
+        ));
+    }
+
+    errors.extend(password_policy().check("password", &req.password));
+
+    let full_name = check_full_name(&req.full_name, &mut errors);
+    let email = check_email(&req.email, &mut errors);
+    let phone_number = req
+        .phone_number
+        .as_deref()
+        .and_then(|p| check_phone(p, &mut errors));
+    let organization = req
+        .organization
+        .as_deref()
+        .and_then(|o| check_organization(o, &mut errors));
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(ValidRegistration {
+        username: req.username.clone(),
+        full_name,
+        email,
+        phone_number,
+        organization,
+    })
+}
+
+// =========================================================
+// ВАЛИДАЦИЯ ИЗМЕНЕНИЯ ПРОФИЛЯ
+// =========================================================
+
+/// Проверенные изменения профиля. `None` — поле не меняется;
+/// для необязательных полей `Some(None)` очищает значение.
+#[derive(Debug)]
+pub struct ValidProfileUpdate {
+    pub full_name: Option<String>,
+    pub email: Option<String>,
+    pub phone_number: Option<Option<String>>,
+    pub organization: Option<Option<String>>,
+}
+
+/// Проверяет только переданные поля. Пустая строка в phone_number
+/// или organization очищает поле.
+pub fn validate_profile_update(req: &UpdateProfileRequest) -> Result<ValidProfileUpdate, Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    let update = ValidProfileUpdate {
+        full_name: req.full_name.as_deref().map(|n| check_full_name(n, &mut errors)),
+        email: req.email.as_deref().map(|e| check_email(e, &mut errors)),
+        phone_number: req.phone_number.as_deref().map(|p| check_phone(p, &mut errors)),
+        organization: req
+            .organization
+            .as_deref()
+            .map(|o| check_organization(o, &mut errors)),
+    };
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(update)
+}
+
+// ------------------ Проверки отдельных полей ------------------
+fn check_full_name(raw: &str, errors: &mut Vec<FieldError>) -> String {
+    let full_name = raw.trim();
+    if full_name.is_empty() {
+        errors.push(FieldError::new("full_name", "required", "Поле full_name обязательно"));
+    } else if full_name.chars().count() > MAX_FULL_NAME_LEN {
+        errors.push(FieldError::new(
+            "full_name",
+            "too_long",
+            format!("Поле full_name не должно превышать {} символов", MAX_FULL_NAME_LEN),
+        ));
+    }
+    full_name.to_string()
+}
+
+fn check_email(raw: &str, errors: &mut Vec<FieldError>) -> String {
+    let email = normalize_email(raw);
+    if email.is_empty() {
+        errors.push(FieldError::new("email", "required", "Поле email обязательно"));
+    } else if email.len() > MAX_EMAIL_LEN || !EMAIL_RE.is_match(&email) {
+        errors.push(FieldError::new("email", "invalid_format", "Некорректный email"));
+    }
+    email
+}
+
+fn check_phone(raw: &str, errors: &mut Vec<FieldError>) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    let phone = normalize_phone(raw);
+    if phone.is_none() {
+        errors.push(FieldError::new(
+            "phone_number",
+            "invalid_format",
+            "Некорректный номер телефона, ожидается формат +79991234567",
+        ));
+    }
+    phone
+}
+
+fn check_organization(raw: &str, errors: &mut Vec<FieldError>) -> Option<String> {
+    let organization = raw.trim();
+    if organization.is_empty() {
+        return None;
+    }
+    if organization.chars().count() > MAX_ORGANIZATION_LEN {
+        errors.push(FieldError::new(
+            "organization",
+            "too_long",
+            format!("Поле organization не должно превышать {} символов", MAX_ORGANIZATION_LEN),
+        ));
+    }
+    Some(organization.to_string())
+}
+
+/// Приводит email к нижнему регистру без пробелов по краям.
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Приводит телефон к формату E.164 (`+79991234567`).
+/// Российские номера вида `8XXXXXXXXXX` переводятся в `+7XXXXXXXXXX`.
+pub fn normalize_phone(phone: &str) -> Option<String> {
+    let has_plus = phone.trim_start().starts_with('+');
+    let digits: String = phone.chars().filter(|c| c.is_ascii_digit()).collect();
+    if phone
+        .chars()
+        .any(|c| !(c.is_ascii_digit() || " +-()".contains(c)))
+    {
+        return None;
+    }
+
+    let normalized = if has_plus {
+        format!("+{digits}")
+    } else if digits.len() == 11 && digits.starts_with('8') {
+        format!("+7{}", &digits[1..])
+    } else if digits.len() == 11 && digits.starts_with('7') {
+        format!("+{digits}")
+    } else {
+        return None;
+    };
+
+    PHONE_RE.is_match(&normalized).then_some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 8,
+            require_upper: true,
+            require_lower: true,
+            require_digit: true,
+            require_special: false,
+        }
+    }
+
+    fn codes(errors: &[FieldError]) -> Vec<(&'static str, &'static str)> {
+        errors.iter().map(|e| (e.field, e.code)).collect()
+    }
+
+    fn registration() -> RegisterRequest {
+        RegisterRequest {
+            username: "ivanov".to_string(),
+            password: "Passw0rd".to_string(),
+            full_name: "Иванов Иван Иванович".to_string(),
+            email: "ivanov@example.com".to_string(),
+            phone_number: None,
+            organization: None,
+        }
+    }
+
+    // =========================================================
+    // ФОРМАТЫ ПОЛЕЙ
+    // =========================================================
+
+    #[test]
+    fn matches_usernames() {
+        for username in ["ivanov", "abc", "ivan.ivanov-2_b", &"a".repeat(50)] {
+            assert!(USERNAME_RE.is_match(username), "{:?} отклонено", username);
+        }
+        for username in ["ab", &"a".repeat(51), "иванов", "ivan ivanov", "ivan@corp", ""] {
+            assert!(!USERNAME_RE.is_match(username), "{:?} принято", username);
+        }
+    }
+
+    #[test]
+    fn matches_emails() {
+        for email in ["a@b.co", "ivan.ivanov+noc@mail.example.com", "x_y@sub-domain.ru"] {
+            assert!(EMAIL_RE.is_match(email), "{:?} отклонено", email);
+        }
+        for email in ["ivanov", "a@b", "a@-b.ru", "a@b-.ru", "a b@c.ru", "Ivanov@Example.com", "@b.ru"] {
+            assert!(!EMAIL_RE.is_match(email), "{:?} принято", email);
+        }
+    }
+
+    #[test]
+    fn matches_phones() {
+        for phone in ["+79991234567", "+4915112345678", "+1234567890"] {
+            assert!(PHONE_RE.is_match(phone), "{:?} отклонено", phone);
+        }
+        for phone in ["79991234567", "+09991234567", "+123456789", "+1234567890123456", "+7999abc4567"] {
+            assert!(!PHONE_RE.is_match(phone), "{:?} принято", phone);
+        }
+    }
+
+    // =========================================================
+    // ПАРОЛЬНАЯ ПОЛИТИКА
+    // =========================================================
+
+    #[test]
+    fn accepts_password_matching_policy() {
+        assert!(policy().check("password", "Passw0rd").is_empty());
+        assert!(policy().check("password", "Пароль2024").is_empty());
+    }
+
+    #[test]
+    fn reports_every_policy_violation() {
+        assert_eq!(
+            codes(&policy().check("password", "abc")),
+            [
+                ("password", "too_short"),
+                ("password", "missing_uppercase"),
+                ("password", "missing_digit"),
+            ]
+        );
+        assert_eq!(
+            codes(&policy().check("new_password", "PASSWORD1")),
+            [("new_password", "missing_lowercase")]
+        );
+    }
+
+    #[test]
+    fn counts_min_length_in_characters() {
+        let policy = PasswordPolicy { min_length: 8, ..policy() };
+        // 7 символов, 14 байт
+        assert_eq!(codes(&policy.check("password", "Пароль1")), [("password", "too_short")]);
+    }
+
+    #[test]
+    fn caps_password_at_bcrypt_limit() {
+        let limit = format!("Aa1{}", "x".repeat(BCRYPT_MAX_PASSWORD_BYTES - 3));
+        assert!(policy().check("password", &limit).is_empty());
+
+        let over = format!("{}x", limit);
+        assert_eq!(codes(&policy().check("password", &over)), [("password", "too_long")]);
+
+        // 37 кириллических символов — 74 байта
+        let cyrillic = format!("Aa1{}", "ж".repeat(36));
+        assert_eq!(codes(&policy().check("password", &cyrillic)), [("password", "too_long")]);
+    }
+
+    #[test]
+    fn requires_special_character_when_enabled() {
+        let policy = PasswordPolicy { require_special: true, ..policy() };
+        assert_eq!(codes(&policy.check("password", "Passw0rd")), [("password", "missing_special")]);
+        assert!(policy.check("password", "Passw0rd!").is_empty());
+    }
+
+    // =========================================================
+    // НОРМАЛИЗАЦИЯ
+    // =========================================================
+
+    #[test]
+    fn normalizes_russian_phones() {
+        assert_eq!(normalize_phone("89991234567").as_deref(), Some("+79991234567"));
+        assert_eq!(normalize_phone("8 (999) 123-45-67").as_deref(), Some("+79991234567"));
+        assert_eq!(normalize_phone("79991234567").as_deref(), Some("+79991234567"));
+        assert_eq!(normalize_phone("+7 999 123 45 67").as_deref(), Some("+79991234567"));
+    }
+
+    #[test]
+    fn rejects_malformed_phones() {
+        for phone in ["9991234567", "899912345678", "8999123456", "+7 999 123-45-6x", "8.999.123.45.67", "+0123456789"] {
+            assert_eq!(normalize_phone(phone), None, "{:?} принято", phone);
+        }
+    }
+
+    #[test]
+    fn normalizes_email() {
+        assert_eq!(normalize_email("  Ivanov@Example.COM "), "ivanov@example.com");
+    }
+
+    // =========================================================
+    // РЕГИСТРАЦИЯ И ПРОФИЛЬ
+    // =========================================================
+
+    #[test]
+    fn normalizes_valid_registration() {
+        let valid = validate_registration(&RegisterRequest {
+            full_name: "  Иванов Иван  ".to_string(),
+            email: " Ivanov@Example.com".to_string(),
+            phone_number: Some("8 999 123-45-67".to_string()),
+            organization: Some("   ".to_string()),
+            ..registration()
+        })
+        .unwrap();
+
+        assert_eq!(valid.full_name, "Иванов Иван");
+        assert_eq!(valid.email, "ivanov@example.com");
+        assert_eq!(valid.phone_number.as_deref(), Some("+79991234567"));
+        assert_eq!(valid.organization, None);
+    }
+
+    #[test]
+    fn reports_all_registration_errors() {
+        let errors = validate_registration(&RegisterRequest {
+            username: "ив".to_string(),
+            full_name: " ".to_string(),
+            email: "ivanov".to_string(),
+            phone_number: Some("123".to_string()),
+            organization: Some("о".repeat(MAX_ORGANIZATION_LEN + 1)),
+            ..registration()
+        })
+        .unwrap_err();
+
+        assert_eq!(
+            codes(&errors),
+            [
+                ("username", "invalid_format"),
+                ("full_name", "required"),
+                ("email", "invalid_format"),
+                ("phone_number", "invalid_format"),
+                ("organization", "too_long"),
+            ]
+        );
+    }
+
+    #[test]
+    fn clears_optional_profile_fields() {
+        let update = validate_profile_update(&UpdateProfileRequest {
+            full_name: None,
+            email: None,
+            phone_number: Some(" ".to_string()),
+            organization: Some(String::new()),
+        })
+        .unwrap();
+
+        assert_eq!(update.full_name, None);
+        assert_eq!(update.email, None);
+        assert_eq!(update.phone_number, Some(None));
+        assert_eq!(update.organization, Some(None));
+    }
+}
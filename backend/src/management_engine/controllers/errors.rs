@@ -0,0 +1,174 @@
+use actix_web::body::BoxBody;
+use actix_web::http::StatusCode;
+use actix_web::http::header::RETRY_AFTER;
+use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+// =========================================================
+// ОШИБКИ ПРЕДМЕТНОЙ ОБЛАСТИ
+// =========================================================
+
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("Пользователь уже существует")]
+    UserExists,
+    #[error("Неверные учетные данные")]
+    InvalidCredentials,
+    #[error("Учётная запись отключена")]
+    AccountDisabled,
+    #[error("Ошибка валидации входных данных")]
+    Validation(Vec<FieldError>),
+    #[error("Пользователь '{0}' не найден")]
+    UserNotFound(String),
+    #[error("Слишком много неудачных попыток входа, повторите через {retry_after} с")]
+    TooManyAttempts { retry_after: u64 },
+    #[error("Роль '{0}' не найдена")]
+    RoleNotFound(String),
+    #[error("Роль '{0}' уже существует")]
+    RoleExists(String),
+    #[error("Неизвестная метрика '{0}'")]
+    UnknownMetric(String),
+    #[error("Устройство '{0}' не найдено")]
+    DeviceNotFound(String),
+    #[error("Порог #{0} не найден")]
+    ThresholdNotFound(i32),
+    #[error("Порог для этой метрики, области и направления уже существует")]
+    ThresholdExists,
+    #[error("Алерт #{0} не найден")]
+    AlertNotFound(i32),
+    #[error("Алерт #{0} уже закрыт")]
+    AlertResolved(i32),
+    #[error("Подписка #{0} не найдена")]
+    SubscriptionNotFound(i32),
+    #[error("Такая подписка уже существует")]
+    SubscriptionExists,
+    #[error("Тело запроса больше {limit} байт, разбейте его на части")]
+    PayloadTooLarge { limit: usize },
+    #[error("Недействительный refresh-токен")]
+    InvalidRefreshToken,
+    #[error("Срок действия refresh-токена истёк")]
+    RefreshTokenExpired,
+    #[error("Refresh-токен уже использован, сессия отозвана")]
+    RefreshTokenReused,
+    #[error("Ошибка базы данных")]
+    Database(#[from] sqlx::Error),
+    #[error("Внутренняя ошибка сервера")]
+    Internal(String),
+}
+
+/// Ошибка отдельного поля запроса.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: &'static str,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &'static str, code: &'static str, message: impl Into<String>) -> Self {
+        FieldError {
+            field,
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<telemetry_protocol::ValidationError> for FieldError {
+    fn from(err: telemetry_protocol::ValidationError) -> Self {
+        FieldError {
+            field: err.field,
+            code: err.code,
+            message: err.message,
+        }
+    }
+}
+
+/// Единый формат тела ответа об ошибке.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    /// Машиночитаемый код ошибки
+    pub code: &'static str,
+    /// Сообщение для пользователя
+    pub message: String,
+    /// Ошибки по полям (только для ошибок валидации)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::UserExists => "user_exists",
+            AppError::InvalidCredentials => "invalid_credentials",
+            AppError::AccountDisabled => "account_disabled",
+            AppError::Validation(_) => "validation_error",
+            AppError::UserNotFound(_) => "user_not_found",
+            AppError::TooManyAttempts { .. } => "too_many_attempts",
+            AppError::RoleNotFound(_) => "role_not_found",
+            AppError::RoleExists(_) => "role_exists",
+            AppError::UnknownMetric(_) => "unknown_metric",
+            AppError::DeviceNotFound(_) => "device_not_found",
+            AppError::ThresholdNotFound(_) => "threshold_not_found",
+            AppError::ThresholdExists => "threshold_exists",
+            AppError::AlertNotFound(_) => "alert_not_found",
+            AppError::AlertResolved(_) => "alert_resolved",
+            AppError::SubscriptionNotFound(_) => "subscription_not_found",
+            AppError::SubscriptionExists => "subscription_exists",
+            AppError::PayloadTooLarge { .. } => "payload_too_large",
+            AppError::InvalidRefreshToken => "invalid_refresh_token",
+            AppError::RefreshTokenExpired => "refresh_token_expired",
+            AppError::RefreshTokenReused => "refresh_token_reused",
+            AppError::Database(_) => "database_error",
+            AppError::Internal(_) => "internal_error",
+        }
+    }
+}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::UserExists
+            | AppError::RoleExists(_)
+            | AppError::ThresholdExists
+            | AppError::AlertResolved(_)
+            | AppError::SubscriptionExists => StatusCode::CONFLICT,
+            AppError::InvalidCredentials
+            | AppError::InvalidRefreshToken
+            | AppError::RefreshTokenExpired
+            | AppError::RefreshTokenReused => StatusCode::UNAUTHORIZED,
+            AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::AccountDisabled => StatusCode::FORBIDDEN,
+            AppError::UnknownMetric(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::UserNotFound(_)
+            | AppError::RoleNotFound(_)
+            | AppError::DeviceNotFound(_)
+            | AppError::ThresholdNotFound(_)
+            | AppError::AlertNotFound(_)
+            | AppError::SubscriptionNotFound(_) => StatusCode::NOT_FOUND,
+            AppError::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::TooManyAttempts { .. } => StatusCode::TOO_MANY_REQUESTS,
+            AppError::Database(
+                sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_),
+            ) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Database(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        let errors = match self {
+            AppError::Validation(errors) => errors.clone(),
+            _ => Vec::new(),
+        };
+        let mut resp = HttpResponse::build(self.status_code());
+        if let AppError::TooManyAttempts { retry_after } = self {
+            resp.insert_header((RETRY_AFTER, retry_after.to_string()));
+        }
+        resp.json(ErrorBody {
+            code: self.code(),
+            message: self.to_string(),
+            errors,
+        })
+    }
+}
@@ -0,0 +1,111 @@
+use crate::management_engine::clients::clients::mailbox::mailbox::PgMailboxClient;
+use crate::management_engine::clients::traits::mailbox::MailboxClient;
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::models::mailbox::mailbox::{
+    DeleteMailboxRequest, MailboxChange, MailboxPage, MailboxQuery, MarkMailboxRequest,
+};
+use actix_web::web;
+use tracing::{error, info};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+const MAX_BULK_IDS: usize = 500;
+
+pub async fn list_mailbox_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    query: &MailboxQuery,
+) -> Result<MailboxPage, AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let unread_only = query.unread_only.unwrap_or(false);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let items = client
+        .list_messages(username, unread_only, limit, offset)
+        .await
+        .map_err(|e| {
+            error!("Ошибка получения почтового ящика {}: {:?}", username, e);
+            AppError::from(e)
+        })?;
+    let total = client.count_messages(username, unread_only).await.map_err(|e| {
+        error!("Ошибка подсчёта сообщений {}: {:?}", username, e);
+        AppError::from(e)
+    })?;
+    let unread = unread_count(&client, username).await?;
+
+    Ok(MailboxPage { items, total, unread })
+}
+
+pub async fn mark_mailbox_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    req: &MarkMailboxRequest,
+) -> Result<MailboxChange, AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    validate_ids(&req.ids)?;
+    let affected = client.mark_messages(username, &req.ids, req.read).await.map_err(|e| {
+        error!("Ошибка изменения статуса сообщений {}: {:?}", username, e);
+        AppError::from(e)
+    })?;
+
+    info!(
+        "Пользователь {} отметил {} сообщений как {}",
+        username,
+        affected,
+        if req.read { "прочитанные" } else { "непрочитанные" }
+    );
+    let unread = unread_count(&client, username).await?;
+    Ok(MailboxChange { affected, unread })
+}
+
+pub async fn delete_mailbox_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    req: &DeleteMailboxRequest,
+) -> Result<MailboxChange, AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    validate_ids(&req.ids)?;
+    let affected = client.delete_messages(username, &req.ids).await.map_err(|e| {
+        error!("Ошибка удаления сообщений {}: {:?}", username, e);
+        AppError::from(e)
+    })?;
+
+    info!("Пользователь {} удалил {} сообщений", username, affected);
+    let unread = unread_count(&client, username).await?;
+    Ok(MailboxChange { affected, unread })
+}
+
+fn validate_ids(ids: &[i32]) -> Result<(), AppError> {
+    if ids.is_empty() {
+        return Err(AppError::Validation(vec![FieldError::new(
+            "ids",
+            "required",
+            "Поле ids не должно быть пустым",
+        )]));
+    }
+    if ids.len() > MAX_BULK_IDS {
+        return Err(AppError::Validation(vec![FieldError::new(
+            "ids",
+            "too_many",
+            format!("За один запрос можно изменить не больше {} сообщений", MAX_BULK_IDS),
+        )]));
+    }
+    Ok(())
+}
+
+async fn unread_count(client: &PgMailboxClient, username: &str) -> Result<i64, AppError> {
+    client.count_unread(username).await.map_err(|e| {
+        error!("Ошибка подсчёта непрочитанных сообщений {}: {:?}", username, e);
+        AppError::from(e)
+    })
+}
@@ -0,0 +1,213 @@
+use crate::management_engine::clients::clients::mailbox::mailbox::PgMailboxClient;
+use crate::management_engine::clients::traits::mailbox::{MailboxClient, NewSubscription};
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::controllers::telemetry::query::non_empty;
+use crate::management_engine::models::mailbox::mailbox::{SubscriptionRequest, SubscriptionView};
+use crate::management_engine::models::telemetry::telemetry::Severity;
+use actix_web::web;
+use tracing::{error, info};
+
+const MAX_LOCATION_LEN: usize = 150;
+
+pub async fn list_user_subscriptions_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+) -> Result<Vec<SubscriptionView>, AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    client.list_user_subscriptions(username).await.map_err(|e| {
+        error!("Ошибка получения подписок {}: {:?}", username, e);
+        AppError::from(e)
+    })
+}
+
+pub async fn create_user_subscription_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    req: &SubscriptionRequest,
+) -> Result<SubscriptionView, AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let mut subscription = validate_subscription(&client, req).await?;
+    subscription.username = Some(username.to_string());
+    let id = create_subscription(&client, &subscription).await?;
+
+    info!("Пользователь {} подписался на алерты, подписка #{}: {:?}", username, id, subscription);
+    fetch_subscription(&client, id).await
+}
+
+pub async fn delete_user_subscription_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    id: i32,
+) -> Result<(), AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    match client.delete_user_subscription(id, username).await {
+        Ok(true) => {
+            info!("Пользователь {} удалил подписку #{}", username, id);
+            Ok(())
+        }
+        // Подписки роли через этот путь не удаляются
+        Ok(false) => Err(AppError::SubscriptionNotFound(id)),
+        Err(e) => {
+            error!("Ошибка удаления подписки #{}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+pub async fn list_role_subscriptions_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    role_name: &str,
+) -> Result<Vec<SubscriptionView>, AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    fetch_role_id(&client, role_name).await?;
+    client.list_role_subscriptions(role_name).await.map_err(|e| {
+        error!("Ошибка получения подписок роли '{}': {:?}", role_name, e);
+        AppError::from(e)
+    })
+}
+
+pub async fn create_role_subscription_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    role_name: &str,
+    req: &SubscriptionRequest,
+    admin: &str,
+) -> Result<SubscriptionView, AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let role_id = fetch_role_id(&client, role_name).await?;
+    let mut subscription = validate_subscription(&client, req).await?;
+    subscription.role_id = Some(role_id);
+    let id = create_subscription(&client, &subscription).await?;
+
+    info!(
+        "Администратор {} подписал роль '{}' на алерты, подписка #{}: {:?}",
+        admin, role_name, id, subscription
+    );
+    fetch_subscription(&client, id).await
+}
+
+pub async fn delete_role_subscription_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    role_name: &str,
+    id: i32,
+    admin: &str,
+) -> Result<(), AppError> {
+    let client = PgMailboxClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    fetch_role_id(&client, role_name).await?;
+    match client.delete_role_subscription(id, role_name).await {
+        Ok(true) => {
+            info!("Администратор {} удалил подписку #{} роли '{}'", admin, id, role_name);
+            Ok(())
+        }
+        Ok(false) => Err(AppError::SubscriptionNotFound(id)),
+        Err(e) => {
+            error!("Ошибка удаления подписки #{}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+async fn create_subscription(client: &PgMailboxClient, subscription: &NewSubscription) -> Result<i32, AppError> {
+    match client.create_subscription(subscription).await {
+        Ok(id) => Ok(id),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => Err(AppError::SubscriptionExists),
+        Err(e) => {
+            error!("Ошибка создания подписки: {:?}", e);
+            Err(e.into())
+        }
+    }
+}
+
+async fn fetch_subscription(client: &PgMailboxClient, id: i32) -> Result<SubscriptionView, AppError> {
+    match client.get_subscription(id).await {
+        Ok(Some(subscription)) => Ok(subscription),
+        Ok(None) => Err(AppError::SubscriptionNotFound(id)),
+        Err(e) => {
+            error!("Ошибка получения подписки #{}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+async fn fetch_role_id(client: &PgMailboxClient, role_name: &str) -> Result<i32, AppError> {
+    match client.get_role_id(role_name).await {
+        Ok(Some(id)) => Ok(id),
+        Ok(None) => Err(AppError::RoleNotFound(role_name.to_string())),
+        Err(e) => {
+            error!("Ошибка поиска роли '{}': {:?}", role_name, e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Проверяет область подписки; подписчика заполняет вызывающий.
+async fn validate_subscription(
+    client: &PgMailboxClient,
+    req: &SubscriptionRequest,
+) -> Result<NewSubscription, AppError> {
+    let device_name = non_empty(&req.device_name);
+    let location = non_empty(&req.location);
+
+    let mut errors = Vec::new();
+    if device_name.is_some() && location.is_some() {
+        errors.push(FieldError::new(
+            "location",
+            "conflicting_scope",
+            "Подписка задаётся либо на устройство, либо на расположение",
+        ));
+    }
+    if location.as_ref().is_some_and(|l| l.chars().count() > MAX_LOCATION_LEN) {
+        errors.push(FieldError::new(
+            "location",
+            "too_long",
+            format!("Поле location не должно превышать {} символов", MAX_LOCATION_LEN),
+        ));
+    }
+    if req.min_severity == Some(Severity::Ok) {
+        errors.push(FieldError::new(
+            "min_severity",
+            "invalid_value",
+            "Поле min_severity должно быть warning или critical",
+        ));
+    }
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let device_id = match &device_name {
+        None => None,
+        Some(name) => match client.get_device_id(name).await {
+            Ok(Some(id)) => Some(id),
+            Ok(None) => return Err(AppError::DeviceNotFound(name.clone())),
+            Err(e) => {
+                error!("Ошибка поиска устройства '{}': {:?}", name, e);
+                return Err(e.into());
+            }
+        },
+    };
+
+    Ok(NewSubscription {
+        username: None,
+        role_id: None,
+        device_id,
+        location,
+        min_severity: req.min_severity.unwrap_or(Severity::Warning),
+    })
+}
@@ -1 +1,8 @@
+pub mod alerts;
 pub mod auth;
+pub mod errors;
+pub mod mailbox;
+pub mod roles;
+pub mod telemetry;
+pub mod thresholds;
+pub mod users;
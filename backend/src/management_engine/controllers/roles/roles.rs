@@ -0,0 +1,193 @@
+use crate::management_engine::clients::clients::roles::roles::PgRoleClient;
+use crate::management_engine::clients::traits::roles::RoleClient;
+use crate::management_engine::controllers::auth::permissions::is_known_permission;
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::models::roles::roles::{
+    CreateRoleRequest, RoleWithPermissions, UpdateRolePermissionsRequest,
+};
+use actix_web::web;
+use tracing::{error, info};
+
+const MAX_ROLE_NAME_LEN: usize = 50;
+
+pub async fn list_roles_logic(
+    pool: &web::Data<sqlx::PgPool>,
+) -> Result<Vec<RoleWithPermissions>, AppError> {
+    let client = PgRoleClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    client.list_roles().await.map_err(|e| {
+        error!("Ошибка получения списка ролей: {:?}", e);
+        AppError::from(e)
+    })
+}
+
+pub async fn create_role_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    req: &CreateRoleRequest,
+    admin: &str,
+) -> Result<RoleWithPermissions, AppError> {
+    let client = PgRoleClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let role_name = req.role_name.trim();
+    let mut errors = Vec::new();
+    if role_name.is_empty() {
+        errors.push(FieldError::new("role_name", "required", "Поле role_name обязательно"));
+    } else if role_name.chars().count() > MAX_ROLE_NAME_LEN {
+        errors.push(FieldError::new(
+            "role_name",
+            "too_long",
+            format!("Поле role_name не должно превышать {} символов", MAX_ROLE_NAME_LEN),
+        ));
+    }
+    let permissions = match validate_permissions(&req.permissions) {
+        Ok(permissions) => permissions,
+        Err(e) => {
+            errors.extend(e);
+            Vec::new()
+        }
+    };
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let id = match client.create_role(role_name, &permissions).await {
+        Ok(id) => id,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return Err(AppError::RoleExists(role_name.to_string()));
+        }
+        Err(e) => {
+            error!("Ошибка создания роли {}: {:?}", role_name, e);
+            return Err(e.into());
+        }
+    };
+
+    info!(
+        "Администратор {} создал роль '{}' с правами {:?}",
+        admin, role_name, permissions
+    );
+    Ok(RoleWithPermissions {
+        id,
+        role_name: role_name.to_string(),
+        permissions,
+    })
+}
+
+pub async fn update_role_permissions_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    role_name: &str,
+    req: &UpdateRolePermissionsRequest,
+    admin: &str,
+) -> Result<RoleWithPermissions, AppError> {
+    let client = PgRoleClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let permissions = validate_permissions(&req.permissions).map_err(AppError::Validation)?;
+
+    let role = match client.get_role(role_name).await {
+        Ok(Some(role)) => role,
+        Ok(None) => return Err(AppError::RoleNotFound(role_name.to_string())),
+        Err(e) => {
+            error!("Ошибка получения роли {}: {:?}", role_name, e);
+            return Err(e.into());
+        }
+    };
+
+    if let Err(e) = client.set_role_permissions(role.id, &permissions).await {
+        error!("Ошибка изменения прав роли {}: {:?}", role_name, e);
+        return Err(e.into());
+    }
+
+    info!(
+        "Администратор {} изменил права роли '{}': {:?}",
+        admin, role_name, permissions
+    );
+    Ok(RoleWithPermissions {
+        permissions,
+        ..role
+    })
+}
+
+pub async fn assign_role_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    role_name: &str,
+    admin: &str,
+) -> Result<(), AppError> {
+    let client = PgRoleClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    match client.get_role(role_name).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(AppError::RoleNotFound(role_name.to_string())),
+        Err(e) => {
+            error!("Ошибка получения роли {}: {:?}", role_name, e);
+            return Err(e.into());
+        }
+    }
+
+    match client.assign_user_role(username, role_name).await {
+        Ok(true) => {
+            info!(
+                "Администратор {} назначил пользователю {} роль '{}'",
+                admin, username, role_name
+            );
+            Ok(())
+        }
+        Ok(false) => Err(AppError::UserNotFound(username.to_string())),
+        Err(e) => {
+            error!("Ошибка назначения роли пользователю {}: {:?}", username, e);
+            Err(e.into())
+        }
+    }
+}
+
+pub async fn revoke_role_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    admin: &str,
+) -> Result<(), AppError> {
+    let client = PgRoleClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    match client.revoke_user_role(username).await {
+        Ok(true) => {
+            info!("Администратор {} отозвал роль пользователя {}", admin, username);
+            Ok(())
+        }
+        Ok(false) => Err(AppError::UserNotFound(username.to_string())),
+        Err(e) => {
+            error!("Ошибка отзыва роли пользователя {}: {:?}", username, e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Проверяет, что все права известны, и убирает дубликаты.
+fn validate_permissions(permissions: &[String]) -> Result<Vec<String>, Vec<FieldError>> {
+    let errors: Vec<FieldError> = permissions
+        .iter()
+        .filter(|p| !is_known_permission(p))
+        .map(|p| {
+            FieldError::new(
+                "permissions",
+                "unknown_permission",
+                format!("Неизвестное право '{}'", p),
+            )
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut permissions = permissions.to_vec();
+    permissions.sort();
+    permissions.dedup();
+    Ok(permissions)
+}
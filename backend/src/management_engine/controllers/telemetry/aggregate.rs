@@ -0,0 +1,97 @@
+use crate::management_engine::clients::clients::telemetry::telemetry::PgTelemetryClient;
+use crate::management_engine::clients::traits::telemetry::{AggregateFilter, TelemetryClient};
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::controllers::telemetry::query::non_empty;
+use crate::management_engine::controllers::telemetry::rollups::rollup_config;
+use crate::management_engine::models::telemetry::telemetry::{
+    AggregatePoint, AggregateQuery, AggregateResponse, AggregateSeries,
+};
+use actix_web::web;
+use chrono::Utc;
+use tracing::error;
+
+/// Предел корзин в одном ряду, чтобы запрос за год с шагом 1m не выполнялся.
+const MAX_BUCKETS: i64 = 10_000;
+
+/// Агрегаты телеметрии по корзинам для графиков.
+pub async fn aggregate_telemetry_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    query: &AggregateQuery,
+) -> Result<AggregateResponse, AppError> {
+    let client = PgTelemetryClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    if query.from >= query.to {
+        return Err(AppError::Validation(vec![FieldError::new(
+            "to",
+            "invalid_range",
+            "Значение to должно быть позже from",
+        )]));
+    }
+    let buckets = (query.to - query.from).num_seconds() / query.bucket.seconds();
+    if buckets > MAX_BUCKETS {
+        return Err(AppError::Validation(vec![FieldError::new(
+            "bucket",
+            "too_many_buckets",
+            format!(
+                "Интервал содержит {} корзин, допустимо не более {}; увеличьте bucket",
+                buckets, MAX_BUCKETS
+            ),
+        )]));
+    }
+
+    let filter = AggregateFilter {
+        from: query.from.naive_utc(),
+        to: query.to.naive_utc(),
+        device_id: query.device_id,
+        device_name: non_empty(&query.device_name),
+        metric_type_id: query.metric_type_id,
+        metric_name: non_empty(&query.metric_name),
+        rollup_since: rollup_config()
+            .retention(query.bucket.rollup_level())
+            .map(|retention| Utc::now().naive_utc() - retention),
+    };
+
+    let rows = client
+        .aggregate_telemetry(&filter, query.bucket)
+        .await
+        .map_err(|e| {
+            error!("Ошибка агрегации телеметрии: {:?}", e);
+            AppError::from(e)
+        })?;
+
+    // Строки упорядочены по ряду, поэтому ряд меняется на границе пары устройство/метрика
+    let mut series: Vec<AggregateSeries> = Vec::new();
+    for row in rows {
+        let point = AggregatePoint {
+            bucket: row.bucket,
+            count: row.count,
+            min: row.min,
+            max: row.max,
+            avg: row.avg,
+            p95: row.p95,
+            anomaly_count: row.anomaly_count,
+        };
+        match series.last_mut() {
+            Some(s) if s.device_id == row.device_id && s.metric_type_id == row.metric_type_id => {
+                s.points.push(point)
+            }
+            _ => series.push(AggregateSeries {
+                device_id: row.device_id,
+                device_name: row.device_name,
+                metric_type_id: row.metric_type_id,
+                metric_name: row.metric_name,
+                points: vec![point],
+            }),
+        }
+    }
+
+    Ok(AggregateResponse {
+        bucket: query.bucket,
+        p95_approximate: query.bucket.seconds() != query.bucket.rollup_level().seconds(),
+        from: query.from,
+        to: query.to,
+        series,
+    })
+}
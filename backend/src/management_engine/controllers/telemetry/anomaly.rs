@@ -0,0 +1,571 @@
+use crate::management_engine::clients::traits::detectors::DetectorRule;
+use crate::management_engine::clients::traits::telemetry::{TelemetryRow, TelemetryUnitOfWork};
+use crate::management_engine::models::thresholds::detectors::DetectorKind;
+use chrono::NaiveDateTime;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+// =========================================================
+// СТАТИСТИЧЕСКИЕ ДЕТЕКТОРЫ АНОМАЛИЙ
+// =========================================================
+//
+// Детекторы настраиваются по типам метрик (metric_detectors) и оценивают
+// новое значение относительно окна последних значений той же пары
+// устройство/метрика. Значение аномально, если |оценка| >= threshold
+// хотя бы одного детектора; записывается детектор с наибольшим
+// превышением. Пока в окне меньше MIN_SAMPLES значений, статистические
+// детекторы молчат.
+
+/// Имя в anomaly_detector для строк, помеченных критическим порогом.
+pub const THRESHOLD_DETECTOR: &str = "threshold";
+
+const MIN_SAMPLES: usize = 5;
+const DEFAULT_EWMA_ALPHA: f64 = 0.3;
+/// Приводит MAD к стандартному отклонению нормального распределения
+const MAD_SCALE: f64 = 0.6745;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub value: f64,
+    pub at: NaiveDateTime,
+}
+
+pub trait AnomalyDetector: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Сколько последних значений нужно детектору.
+    fn window(&self) -> usize;
+
+    fn threshold(&self) -> f64;
+
+    /// Оценка `sample` относительно `window` (старые значения первыми).
+    /// `None`, если данных недостаточно для оценки.
+    fn score(&self, window: &[Sample], sample: Sample) -> Option<f64>;
+}
+
+fn enough(window: &[Sample], size: usize) -> bool {
+    window.len() >= MIN_SAMPLES.min(size)
+}
+
+fn mean(values: impl Iterator<Item = f64> + Clone) -> f64 {
+    let (sum, n) = values.fold((0.0, 0usize), |(s, n), v| (s + v, n + 1));
+    sum / n as f64
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Отклонение от среднего окна в стандартных отклонениях.
+pub struct ZScore {
+    pub window: usize,
+    pub threshold: f64,
+}
+
+impl AnomalyDetector for ZScore {
+    fn name(&self) -> &'static str {
+        DetectorKind::Zscore.as_str()
+    }
+
+    fn window(&self) -> usize {
+        self.window
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    fn score(&self, window: &[Sample], sample: Sample) -> Option<f64> {
+        if !enough(window, self.window) {
+            return None;
+        }
+        let values = window.iter().map(|s| s.value);
+        let mean = mean(values.clone());
+        let variance = values.map(|v| (v - mean).powi(2)).sum::<f64>() / (window.len() - 1) as f64;
+        let std = variance.sqrt();
+        (std > f64::EPSILON).then(|| (sample.value - mean) / std)
+    }
+}
+
+/// Отклонение от экспоненциально сглаженного среднего в сглаженных
+/// стандартных отклонениях.
+pub struct Ewma {
+    pub window: usize,
+    pub threshold: f64,
+    pub alpha: f64,
+}
+
+impl AnomalyDetector for Ewma {
+    fn name(&self) -> &'static str {
+        DetectorKind::Ewma.as_str()
+    }
+
+    fn window(&self) -> usize {
+        self.window
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    fn score(&self, window: &[Sample], sample: Sample) -> Option<f64> {
+        if !enough(window, self.window) {
+            return None;
+        }
+        let mut mean = window[0].value;
+        let mut variance = 0.0;
+        for s in &window[1..] {
+            let diff = s.value - mean;
+            mean += self.alpha * diff;
+            variance = (1.0 - self.alpha) * (variance + self.alpha * diff * diff);
+        }
+        let std = variance.sqrt();
+        (std > f64::EPSILON).then(|| (sample.value - mean) / std)
+    }
+}
+
+/// Робастная оценка по медиане и медианному абсолютному отклонению (MAD).
+pub struct Mad {
+    pub window: usize,
+    pub threshold: f64,
+}
+
+impl AnomalyDetector for Mad {
+    fn name(&self) -> &'static str {
+        DetectorKind::Mad.as_str()
+    }
+
+    fn window(&self) -> usize {
+        self.window
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    fn score(&self, window: &[Sample], sample: Sample) -> Option<f64> {
+        if !enough(window, self.window) {
+            return None;
+        }
+        let mut values: Vec<f64> = window.iter().map(|s| s.value).collect();
+        let median_value = median(&mut values);
+        let mut deviations: Vec<f64> = values.iter().map(|v| (v - median_value).abs()).collect();
+        let mad = median(&mut deviations);
+        (mad > f64::EPSILON).then(|| MAD_SCALE * (sample.value - median_value) / mad)
+    }
+}
+
+/// Изменение значения в секунду относительно предыдущего измерения.
+pub struct RateOfChange {
+    pub threshold: f64,
+}
+
+impl AnomalyDetector for RateOfChange {
+    fn name(&self) -> &'static str {
+        DetectorKind::RateOfChange.as_str()
+    }
+
+    fn window(&self) -> usize {
+        1
+    }
+
+    fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    fn score(&self, window: &[Sample], sample: Sample) -> Option<f64> {
+        let last = window.last()?;
+        let seconds = (sample.at - last.at).num_milliseconds() as f64 / 1000.0;
+        (seconds > 0.0).then(|| (sample.value - last.value) / seconds)
+    }
+}
+
+pub fn build_detector(rule: &DetectorRule) -> Box<dyn AnomalyDetector> {
+    let window = rule.window_size.max(1) as usize;
+    match rule.detector {
+        DetectorKind::Zscore => Box::new(ZScore {
+            window,
+            threshold: rule.threshold,
+        }),
+        DetectorKind::Ewma => Box::new(Ewma {
+            window,
+            threshold: rule.threshold,
+            alpha: rule.alpha.unwrap_or(DEFAULT_EWMA_ALPHA),
+        }),
+        DetectorKind::Mad => Box::new(Mad {
+            window,
+            threshold: rule.threshold,
+        }),
+        DetectorKind::RateOfChange => Box::new(RateOfChange {
+            threshold: rule.threshold,
+        }),
+    }
+}
+
+/// Сработавший детектор.
+#[derive(Debug, Clone, Copy)]
+pub struct Detection {
+    pub detector: &'static str,
+    pub score: f64,
+}
+
+/// Детекторы по metric_type_id.
+#[derive(Default)]
+pub struct DetectorSet {
+    by_metric: HashMap<i32, Vec<Box<dyn AnomalyDetector>>>,
+}
+
+impl DetectorSet {
+    pub fn from_rules(rules: &[DetectorRule]) -> Self {
+        let mut set = DetectorSet::default();
+        for rule in rules {
+            set.by_metric
+                .entry(rule.metric_type_id)
+                .or_default()
+                .push(build_detector(rule));
+        }
+        set
+    }
+
+    /// Наибольшее окно среди детекторов метрики; `None`, если их нет.
+    pub fn window(&self, metric_type_id: i32) -> Option<usize> {
+        self.by_metric
+            .get(&metric_type_id)
+            .and_then(|detectors| detectors.iter().map(|d| d.window()).max())
+    }
+
+    /// Детектор с наибольшим превышением порога.
+    pub fn detect(&self, metric_type_id: i32, window: &[Sample], sample: Sample) -> Option<Detection> {
+        self.by_metric
+            .get(&metric_type_id)?
+            .iter()
+            .filter_map(|d| {
+                let tail = &window[window.len().saturating_sub(d.window())..];
+                let score = d.score(tail, sample)?;
+                (score.abs() >= d.threshold()).then_some((score.abs() / d.threshold(), d.name(), score))
+            })
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, detector, score)| Detection { detector, score })
+    }
+}
+
+/// Проверяет строки пачки детекторами их метрик. Окно пары берётся из
+/// сохранённых значений и пополняется строками пачки по времени измерения.
+/// Строки, уже помеченные порогом, сохраняют `THRESHOLD_DETECTOR`.
+pub async fn detect_anomalies(
+    uow: &mut Box<dyn TelemetryUnitOfWork>,
+    detectors: &DetectorSet,
+    rows: &mut [TelemetryRow],
+) -> Result<(), sqlx::Error> {
+    let mut sizes: BTreeMap<(i32, i32), usize> = BTreeMap::new();
+    for row in rows.iter() {
+        if let Some(size) = detectors.window(row.metric_type_id) {
+            sizes.insert((row.device_id, row.metric_type_id), size);
+        }
+    }
+    if sizes.is_empty() {
+        return Ok(());
+    }
+
+    let requested: Vec<(i32, i32, i32)> = sizes
+        .iter()
+        .map(|(&(device_id, metric_type_id), &size)| (device_id, metric_type_id, size as i32))
+        .collect();
+    let mut windows: HashMap<(i32, i32), VecDeque<Sample>> = HashMap::new();
+    for value in uow.recent_values(&requested).await? {
+        // Новые значения приходят первыми
+        windows
+            .entry((value.device_id, value.metric_type_id))
+            .or_default()
+            .push_front(Sample {
+                value: value.metric_value,
+                at: value.recorded_at,
+            });
+    }
+
+    let mut order: Vec<usize> = (0..rows.len())
+        .filter(|&i| sizes.contains_key(&(rows[i].device_id, rows[i].metric_type_id)))
+        .collect();
+    order.sort_by_key(|&i| rows[i].recorded_at);
+
+    for i in order {
+        let row = &mut rows[i];
+        let key = (row.device_id, row.metric_type_id);
+        let window = windows.entry(key).or_default();
+        let sample = Sample {
+            value: row.metric_value,
+            at: row.recorded_at,
+        };
+
+        if let Some(detection) = detectors.detect(row.metric_type_id, window.make_contiguous(), sample) {
+            row.is_anomaly = true;
+            if row.anomaly_detector.is_none() {
+                row.anomaly_detector = Some(detection.detector);
+                row.anomaly_score = Some(detection.score);
+            }
+        }
+
+        window.push_back(sample);
+        if window.len() > sizes[&key] {
+            window.pop_front();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    const CPU: i32 = 1;
+    const TRAFFIC: i32 = 2;
+
+    fn at(seconds: i64) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            + Duration::seconds(seconds)
+    }
+
+    /// Значения с шагом 10 секунд, начиная с нулевой секунды.
+    fn samples(values: &[f64]) -> Vec<Sample> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| Sample {
+                value,
+                at: at(i as i64 * 10),
+            })
+            .collect()
+    }
+
+    fn next(window: &[Sample], value: f64) -> Sample {
+        Sample {
+            value,
+            at: at(window.len() as i64 * 10),
+        }
+    }
+
+    fn rule(metric_type_id: i32, detector: DetectorKind, window_size: i32, threshold: f64) -> DetectorRule {
+        DetectorRule {
+            metric_type_id,
+            detector,
+            window_size,
+            threshold,
+            alpha: None,
+        }
+    }
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("ожидалась оценка");
+        assert!((actual - expected).abs() < 1e-3, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn zscore_needs_min_samples() {
+        let detector = ZScore {
+            window: 20,
+            threshold: 3.0,
+        };
+        let window = samples(&[10.0, 12.0, 10.0, 12.0]);
+        assert_eq!(detector.score(&window, next(&window, 100.0)), None);
+    }
+
+    #[test]
+    fn zscore_small_window_is_enough_when_full() {
+        let detector = ZScore {
+            window: 3,
+            threshold: 3.0,
+        };
+        let window = samples(&[10.0, 12.0, 14.0]);
+        // Среднее 12, выборочное стандартное отклонение 2
+        assert_close(detector.score(&window, next(&window, 18.0)), 3.0);
+    }
+
+    #[test]
+    fn zscore_silent_on_zero_variance() {
+        let detector = ZScore {
+            window: 5,
+            threshold: 3.0,
+        };
+        let window = samples(&[10.0; 5]);
+        assert_eq!(detector.score(&window, next(&window, 100.0)), None);
+    }
+
+    #[test]
+    fn zscore_scores_deviation_in_std() {
+        let detector = ZScore {
+            window: 5,
+            threshold: 3.0,
+        };
+        let window = samples(&[10.0, 12.0, 10.0, 12.0, 10.0]);
+        // Среднее 10.8, выборочная дисперсия 1.2
+        assert_close(detector.score(&window, next(&window, 10.8)), 0.0);
+        assert_close(detector.score(&window, next(&window, 12.8)), 2.0 / 1.2f64.sqrt());
+        assert_close(detector.score(&window, next(&window, 8.8)), -2.0 / 1.2f64.sqrt());
+    }
+
+    #[test]
+    fn ewma_needs_min_samples() {
+        let detector = Ewma {
+            window: 20,
+            threshold: 3.0,
+            alpha: DEFAULT_EWMA_ALPHA,
+        };
+        let window = samples(&[10.0, 12.0, 10.0, 12.0]);
+        assert_eq!(detector.score(&window, next(&window, 100.0)), None);
+    }
+
+    #[test]
+    fn ewma_silent_on_zero_variance() {
+        let detector = Ewma {
+            window: 5,
+            threshold: 3.0,
+            alpha: DEFAULT_EWMA_ALPHA,
+        };
+        let window = samples(&[10.0; 5]);
+        assert_eq!(detector.score(&window, next(&window, 100.0)), None);
+    }
+
+    #[test]
+    fn ewma_follows_smoothed_mean() {
+        let detector = Ewma {
+            window: 5,
+            threshold: 3.0,
+            alpha: 0.5,
+        };
+        let window = samples(&[0.0, 0.0, 0.0, 0.0, 16.0]);
+        // После скачка: среднее 8, дисперсия 0.5 * (0 + 0.5 * 256) = 64
+        assert_close(detector.score(&window, next(&window, 8.0)), 0.0);
+        assert_close(detector.score(&window, next(&window, 32.0)), 3.0);
+        assert_close(detector.score(&window, next(&window, 0.0)), -1.0);
+    }
+
+    #[test]
+    fn mad_needs_min_samples() {
+        let detector = Mad {
+            window: 20,
+            threshold: 3.5,
+        };
+        let window = samples(&[10.0, 11.0, 12.0, 13.0]);
+        assert_eq!(detector.score(&window, next(&window, 100.0)), None);
+    }
+
+    #[test]
+    fn mad_silent_on_zero_deviation() {
+        let detector = Mad {
+            window: 5,
+            threshold: 3.5,
+        };
+        // Медианное отклонение нулевое, хотя одно значение выбивается
+        let window = samples(&[10.0, 10.0, 10.0, 10.0, 12.0]);
+        assert_eq!(detector.score(&window, next(&window, 100.0)), None);
+    }
+
+    #[test]
+    fn mad_scores_against_median() {
+        let detector = Mad {
+            window: 6,
+            threshold: 3.5,
+        };
+        // Медиана 12, MAD 1; выброс 1000 на оценку не влияет
+        let window = samples(&[10.0, 11.0, 12.0, 12.0, 13.0, 1000.0]);
+        assert_close(detector.score(&window, next(&window, 12.0)), 0.0);
+        assert_close(detector.score(&window, next(&window, 20.0)), MAD_SCALE * 8.0);
+    }
+
+    #[test]
+    fn rate_of_change_needs_previous_sample() {
+        let detector = RateOfChange { threshold: 1.0 };
+        assert_eq!(detector.score(&[], next(&[], 100.0)), None);
+    }
+
+    #[test]
+    fn rate_of_change_ignores_same_timestamp() {
+        let detector = RateOfChange { threshold: 1.0 };
+        let window = samples(&[10.0]);
+        let sample = Sample {
+            value: 100.0,
+            at: window[0].at,
+        };
+        assert_eq!(detector.score(&window, sample), None);
+    }
+
+    #[test]
+    fn rate_of_change_is_per_second() {
+        let detector = RateOfChange { threshold: 1.0 };
+        // Значение окна на нулевой секунде, новое через 10 секунд
+        let window = samples(&[10.0]);
+        assert_close(detector.score(&window, next(&window, 30.0)), 2.0);
+        assert_close(detector.score(&window, next(&window, 0.0)), -1.0);
+    }
+
+    #[test]
+    fn detector_set_selects_detectors_by_metric() {
+        let set = DetectorSet::from_rules(&[
+            rule(CPU, DetectorKind::Zscore, 5, 3.0),
+            rule(TRAFFIC, DetectorKind::RateOfChange, 1, 1.0),
+        ]);
+        let window = samples(&[10.0, 12.0, 10.0, 12.0, 10.0]);
+        let sample = next(&window, 50.0);
+
+        assert_eq!(set.window(CPU), Some(5));
+        assert_eq!(set.window(TRAFFIC), Some(1));
+        assert_eq!(set.window(3), None);
+
+        assert_eq!(set.detect(CPU, &window, sample).map(|d| d.detector), Some("zscore"));
+        let traffic = set.detect(TRAFFIC, &window, sample).expect("ожидалось срабатывание");
+        assert_eq!(traffic.detector, "rate_of_change");
+        assert_close(Some(traffic.score), 4.0);
+        assert!(set.detect(3, &window, sample).is_none());
+    }
+
+    #[test]
+    fn detector_set_fires_at_exact_threshold() {
+        let set = DetectorSet::from_rules(&[rule(TRAFFIC, DetectorKind::RateOfChange, 1, 2.0)]);
+        let window = samples(&[10.0]);
+
+        assert!(set.detect(TRAFFIC, &window, next(&window, 29.0)).is_none());
+        assert!(set.detect(TRAFFIC, &window, next(&window, 30.0)).is_some());
+        assert!(set.detect(TRAFFIC, &window, next(&window, -10.0)).is_some());
+    }
+
+    #[test]
+    fn detector_set_reports_largest_excess() {
+        let set = DetectorSet::from_rules(&[
+            rule(CPU, DetectorKind::Zscore, 5, 3.0),
+            rule(CPU, DetectorKind::RateOfChange, 1, 100.0),
+        ]);
+        let window = samples(&[10.0, 12.0, 10.0, 12.0, 10.0]);
+
+        // z-оценка ~82 при пороге 3; скорость 9 в секунду ниже порога 100
+        let detection = set.detect(CPU, &window, next(&window, 100.0)).expect("ожидалось срабатывание");
+        assert_eq!(detection.detector, "zscore");
+
+        let set = DetectorSet::from_rules(&[
+            rule(CPU, DetectorKind::Zscore, 5, 50.0),
+            rule(CPU, DetectorKind::RateOfChange, 1, 1.0),
+        ]);
+        // Сработали оба: z-оценка превышает порог в 1.6 раза, скорость — в 9 раз
+        let detection = set.detect(CPU, &window, next(&window, 100.0)).expect("ожидалось срабатывание");
+        assert_eq!(detection.detector, "rate_of_change");
+    }
+
+    #[test]
+    fn detector_set_uses_each_detector_window() {
+        // Окно z-оценки из трёх последних значений: 20, 22, 24
+        let set = DetectorSet::from_rules(&[rule(CPU, DetectorKind::Zscore, 3, 3.0)]);
+        let window = samples(&[0.0, 100.0, 20.0, 22.0, 24.0]);
+
+        assert_eq!(set.window(CPU), Some(3));
+        assert!(set.detect(CPU, &window, next(&window, 26.0)).is_none());
+        assert!(set.detect(CPU, &window, next(&window, 30.0)).is_some());
+    }
+}
@@ -0,0 +1,310 @@
+use crate::management_engine::clients::traits::telemetry::{
+    DeviceHealthRow, MetricHealth, TelemetryRow, TelemetryUnitOfWork,
+};
+use crate::management_engine::models::telemetry::telemetry::{DeviceStatus, Severity};
+use chrono::{Duration, NaiveDateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::env;
+use std::sync::OnceLock;
+use tracing::info;
+
+static HEALTH_CONFIG: OnceLock<HealthConfig> = OnceLock::new();
+
+// =========================================================
+// СТАТУС УСТРОЙСТВА С ГИСТЕРЕЗИСОМ
+// =========================================================
+//
+// DEVICE_STATUS_RAISE_AFTER  измерений подряд на уровне warning/critical,
+//                            чтобы перевести устройство в warning (3)
+// DEVICE_STATUS_CLEAR_AFTER  измерений подряд на уровне ok по каждой метрике,
+//                            чтобы вернуть устройство в active (5)
+// DEVICE_STATUS_STALE_SECS   метрика без новых значений дольше этого срока
+//                            не учитывается в статусе (3600)
+//
+// Между порогами статус не меняется, поэтому одиночные выбросы не
+// переключают его туда и обратно. Метрика, которая перестала приходить,
+// не держит устройство в warning. Статус inactive ставится вручную
+// и автоматически не меняется.
+
+#[derive(Debug, Clone)]
+pub struct HealthConfig {
+    pub raise_after: i32,
+    pub clear_after: i32,
+    pub stale_after: Duration,
+}
+
+impl HealthConfig {
+    pub fn from_env() -> Self {
+        fn var(name: &str, default: i32) -> i32 {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+                .max(1)
+        }
+
+        HealthConfig {
+            raise_after: var("DEVICE_STATUS_RAISE_AFTER", 3),
+            clear_after: var("DEVICE_STATUS_CLEAR_AFTER", 5),
+            stale_after: Duration::seconds(var("DEVICE_STATUS_STALE_SECS", 3600).into()),
+        }
+    }
+
+    /// Новый статус устройства по состоянию его метрик, приходивших не раньше
+    /// `now - stale_after`, или `None`, если он не меняется.
+    pub fn next_status(
+        &self,
+        current: DeviceStatus,
+        metrics: &[DeviceHealthRow],
+        now: NaiveDateTime,
+    ) -> Option<DeviceStatus> {
+        let fresh: Vec<&DeviceHealthRow> = metrics
+            .iter()
+            .filter(|m| m.updated_at >= now - self.stale_after)
+            .collect();
+        if fresh.is_empty() {
+            return None;
+        }
+        match current {
+            DeviceStatus::Active
+                if fresh
+                    .iter()
+                    .any(|m| m.severity != Severity::Ok && m.streak >= self.raise_after) =>
+            {
+                Some(DeviceStatus::Warning)
+            }
+            DeviceStatus::Warning
+                if fresh
+                    .iter()
+                    .all(|m| m.severity == Severity::Ok && m.streak >= self.clear_after) =>
+            {
+                Some(DeviceStatus::Active)
+            }
+            _ => None,
+        }
+    }
+}
+
+pub fn health_config() -> &'static HealthConfig {
+    HEALTH_CONFIG.get_or_init(HealthConfig::from_env)
+}
+
+/// Продлевает серию, если класс уровня (ok / тревога) не сменился, иначе начинает новую.
+fn advance(health: &mut MetricHealth, severity: Severity) {
+    if (health.severity == Severity::Ok) == (severity == Severity::Ok) {
+        health.streak = health.streak.saturating_add(1);
+    } else {
+        health.streak = 1;
+    }
+    health.severity = severity;
+}
+
+/// Обновляет серии по парам устройство/метрика из вставленных строк и
+/// пересчитывает статусы затронутых устройств в той же транзакции.
+pub async fn update_device_health(
+    uow: &mut Box<dyn TelemetryUnitOfWork>,
+    rows: &[&TelemetryRow],
+) -> Result<(), sqlx::Error> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let config = health_config();
+    let now = Utc::now().naive_utc();
+
+    let pairs: Vec<(i32, i32)> = rows
+        .iter()
+        .map(|r| (r.device_id, r.metric_type_id))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let mut health: HashMap<(i32, i32), MetricHealth> = uow
+        .lock_metric_health(&pairs)
+        .await?
+        .into_iter()
+        .map(|h| ((h.device_id, h.metric_type_id), h))
+        .collect();
+
+    let mut ordered = rows.to_vec();
+    ordered.sort_by_key(|r| r.recorded_at);
+    for row in ordered {
+        let entry = health
+            .entry((row.device_id, row.metric_type_id))
+            .or_insert(MetricHealth {
+                device_id: row.device_id,
+                metric_type_id: row.metric_type_id,
+                severity: row.severity,
+                streak: 0,
+            });
+        advance(entry, row.severity);
+    }
+    let updated: Vec<MetricHealth> = health.into_values().collect();
+    uow.upsert_metric_health(&updated).await?;
+
+    let device_ids: Vec<i32> = pairs.iter().map(|(d, _)| *d).collect::<BTreeSet<_>>().into_iter().collect();
+    let mut devices: BTreeMap<i32, Vec<DeviceHealthRow>> = BTreeMap::new();
+    for row in uow.get_device_health(&device_ids).await? {
+        devices.entry(row.id).or_default().push(row);
+    }
+
+    for (device_id, metrics) in devices {
+        let current = metrics[0].status;
+        if let Some(status) = config.next_status(current, &metrics, now)
+            && uow.set_device_status(device_id, status).await?
+        {
+            info!(
+                "Статус устройства {} изменён: {:?} -> {:?}",
+                metrics[0].device_name, current, status
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> HealthConfig {
+        HealthConfig {
+            raise_after: 3,
+            clear_after: 5,
+            stale_after: Duration::hours(1),
+        }
+    }
+
+    fn now() -> NaiveDateTime {
+        "2024-01-01T12:00:00".parse().unwrap()
+    }
+
+    fn metric(status: DeviceStatus, severity: Severity, streak: i32) -> DeviceHealthRow {
+        DeviceHealthRow {
+            id: 1,
+            device_name: "Router-01".to_string(),
+            status,
+            severity,
+            streak,
+            updated_at: now(),
+        }
+    }
+
+    /// Метрика, последний раз приходившая `age` назад.
+    fn reported(age: Duration, row: DeviceHealthRow) -> DeviceHealthRow {
+        DeviceHealthRow {
+            updated_at: now() - age,
+            ..row
+        }
+    }
+
+    #[test]
+    fn raises_warning_at_exact_raise_streak() {
+        let below = [metric(DeviceStatus::Active, Severity::Warning, 2)];
+        let exact = [metric(DeviceStatus::Active, Severity::Warning, 3)];
+        let critical = [metric(DeviceStatus::Active, Severity::Critical, 3)];
+
+        assert_eq!(config().next_status(DeviceStatus::Active, &below, now()), None);
+        assert_eq!(config().next_status(DeviceStatus::Active, &exact, now()), Some(DeviceStatus::Warning));
+        assert_eq!(config().next_status(DeviceStatus::Active, &critical, now()), Some(DeviceStatus::Warning));
+    }
+
+    #[test]
+    fn raises_warning_when_any_metric_alarms() {
+        let metrics = [
+            metric(DeviceStatus::Active, Severity::Ok, 10),
+            metric(DeviceStatus::Active, Severity::Critical, 3),
+        ];
+        assert_eq!(config().next_status(DeviceStatus::Active, &metrics, now()), Some(DeviceStatus::Warning));
+    }
+
+    #[test]
+    fn active_stays_active_on_ok_metrics() {
+        let metrics = [metric(DeviceStatus::Active, Severity::Ok, 100)];
+        assert_eq!(config().next_status(DeviceStatus::Active, &metrics, now()), None);
+    }
+
+    #[test]
+    fn clears_warning_at_exact_clear_streak() {
+        let below = [metric(DeviceStatus::Warning, Severity::Ok, 4)];
+        let exact = [metric(DeviceStatus::Warning, Severity::Ok, 5)];
+
+        assert_eq!(config().next_status(DeviceStatus::Warning, &below, now()), None);
+        assert_eq!(config().next_status(DeviceStatus::Warning, &exact, now()), Some(DeviceStatus::Active));
+    }
+
+    #[test]
+    fn keeps_warning_until_every_metric_clears() {
+        let one_short = [
+            metric(DeviceStatus::Warning, Severity::Ok, 5),
+            metric(DeviceStatus::Warning, Severity::Ok, 4),
+        ];
+        let one_alarming = [
+            metric(DeviceStatus::Warning, Severity::Ok, 5),
+            metric(DeviceStatus::Warning, Severity::Warning, 1),
+        ];
+
+        assert_eq!(config().next_status(DeviceStatus::Warning, &one_short, now()), None);
+        assert_eq!(config().next_status(DeviceStatus::Warning, &one_alarming, now()), None);
+    }
+
+    #[test]
+    fn streak_between_thresholds_does_not_flap() {
+        // Серия ok длиной raise_after ещё не снимает warning
+        let ok = [metric(DeviceStatus::Warning, Severity::Ok, 3)];
+        assert_eq!(config().next_status(DeviceStatus::Warning, &ok, now()), None);
+    }
+
+    #[test]
+    fn stale_alarming_metric_does_not_hold_warning() {
+        let metrics = [
+            metric(DeviceStatus::Warning, Severity::Ok, 5),
+            reported(Duration::hours(2), metric(DeviceStatus::Warning, Severity::Critical, 10)),
+        ];
+        assert_eq!(config().next_status(DeviceStatus::Warning, &metrics, now()), Some(DeviceStatus::Active));
+    }
+
+    #[test]
+    fn metric_within_stale_window_still_counts() {
+        let metrics = [
+            metric(DeviceStatus::Warning, Severity::Ok, 5),
+            reported(Duration::hours(1), metric(DeviceStatus::Warning, Severity::Critical, 10)),
+        ];
+        assert_eq!(config().next_status(DeviceStatus::Warning, &metrics, now()), None);
+    }
+
+    #[test]
+    fn stale_metrics_alone_change_nothing() {
+        let alarming = [reported(Duration::hours(2), metric(DeviceStatus::Active, Severity::Critical, 10))];
+        let ok = [reported(Duration::hours(2), metric(DeviceStatus::Warning, Severity::Ok, 10))];
+
+        assert_eq!(config().next_status(DeviceStatus::Active, &alarming, now()), None);
+        assert_eq!(config().next_status(DeviceStatus::Warning, &ok, now()), None);
+    }
+
+    #[test]
+    fn inactive_is_never_changed() {
+        let alarming = [metric(DeviceStatus::Inactive, Severity::Critical, 100)];
+        let ok = [metric(DeviceStatus::Inactive, Severity::Ok, 100)];
+
+        assert_eq!(config().next_status(DeviceStatus::Inactive, &alarming, now()), None);
+        assert_eq!(config().next_status(DeviceStatus::Inactive, &ok, now()), None);
+    }
+
+    #[test]
+    fn advance_restarts_streak_on_class_change() {
+        let mut health = MetricHealth {
+            device_id: 1,
+            metric_type_id: 1,
+            severity: Severity::Ok,
+            streak: 4,
+        };
+
+        advance(&mut health, Severity::Ok);
+        assert_eq!((health.severity, health.streak), (Severity::Ok, 5));
+        advance(&mut health, Severity::Warning);
+        assert_eq!((health.severity, health.streak), (Severity::Warning, 1));
+        // warning -> critical остаётся тревогой, серия продолжается
+        advance(&mut health, Severity::Critical);
+        assert_eq!((health.severity, health.streak), (Severity::Critical, 2));
+        advance(&mut health, Severity::Ok);
+        assert_eq!((health.severity, health.streak), (Severity::Ok, 1));
+    }
+}
@@ -0,0 +1,413 @@
+use crate::management_engine::clients::clients::detectors::detectors::PgDetectorClient;
+use crate::management_engine::clients::clients::telemetry::telemetry::PgTelemetryClient;
+use crate::management_engine::clients::clients::thresholds::thresholds::PgThresholdClient;
+use crate::management_engine::clients::traits::detectors::DetectorClient;
+use crate::management_engine::clients::traits::telemetry::{
+    DeviceRow, TelemetryClient, TelemetryRow,
+};
+use crate::management_engine::controllers::alerts::episodes::track_alerts;
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::controllers::telemetry::anomaly::{
+    DetectorSet, THRESHOLD_DETECTOR, detect_anomalies,
+};
+use crate::management_engine::controllers::telemetry::health::update_device_health;
+use crate::management_engine::controllers::telemetry::metrics::MetricResolver;
+use crate::management_engine::controllers::telemetry::thresholds::threshold_set;
+use crate::management_engine::models::telemetry::telemetry::{
+    IngestEventError, IngestMode, IngestResponse, Severity,
+};
+use actix_web::ResponseError;
+use actix_web::http::StatusCode;
+use chrono::{Duration, NaiveDateTime, Utc};
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::sync::OnceLock;
+use telemetry_protocol::TelemetryEvent;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+static INGEST_CONFIG: OnceLock<IngestConfig> = OnceLock::new();
+
+// =========================================================
+// ПРИЁМ ПАЧКИ ТЕЛЕМЕТРИИ
+// =========================================================
+//
+// TELEMETRY_INGEST_MODE        partial (по умолчанию) | atomic
+// TELEMETRY_MAX_FUTURE_SKEW_SECS  насколько observed_at может опережать сервер (300)
+// TELEMETRY_MAX_PAST_AGE_SECS     насколько observed_at может отставать (86400)
+// TELEMETRY_SKEW_POLICY        reject (по умолчанию) | clamp
+//
+// partial — корректные события записываются, ошибочные перечисляются в ответе;
+// atomic  — любая ошибка отклоняет всю пачку, ничего не записывается.
+// reject  — событие вне окна допуска отклоняется как ошибка валидации;
+// clamp   — observed_at приводится к ближайшей границе окна.
+
+/// Что делать с observed_at за пределами допуска по часам.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkewPolicy {
+    Reject,
+    Clamp,
+}
+
+#[derive(Debug, Clone)]
+pub struct IngestConfig {
+    pub default_mode: IngestMode,
+    pub max_future_skew: Duration,
+    pub max_past_age: Duration,
+    pub skew_policy: SkewPolicy,
+}
+
+impl IngestConfig {
+    pub fn from_env() -> Self {
+        fn secs(name: &str, default: i64) -> Duration {
+            Duration::seconds(
+                env::var(name)
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(default),
+            )
+        }
+
+        let default_mode = match env::var("TELEMETRY_INGEST_MODE").as_deref() {
+            Ok("atomic") => IngestMode::Atomic,
+            Ok("partial") | Err(_) => IngestMode::Partial,
+            Ok(other) => {
+                warn!("Неизвестный TELEMETRY_INGEST_MODE '{}', используется partial", other);
+                IngestMode::Partial
+            }
+        };
+        let skew_policy = match env::var("TELEMETRY_SKEW_POLICY").as_deref() {
+            Ok("clamp") => SkewPolicy::Clamp,
+            Ok("reject") | Err(_) => SkewPolicy::Reject,
+            Ok(other) => {
+                warn!("Неизвестный TELEMETRY_SKEW_POLICY '{}', используется reject", other);
+                SkewPolicy::Reject
+            }
+        };
+
+        IngestConfig {
+            default_mode,
+            max_future_skew: secs("TELEMETRY_MAX_FUTURE_SKEW_SECS", 300),
+            max_past_age: secs("TELEMETRY_MAX_PAST_AGE_SECS", 86400),
+            skew_policy,
+        }
+    }
+
+    /// Время измерения для записи. События без observed_at получают время приёма.
+    pub fn observed_at(
+        &self,
+        event: &TelemetryEvent,
+        received_at: NaiveDateTime,
+    ) -> Result<NaiveDateTime, FieldError> {
+        let Some(observed_at) = event.observed_at.map(|t| t.naive_utc()) else {
+            return Ok(received_at);
+        };
+        let latest = received_at + self.max_future_skew;
+        let earliest = received_at - self.max_past_age;
+
+        match self.skew_policy {
+            SkewPolicy::Clamp => Ok(observed_at.clamp(earliest, latest)),
+            SkewPolicy::Reject if observed_at > latest => Err(FieldError::new(
+                "observed_at",
+                "in_future",
+                format!(
+                    "observed_at опережает время сервера более чем на {} с",
+                    self.max_future_skew.num_seconds()
+                ),
+            )),
+            SkewPolicy::Reject if observed_at < earliest => Err(FieldError::new(
+                "observed_at",
+                "too_old",
+                format!(
+                    "observed_at старше времени сервера более чем на {} с",
+                    self.max_past_age.num_seconds()
+                ),
+            )),
+            SkewPolicy::Reject => Ok(observed_at),
+        }
+    }
+}
+
+pub fn ingest_config() -> &'static IngestConfig {
+    INGEST_CONFIG.get_or_init(IngestConfig::from_env)
+}
+
+/// Итог приёма пачки и ошибка БД, если запись не удалась целиком.
+#[derive(Debug)]
+pub struct IngestOutcome {
+    pub response: IngestResponse,
+    pub batch_error: Option<AppError>,
+}
+
+impl IngestOutcome {
+    /// 200, если пачка пуста или хоть что-то записано; иначе статус
+    /// ошибки БД или 422 для некорректных событий.
+    pub fn status(&self) -> StatusCode {
+        if self.response.rejected == 0 || self.response.accepted > 0 {
+            StatusCode::OK
+        } else if let Some(err) = &self.batch_error {
+            err.status_code()
+        } else {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+    }
+}
+
+/// Событие, прошедшее проверку и готовое к записи.
+struct PreparedEvent<'a> {
+    index: usize,
+    event: &'a TelemetryEvent,
+    metric_type_id: i32,
+    observed_at: NaiveDateTime,
+}
+
+fn event_error(index: usize, err: &AppError) -> IngestEventError {
+    IngestEventError {
+        index,
+        code: err.code(),
+        message: err.to_string(),
+        errors: match err {
+            AppError::Validation(errors) => errors.clone(),
+            _ => Vec::new(),
+        },
+    }
+}
+
+/// Проверяет контракт события и допуск по часам.
+fn validate_event(
+    event: &TelemetryEvent,
+    received_at: NaiveDateTime,
+) -> Result<NaiveDateTime, AppError> {
+    let mut errors: Vec<FieldError> = match event.validate() {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.into_iter().map(Into::into).collect(),
+    };
+    match ingest_config().observed_at(event, received_at) {
+        Ok(observed_at) if errors.is_empty() => Ok(observed_at),
+        Ok(_) => Err(AppError::Validation(errors)),
+        Err(err) => {
+            errors.push(err);
+            Err(AppError::Validation(errors))
+        }
+    }
+}
+
+/// Принимает пачку событий: проверяет контракт и метрики, затем одной
+/// транзакцией создаёт устройства и вставляет всю телеметрию.
+/// Ошибка БД откатывает всю пачку и отмечается на каждом событии.
+/// Уже записанные event_id возвращаются как дубликаты, а не ошибки.
+pub async fn ingest_batch(pool: &PgPool, events: &[TelemetryEvent], mode: IngestMode) -> IngestOutcome {
+    let client = PgTelemetryClient { pool: pool.clone() };
+    let mut resolver = MetricResolver::new(pool);
+    let mut errors = Vec::new();
+    let received_at = Utc::now().naive_utc();
+
+    // Проверка событий и сопоставление метрик
+    let mut prepared: Vec<PreparedEvent> = Vec::with_capacity(events.len());
+    for (index, event) in events.iter().enumerate() {
+        let observed_at = match validate_event(event, received_at) {
+            Ok(observed_at) => observed_at,
+            Err(err) => {
+                errors.push(event_error(index, &err));
+                continue;
+            }
+        };
+        match resolver.resolve(&event.metric).await {
+            Ok(metric_type_id) => prepared.push(PreparedEvent {
+                index,
+                event,
+                metric_type_id,
+                observed_at,
+            }),
+            Err(err) => errors.push(event_error(index, &err)),
+        }
+    }
+
+    // Повтор event_id внутри пачки записывается один раз
+    let mut duplicate_indexes = Vec::new();
+    let mut seen_ids = HashSet::new();
+    prepared.retain(|p| match p.event.event_id {
+        Some(id) if !seen_ids.insert(id) => {
+            duplicate_indexes.push(p.index);
+            false
+        }
+        _ => true,
+    });
+
+    let mut outcome = IngestOutcome {
+        response: IngestResponse {
+            mode,
+            accepted: 0,
+            duplicates: 0,
+            rejected: errors.len(),
+            errors,
+            duplicate_indexes: Vec::new(),
+        },
+        batch_error: None,
+    };
+
+    if mode == IngestMode::Atomic && !outcome.response.errors.is_empty() {
+        outcome.response.rejected = events.len();
+        info!(
+            "Пачка из {} событий отклонена целиком: ошибок {}",
+            events.len(),
+            outcome.response.errors.len()
+        );
+        return outcome;
+    }
+
+    if !prepared.is_empty() {
+        match write_batch(&client, &prepared, received_at).await {
+            Ok(inserted) => {
+                outcome.response.accepted = inserted.len();
+                // Событие с event_id, которого нет среди вставленных, уже было записано
+                let inserted: HashSet<Uuid> = inserted.into_iter().flatten().collect();
+                duplicate_indexes.extend(
+                    prepared
+                        .iter()
+                        .filter(|p| p.event.event_id.is_some_and(|id| !inserted.contains(&id)))
+                        .map(|p| p.index),
+                );
+            }
+            Err(err) => {
+                let failed = event_error(0, &err);
+                outcome.response.errors.extend(prepared.iter().map(|p| IngestEventError {
+                    index: p.index,
+                    ..failed.clone()
+                }));
+                outcome.response.errors.sort_by_key(|e| e.index);
+                outcome.response.rejected = outcome.response.errors.len();
+                outcome.batch_error = Some(err);
+                return outcome;
+            }
+        }
+    }
+
+    duplicate_indexes.sort_unstable();
+    outcome.response.duplicates = duplicate_indexes.len();
+    outcome.response.duplicate_indexes = duplicate_indexes;
+    info!(
+        "Пачка телеметрии: записано {}, дубликатов {}, отклонено {}",
+        outcome.response.accepted, outcome.response.duplicates, outcome.response.rejected
+    );
+    outcome
+}
+
+/// Одной транзакцией создаёт устройства и вставляет подготовленные события.
+/// Возвращает event_id вставленных строк (`None` для событий без id).
+async fn write_batch(
+    client: &PgTelemetryClient,
+    prepared: &[PreparedEvent<'_>],
+    received_at: NaiveDateTime,
+) -> Result<Vec<Option<Uuid>>, AppError> {
+    let threshold_client = PgThresholdClient {
+        pool: client.pool.clone(),
+    };
+    let thresholds = threshold_set(&threshold_client).await.map_err(|e| {
+        error!("Ошибка получения порогов: {:?}", e);
+        AppError::from(e)
+    })?;
+    let detector_client = PgDetectorClient {
+        pool: client.pool.clone(),
+    };
+    let detectors = match detector_client.get_detector_rules().await {
+        Ok(rules) => DetectorSet::from_rules(&rules),
+        Err(e) => {
+            error!("Ошибка получения детекторов аномалий: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    // Уникальные устройства пачки: данные берутся из первого события
+    let mut devices: Vec<DeviceRow> = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    for p in prepared {
+        if seen.insert(p.event.device_name.as_str()) {
+            devices.push(DeviceRow {
+                device_name: p.event.device_name.clone(),
+                ip_address: p.event.ip_address.clone(),
+                location: p.event.location.clone(),
+            });
+        }
+    }
+
+    let mut uow = client.begin().await.map_err(|e| {
+        error!("Ошибка начала транзакции телеметрии: {:?}", e);
+        AppError::from(e)
+    })?;
+
+    let stored: HashMap<String, (i32, Option<String>)> = uow
+        .upsert_devices(&devices)
+        .await
+        .map_err(|e| {
+            error!("Ошибка вставки устройств: {:?}", e);
+            AppError::from(e)
+        })?
+        .into_iter()
+        .map(|(device_name, id, location)| (device_name, (id, location)))
+        .collect();
+
+    let mut rows = Vec::with_capacity(prepared.len());
+    for p in prepared {
+        let Some((device_id, location)) = stored.get(&p.event.device_name) else {
+            return Err(AppError::Internal(format!(
+                "устройство {} не вернулось из upsert",
+                p.event.device_name
+            )));
+        };
+        let severity = thresholds.severity(
+            p.metric_type_id,
+            *device_id,
+            location.as_deref(),
+            p.event.metric_value,
+        );
+        let critical = severity == Severity::Critical;
+
+        rows.push(TelemetryRow {
+            device_id: *device_id,
+            metric_type_id: p.metric_type_id,
+            metric_value: p.event.metric_value,
+            is_anomaly: critical,
+            severity,
+            action_description: p.event.action_description.clone(),
+            recorded_at: p.observed_at,
+            received_at,
+            event_id: p.event.event_id,
+            anomaly_detector: critical.then_some(THRESHOLD_DETECTOR),
+            anomaly_score: None,
+        });
+    }
+
+    detect_anomalies(&mut uow, &detectors, &mut rows).await.map_err(|e| {
+        error!("Ошибка проверки детекторами аномалий: {:?}", e);
+        AppError::from(e)
+    })?;
+
+    let inserted = uow.insert_telemetry(&rows).await.map_err(|e| {
+        error!("Ошибка вставки телеметрии: {:?}", e);
+        AppError::from(e)
+    })?;
+
+    // Серии, статусы и алерты считаются только по реально вставленным строкам,
+    // чтобы повторно присланные события не продлевали их
+    let inserted_ids: HashSet<Uuid> = inserted.iter().flatten().copied().collect();
+    let inserted_rows: Vec<&TelemetryRow> = rows
+        .iter()
+        .filter(|row| row.event_id.is_none_or(|id| inserted_ids.contains(&id)))
+        .collect();
+    update_device_health(&mut uow, &inserted_rows).await.map_err(|e| {
+        error!("Ошибка обновления статусов устройств: {:?}", e);
+        AppError::from(e)
+    })?;
+    track_alerts(&mut uow, &inserted_rows).await.map_err(|e| {
+        error!("Ошибка обновления алертов: {:?}", e);
+        AppError::from(e)
+    })?;
+    uow.commit().await.map_err(|e| {
+        error!("Ошибка фиксации транзакции телеметрии: {:?}", e);
+        AppError::from(e)
+    })?;
+
+    Ok(inserted)
+}
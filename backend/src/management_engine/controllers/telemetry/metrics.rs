@@ -0,0 +1,93 @@
+use crate::management_engine::clients::clients::metrics::metrics::PgMetricClient;
+use crate::management_engine::clients::traits::metrics::MetricClient;
+use crate::management_engine::controllers::errors::AppError;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use telemetry_protocol::{MetricRef, is_valid_metric_name};
+use tracing::{error, info};
+
+/// METRIC_AUTO_REGISTER=true — неизвестные имена метрик регистрируются
+/// в metric_types при приёме телеметрии (по умолчанию false).
+pub fn metric_auto_register() -> bool {
+    env::var("METRIC_AUTO_REGISTER")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(false)
+}
+
+/// Сопоставляет `MetricRef` с id из metric_types. Результаты кэшируются
+/// на время жизни объекта (одна пачка событий).
+pub struct MetricResolver {
+    client: PgMetricClient,
+    auto_register: bool,
+    by_name: HashMap<String, i32>,
+    known_ids: HashSet<i32>,
+}
+
+impl MetricResolver {
+    pub fn new(pool: &PgPool) -> Self {
+        MetricResolver {
+            client: PgMetricClient { pool: pool.clone() },
+            auto_register: metric_auto_register(),
+            by_name: HashMap::new(),
+            known_ids: HashSet::new(),
+        }
+    }
+
+    pub async fn resolve(&mut self, metric: &MetricRef) -> Result<i32, AppError> {
+        match metric {
+            MetricRef::Id { metric_type_id } => self.resolve_id(*metric_type_id).await,
+            MetricRef::Name { metric_name } => self.resolve_name(metric_name).await,
+        }
+    }
+
+    async fn resolve_id(&mut self, id: i32) -> Result<i32, AppError> {
+        if self.known_ids.contains(&id) {
+            return Ok(id);
+        }
+        match self.client.metric_type_exists(id).await {
+            Ok(true) => {
+                self.known_ids.insert(id);
+                Ok(id)
+            }
+            Ok(false) => Err(AppError::UnknownMetric(format!("#{}", id))),
+            Err(e) => {
+                error!("Ошибка поиска метрики #{}: {:?}", id, e);
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn resolve_name(&mut self, raw: &str) -> Result<i32, AppError> {
+        let name = raw.trim();
+        if let Some(id) = self.by_name.get(name) {
+            return Ok(*id);
+        }
+
+        let id = match self.client.get_metric_type_id(name).await {
+            Ok(Some(id)) => id,
+            Ok(None) if self.auto_register && is_valid_metric_name(name) => {
+                match self.client.register_metric_type(name, None).await {
+                    Ok(id) => {
+                        info!("Метрика '{}' зарегистрирована автоматически, id={}", name, id);
+                        id
+                    }
+                    Err(e) => {
+                        error!("Ошибка регистрации метрики '{}': {:?}", name, e);
+                        return Err(e.into());
+                    }
+                }
+            }
+            Ok(None) => return Err(AppError::UnknownMetric(name.to_string())),
+            Err(e) => {
+                error!("Ошибка поиска метрики '{}': {:?}", name, e);
+                return Err(e.into());
+            }
+        };
+
+        self.by_name.insert(name.to_string(), id);
+        self.known_ids.insert(id);
+        Ok(id)
+    }
+}
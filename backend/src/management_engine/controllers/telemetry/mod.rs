@@ -0,0 +1,8 @@
+pub mod aggregate;
+pub mod anomaly;
+pub mod health;
+pub mod ingest;
+pub mod metrics;
+pub mod query;
+pub mod rollups;
+pub mod thresholds;
@@ -0,0 +1,98 @@
+use crate::management_engine::clients::clients::telemetry::telemetry::PgTelemetryClient;
+use crate::management_engine::clients::traits::telemetry::{TelemetryClient, TelemetryFilter};
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::models::telemetry::telemetry::{TelemetryPage, TelemetryQuery};
+use actix_web::web;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, NaiveDateTime};
+use tracing::error;
+
+const DEFAULT_PAGE_SIZE: i64 = 100;
+const MAX_PAGE_SIZE: i64 = 1000;
+
+// =========================================================
+// КУРСОР СТРАНИЦЫ
+// =========================================================
+//
+// Курсор — base64url от "<recorded_at в мкс>:<id>" последней записи страницы.
+// Новые записи не сдвигают уже выданные страницы, в отличие от OFFSET.
+
+fn encode_cursor(recorded_at: NaiveDateTime, id: i32) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}:{}", recorded_at.and_utc().timestamp_micros(), id))
+}
+
+fn decode_cursor(cursor: &str) -> Option<(NaiveDateTime, i32)> {
+    let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+    let (micros, id) = raw.split_once(':')?;
+    let recorded_at = DateTime::from_timestamp_micros(micros.parse().ok()?)?.naive_utc();
+    Some((recorded_at, id.parse().ok()?))
+}
+
+/// Строковый фильтр без пробелов по краям; пустой — без ограничения.
+pub fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Страница телеметрии по фильтрам запроса, новые записи первыми.
+pub async fn list_telemetry_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    query: &TelemetryQuery,
+) -> Result<TelemetryPage, AppError> {
+    let client = PgTelemetryClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let mut errors = Vec::new();
+    let after = match query.cursor.as_deref() {
+        None => None,
+        Some(cursor) => {
+            let decoded = decode_cursor(cursor);
+            if decoded.is_none() {
+                errors.push(FieldError::new("cursor", "invalid_cursor", "Некорректный курсор страницы"));
+            }
+            decoded
+        }
+    };
+    if let (Some(from), Some(to)) = (query.from, query.to)
+        && from >= to
+    {
+        errors.push(FieldError::new("to", "invalid_range", "Значение to должно быть позже from"));
+    }
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let filter = TelemetryFilter {
+        from: query.from.map(|t| t.naive_utc()),
+        to: query.to.map(|t| t.naive_utc()),
+        device_id: query.device_id,
+        device_name: non_empty(&query.device_name),
+        metric_type_id: query.metric_type_id,
+        metric_name: non_empty(&query.metric_name),
+        anomaly_only: query.anomaly_only.unwrap_or(false),
+        location: non_empty(&query.location),
+        search: non_empty(&query.search),
+        after,
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    // Лишняя запись показывает, есть ли следующая страница
+    let mut items = client.list_telemetry(&filter, limit + 1).await.map_err(|e| {
+        error!("Ошибка при получении телеметрии: {:?}", e);
+        AppError::from(e)
+    })?;
+
+    let next_cursor = if items.len() as i64 > limit {
+        items.truncate(limit as usize);
+        items.last().map(|r| encode_cursor(r.recorded_at, r.id))
+    } else {
+        None
+    };
+
+    Ok(TelemetryPage { items, next_cursor })
+}
@@ -0,0 +1,180 @@
+use crate::management_engine::clients::clients::rollups::rollups::PgRollupClient;
+use crate::management_engine::clients::traits::rollups::RollupClient;
+use crate::management_engine::controllers::errors::AppError;
+use crate::management_engine::controllers::telemetry::ingest::ingest_config;
+use crate::management_engine::models::telemetry::telemetry::RollupLevel;
+use actix_web::web;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use std::env;
+use std::sync::OnceLock;
+use tracing::{error, info, warn};
+
+static ROLLUP_CONFIG: OnceLock<RollupConfig> = OnceLock::new();
+
+/// Сколько сырых строк удаляется за один запрос.
+const DELETE_BATCH_SIZE: i64 = 10_000;
+
+// =========================================================
+// АГРЕГАТЫ И СРОК ХРАНЕНИЯ ТЕЛЕМЕТРИИ
+// =========================================================
+//
+// ROLLUP_INTERVAL_SECS          период фоновой задачи (60)
+// ROLLUP_LAG_SECS               отставание от текущего времени, чтобы не пропустить
+//                               ещё не зафиксированные пачки (30)
+// TELEMETRY_RAW_RETENTION_DAYS  срок хранения сырых строк, 0 — бессрочно (30)
+// ROLLUP_1M_RETENTION_DAYS      срок хранения минутных агрегатов (14)
+// ROLLUP_1H_RETENTION_DAYS      срок хранения часовых агрегатов (365)
+// ROLLUP_1D_RETENTION_DAYS      срок хранения дневных агрегатов, 0 — бессрочно (0)
+//
+// Задача пересчитывает корзины, в которые попали строки, принятые после
+// прошлого запуска, поэтому опоздавшие события тоже учитываются. Сырые
+// строки удаляются только после того, как учтены во всех уровнях.
+// Корзины уровня старше его срока хранения запрос агрегатов досчитывает
+// по сырым строкам, пока те не удалены.
+
+#[derive(Debug, Clone)]
+pub struct RollupConfig {
+    pub interval: std::time::Duration,
+    pub lag: Duration,
+    pub raw_retention: Option<Duration>,
+    pub minute_retention: Option<Duration>,
+    pub hour_retention: Option<Duration>,
+    pub day_retention: Option<Duration>,
+}
+
+impl RollupConfig {
+    pub fn from_env() -> Self {
+        fn var(name: &str, default: i64) -> i64 {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+        fn days(name: &str, default: i64) -> Option<Duration> {
+            let days = var(name, default);
+            (days > 0).then(|| Duration::days(days))
+        }
+
+        RollupConfig {
+            interval: std::time::Duration::from_secs(var("ROLLUP_INTERVAL_SECS", 60).max(1) as u64),
+            lag: Duration::seconds(var("ROLLUP_LAG_SECS", 30)),
+            raw_retention: days("TELEMETRY_RAW_RETENTION_DAYS", 30),
+            minute_retention: days("ROLLUP_1M_RETENTION_DAYS", 14),
+            hour_retention: days("ROLLUP_1H_RETENTION_DAYS", 365),
+            day_retention: days("ROLLUP_1D_RETENTION_DAYS", 0),
+        }
+    }
+
+    pub fn retention(&self, level: RollupLevel) -> Option<Duration> {
+        match level {
+            RollupLevel::Minute => self.minute_retention,
+            RollupLevel::Hour => self.hour_retention,
+            RollupLevel::Day => self.day_retention,
+        }
+    }
+}
+
+pub fn rollup_config() -> &'static RollupConfig {
+    ROLLUP_CONFIG.get_or_init(RollupConfig::from_env)
+}
+
+/// Один проход: обновляет агрегаты всех уровней и удаляет устаревшие строки.
+pub async fn run_rollup_once(pool: &PgPool) -> Result<(), AppError> {
+    let config = rollup_config();
+    let client = PgRollupClient { pool: pool.clone() };
+    let until = Utc::now().naive_utc() - config.lag;
+
+    let mut uow = client.begin().await.map_err(|e| {
+        error!("Ошибка начала транзакции агрегатов: {:?}", e);
+        AppError::from(e)
+    })?;
+    if !uow.try_lock().await? {
+        info!("Агрегаты телеметрии обновляет другой экземпляр, проход пропущен");
+        return Ok(());
+    }
+
+    for level in RollupLevel::ALL {
+        let from = uow
+            .get_watermark(level)
+            .await?
+            .unwrap_or(DateTime::UNIX_EPOCH.naive_utc());
+        if from >= until {
+            continue;
+        }
+        let buckets = uow.rollup(level, from, until).await.map_err(|e| {
+            error!("Ошибка обновления агрегатов {}: {:?}", level.name(), e);
+            AppError::from(e)
+        })?;
+        uow.set_watermark(level, until).await?;
+        if buckets > 0 {
+            info!("Агрегаты {}: пересчитано корзин {}", level.name(), buckets);
+        }
+    }
+    uow.commit().await.map_err(|e| {
+        error!("Ошибка фиксации агрегатов: {:?}", e);
+        AppError::from(e)
+    })?;
+
+    let now = Utc::now().naive_utc();
+    if let Some(retention) = config.raw_retention {
+        let mut deleted = 0;
+        loop {
+            let batch = client
+                .delete_expired_telemetry(now - retention, until, DELETE_BATCH_SIZE)
+                .await
+                .map_err(|e| {
+                    error!("Ошибка удаления устаревшей телеметрии: {:?}", e);
+                    AppError::from(e)
+                })?;
+            deleted += batch;
+            if batch < DELETE_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+        if deleted > 0 {
+            info!("Удалено сырых строк телеметрии по сроку хранения: {}", deleted);
+        }
+    }
+    for level in RollupLevel::ALL {
+        let Some(retention) = config.retention(level) else {
+            continue;
+        };
+        let deleted = client
+            .delete_expired_rollup(level, now - retention)
+            .await
+            .map_err(|e| {
+                error!("Ошибка удаления агрегатов {}: {:?}", level.name(), e);
+                AppError::from(e)
+            })?;
+        if deleted > 0 {
+            info!("Удалено агрегатов {} по сроку хранения: {}", level.name(), deleted);
+        }
+    }
+
+    Ok(())
+}
+
+// ==================== ФОНОВАЯ ЗАДАЧА АГРЕГАТОВ ====================
+pub async fn start_rollup_job(pool: web::Data<PgPool>) {
+    let config = rollup_config();
+
+    // Событие, принятое с максимальным опозданием, должно застать свои сырые
+    // соседние строки, иначе пересчёт корзины потеряет их
+    if let Some(retention) = config.raw_retention
+        && retention < ingest_config().max_past_age + Duration::days(1)
+    {
+        warn!(
+            "TELEMETRY_RAW_RETENTION_DAYS меньше TELEMETRY_MAX_PAST_AGE_SECS + 1 день: \
+             поздние события могут исказить дневные агрегаты"
+        );
+    }
+
+    let mut interval = tokio::time::interval(config.interval);
+    loop {
+        interval.tick().await;
+        if let Err(err) = run_rollup_once(pool.get_ref()).await {
+            error!("Ошибка фоновой задачи агрегатов: {:?}", err);
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use crate::management_engine::clients::clients::thresholds::thresholds::PgThresholdClient;
+use crate::management_engine::clients::traits::thresholds::{ThresholdClient, ThresholdRule};
+use crate::management_engine::models::telemetry::telemetry::Severity;
+use crate::management_engine::models::thresholds::thresholds::Comparison;
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+// =========================================================
+// КЭШ ПОРОГОВ
+// =========================================================
+//
+// THRESHOLD_CACHE_TTL_SECS  как долго пороги берутся из памяти (30)
+//
+// Пороги меняются редко, а нужны для каждого события, поэтому пачка
+// телеметрии не обращается к thresholds, пока кэш свежий. Изменение
+// порогов через API сбрасывает кэш сразу.
+//
+// Для метрики действует порог самой узкой области: устройство, затем
+// расположение, затем глобальный. Верхняя (gte/gt) и нижняя (lte/lt)
+// границы выбираются независимо, итоговый уровень — худший из двух.
+
+/// Уровни одной границы.
+#[derive(Debug, Clone, Copy)]
+pub struct Bound {
+    pub comparison: Comparison,
+    pub warning: Option<f64>,
+    pub critical: Option<f64>,
+}
+
+impl Bound {
+    pub fn severity(&self, value: f64) -> Severity {
+        let reached = |level: Option<f64>| level.is_some_and(|level| self.comparison.reached(value, level));
+        if reached(self.critical) {
+            Severity::Critical
+        } else if reached(self.warning) {
+            Severity::Warning
+        } else {
+            Severity::Ok
+        }
+    }
+}
+
+/// Ключ границы: (metric_type_id, нижняя ли граница).
+type BoundKey = (i32, bool);
+
+/// Все пороги, разложенные по областям.
+#[derive(Debug, Default)]
+pub struct ThresholdSet {
+    global: HashMap<BoundKey, Bound>,
+    by_location: HashMap<BoundKey, HashMap<String, Bound>>,
+    by_device: HashMap<BoundKey, HashMap<i32, Bound>>,
+}
+
+impl ThresholdSet {
+    pub fn from_rules(rules: Vec<ThresholdRule>) -> Self {
+        let mut set = ThresholdSet::default();
+        for rule in rules {
+            let key = (rule.metric_type_id, rule.comparison.is_lower());
+            let bound = Bound {
+                comparison: rule.comparison,
+                warning: rule.warning_level,
+                critical: rule.critical_level,
+            };
+            match (rule.device_id, rule.location) {
+                (Some(device_id), _) => {
+                    set.by_device.entry(key).or_default().insert(device_id, bound);
+                }
+                (None, Some(location)) => {
+                    set.by_location.entry(key).or_default().insert(location, bound);
+                }
+                (None, None) => {
+                    set.global.insert(key, bound);
+                }
+            }
+        }
+        set
+    }
+
+    fn bound(&self, key: BoundKey, device_id: i32, location: Option<&str>) -> Option<&Bound> {
+        self.by_device
+            .get(&key)
+            .and_then(|devices| devices.get(&device_id))
+            .or_else(|| {
+                location.and_then(|location| self.by_location.get(&key).and_then(|locations| locations.get(location)))
+            })
+            .or_else(|| self.global.get(&key))
+    }
+
+    /// Уровень измерения устройства с расположением `location`.
+    pub fn severity(&self, metric_type_id: i32, device_id: i32, location: Option<&str>, value: f64) -> Severity {
+        [false, true]
+            .into_iter()
+            .filter_map(|lower| self.bound((metric_type_id, lower), device_id, location))
+            .map(|bound| bound.severity(value))
+            .max()
+            .unwrap_or(Severity::Ok)
+    }
+}
+
+type CachedSet = Option<(Instant, Arc<ThresholdSet>)>;
+
+static THRESHOLD_CACHE: OnceLock<RwLock<CachedSet>> = OnceLock::new();
+
+fn cache() -> &'static RwLock<CachedSet> {
+    THRESHOLD_CACHE.get_or_init(|| RwLock::new(None))
+}
+
+fn cache_ttl() -> Duration {
+    Duration::from_secs(
+        env::var("THRESHOLD_CACHE_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30),
+    )
+}
+
+/// Пороги из кэша или из БД, если кэш устарел.
+pub async fn threshold_set(client: &PgThresholdClient) -> Result<Arc<ThresholdSet>, sqlx::Error> {
+    if let Some((loaded_at, set)) = cache().read().expect("threshold cache poisoned").as_ref()
+        && loaded_at.elapsed() < cache_ttl()
+    {
+        return Ok(set.clone());
+    }
+
+    let rules = client.get_threshold_rules().await?;
+    info!("Кэш порогов обновлён: {} порогов", rules.len());
+    let set = Arc::new(ThresholdSet::from_rules(rules));
+    *cache().write().expect("threshold cache poisoned") = Some((Instant::now(), set.clone()));
+    Ok(set)
+}
+
+/// Следующая пачка телеметрии перечитает пороги из БД.
+pub fn invalidate_threshold_cache() {
+    *cache().write().expect("threshold cache poisoned") = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CPU: i32 = 1;
+    const DISK_FREE: i32 = 2;
+
+    fn rule(
+        metric_type_id: i32,
+        comparison: Comparison,
+        warning: Option<f64>,
+        critical: Option<f64>,
+    ) -> ThresholdRule {
+        ThresholdRule {
+            metric_type_id,
+            device_id: None,
+            location: None,
+            comparison,
+            warning_level: warning,
+            critical_level: critical,
+        }
+    }
+
+    #[test]
+    fn upper_bound_reaches_levels_inclusively() {
+        let set = ThresholdSet::from_rules(vec![rule(CPU, Comparison::Gte, Some(70.0), Some(90.0))]);
+
+        assert_eq!(set.severity(CPU, 1, None, 69.99), Severity::Ok);
+        assert_eq!(set.severity(CPU, 1, None, 70.0), Severity::Warning);
+        assert_eq!(set.severity(CPU, 1, None, 89.99), Severity::Warning);
+        assert_eq!(set.severity(CPU, 1, None, 90.0), Severity::Critical);
+    }
+
+    #[test]
+    fn strict_upper_bound_excludes_level() {
+        let set = ThresholdSet::from_rules(vec![rule(CPU, Comparison::Gt, Some(70.0), Some(90.0))]);
+
+        assert_eq!(set.severity(CPU, 1, None, 70.0), Severity::Ok);
+        assert_eq!(set.severity(CPU, 1, None, 90.0), Severity::Warning);
+        assert_eq!(set.severity(CPU, 1, None, 90.01), Severity::Critical);
+    }
+
+    #[test]
+    fn lower_bound_fires_below_levels() {
+        let set = ThresholdSet::from_rules(vec![rule(DISK_FREE, Comparison::Lte, Some(20.0), Some(5.0))]);
+
+        assert_eq!(set.severity(DISK_FREE, 1, None, 20.01), Severity::Ok);
+        assert_eq!(set.severity(DISK_FREE, 1, None, 20.0), Severity::Warning);
+        assert_eq!(set.severity(DISK_FREE, 1, None, 5.0), Severity::Critical);
+        assert_eq!(set.severity(DISK_FREE, 1, None, 0.0), Severity::Critical);
+    }
+
+    #[test]
+    fn strict_lower_bound_excludes_level() {
+        let set = ThresholdSet::from_rules(vec![rule(DISK_FREE, Comparison::Lt, Some(20.0), Some(5.0))]);
+
+        assert_eq!(set.severity(DISK_FREE, 1, None, 20.0), Severity::Ok);
+        assert_eq!(set.severity(DISK_FREE, 1, None, 5.0), Severity::Warning);
+        assert_eq!(set.severity(DISK_FREE, 1, None, 4.99), Severity::Critical);
+    }
+
+    #[test]
+    fn worst_of_upper_and_lower_bounds() {
+        let set = ThresholdSet::from_rules(vec![
+            rule(CPU, Comparison::Gte, Some(70.0), Some(90.0)),
+            rule(CPU, Comparison::Lte, Some(10.0), None),
+        ]);
+
+        assert_eq!(set.severity(CPU, 1, None, 50.0), Severity::Ok);
+        assert_eq!(set.severity(CPU, 1, None, 95.0), Severity::Critical);
+        assert_eq!(set.severity(CPU, 1, None, 5.0), Severity::Warning);
+    }
+
+    #[test]
+    fn missing_levels_and_metrics_are_ok() {
+        let set = ThresholdSet::from_rules(vec![rule(CPU, Comparison::Gte, None, Some(90.0))]);
+
+        assert_eq!(set.severity(CPU, 1, None, 89.0), Severity::Ok);
+        assert_eq!(set.severity(CPU, 1, None, 90.0), Severity::Critical);
+        assert_eq!(set.severity(DISK_FREE, 1, None, 0.0), Severity::Ok);
+    }
+
+    #[test]
+    fn narrowest_scope_wins() {
+        let set = ThresholdSet::from_rules(vec![
+            rule(CPU, Comparison::Gte, Some(70.0), Some(90.0)),
+            ThresholdRule {
+                location: Some("Москва".to_string()),
+                ..rule(CPU, Comparison::Gte, Some(80.0), Some(95.0))
+            },
+            ThresholdRule {
+                device_id: Some(7),
+                ..rule(CPU, Comparison::Gte, Some(50.0), Some(60.0))
+            },
+        ]);
+
+        assert_eq!(set.severity(CPU, 1, None, 75.0), Severity::Warning);
+        assert_eq!(set.severity(CPU, 1, Some("Москва"), 75.0), Severity::Ok);
+        assert_eq!(set.severity(CPU, 1, Some("Казань"), 75.0), Severity::Warning);
+        assert_eq!(set.severity(CPU, 7, Some("Москва"), 75.0), Severity::Critical);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::management_engine::clients::clients::detectors::detectors::PgDetectorClient;
+use crate::management_engine::clients::clients::metrics::metrics::PgMetricClient;
+use crate::management_engine::clients::traits::detectors::{DetectorClient, DetectorRule};
+use crate::management_engine::clients::traits::metrics::MetricClient;
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::models::thresholds::detectors::{
+    DetectorConfig, DetectorKind, UpdateDetectorsRequest,
+};
+use actix_web::web;
+use std::collections::HashSet;
+use tracing::{error, info};
+
+const DEFAULT_WINDOW_SIZE: i32 = 60;
+const MAX_WINDOW_SIZE: i32 = 1000;
+
+pub async fn list_detectors_logic(
+    pool: &web::Data<sqlx::PgPool>,
+) -> Result<Vec<DetectorConfig>, AppError> {
+    let client = PgDetectorClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    client.list_detectors().await.map_err(|e| {
+        error!("Ошибка получения детекторов аномалий: {:?}", e);
+        AppError::from(e)
+    })
+}
+
+pub async fn set_metric_detectors_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    metric_name: &str,
+    req: &UpdateDetectorsRequest,
+    username: &str,
+) -> Result<Vec<DetectorConfig>, AppError> {
+    let client = PgDetectorClient {
+        pool: pool.get_ref().clone(),
+    };
+    let metric_client = PgMetricClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let metric_type_id = match metric_client.get_metric_type_id(metric_name).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Err(AppError::UnknownMetric(metric_name.to_string())),
+        Err(e) => {
+            error!("Ошибка поиска метрики '{}': {:?}", metric_name, e);
+            return Err(e.into());
+        }
+    };
+    let rules = validate_detectors(metric_type_id, req).map_err(AppError::Validation)?;
+
+    if let Err(e) = client.set_metric_detectors(metric_type_id, &rules).await {
+        error!("Ошибка изменения детекторов метрики '{}': {:?}", metric_name, e);
+        return Err(e.into());
+    }
+
+    info!(
+        "Пользователь {} задал детекторы метрики '{}': {:?}",
+        username, metric_name, rules
+    );
+    client.get_metric_detectors(metric_type_id).await.map_err(|e| {
+        error!("Ошибка получения детекторов метрики '{}': {:?}", metric_name, e);
+        AppError::from(e)
+    })
+}
+
+fn validate_detectors(
+    metric_type_id: i32,
+    req: &UpdateDetectorsRequest,
+) -> Result<Vec<DetectorRule>, Vec<FieldError>> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    let mut rules = Vec::with_capacity(req.detectors.len());
+
+    for settings in &req.detectors {
+        let name = settings.detector.as_str();
+        if !seen.insert(settings.detector) {
+            errors.push(FieldError::new(
+                "detectors",
+                "duplicate_detector",
+                format!("Детектор {} указан несколько раз", name),
+            ));
+        }
+
+        let window_size = settings.window_size.unwrap_or(DEFAULT_WINDOW_SIZE);
+        if !(2..=MAX_WINDOW_SIZE).contains(&window_size) {
+            errors.push(FieldError::new(
+                "window_size",
+                "out_of_range",
+                format!("{}: window_size должен быть от 2 до {}", name, MAX_WINDOW_SIZE),
+            ));
+        }
+        if !settings.threshold.is_finite() || settings.threshold <= 0.0 {
+            errors.push(FieldError::new(
+                "threshold",
+                "out_of_range",
+                format!("{}: threshold должен быть положительным числом", name),
+            ));
+        }
+        match (settings.detector, settings.alpha) {
+            (DetectorKind::Ewma, Some(alpha)) if !(alpha > 0.0 && alpha <= 1.0) => {
+                errors.push(FieldError::new(
+                    "alpha",
+                    "out_of_range",
+                    "ewma: alpha должен быть в интервале (0, 1]",
+                ));
+            }
+            (DetectorKind::Ewma, _) | (_, None) => {}
+            (_, Some(_)) => {
+                errors.push(FieldError::new(
+                    "alpha",
+                    "not_applicable",
+                    format!("{}: alpha задаётся только для ewma", name),
+                ));
+            }
+        }
+
+        rules.push(DetectorRule {
+            metric_type_id,
+            detector: settings.detector,
+            window_size,
+            threshold: settings.threshold,
+            alpha: settings.alpha,
+        });
+    }
+
+    if errors.is_empty() { Ok(rules) } else { Err(errors) }
+}
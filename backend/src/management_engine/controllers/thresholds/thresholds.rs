@@ -0,0 +1,222 @@
+use crate::management_engine::clients::clients::metrics::metrics::PgMetricClient;
+use crate::management_engine::clients::clients::thresholds::thresholds::PgThresholdClient;
+use crate::management_engine::clients::traits::metrics::MetricClient;
+use crate::management_engine::clients::traits::thresholds::{
+    ThresholdClient, ThresholdFilter, ThresholdRow,
+};
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::controllers::telemetry::query::non_empty;
+use crate::management_engine::controllers::telemetry::thresholds::invalidate_threshold_cache;
+use crate::management_engine::models::thresholds::thresholds::{
+    ThresholdQuery, ThresholdRequest, ThresholdView,
+};
+use actix_web::web;
+use telemetry_protocol::{MAX_METRIC_VALUE_ABS, is_valid_metric_value};
+use tracing::{error, info};
+
+const MAX_LOCATION_LEN: usize = 150;
+
+pub async fn list_thresholds_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    query: &ThresholdQuery,
+) -> Result<Vec<ThresholdView>, AppError> {
+    let client = PgThresholdClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let filter = ThresholdFilter {
+        metric_name: non_empty(&query.metric_name),
+        device_name: non_empty(&query.device_name),
+        location: non_empty(&query.location),
+    };
+    client.list_thresholds(&filter).await.map_err(|e| {
+        error!("Ошибка получения списка порогов: {:?}", e);
+        AppError::from(e)
+    })
+}
+
+pub async fn create_threshold_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    req: &ThresholdRequest,
+    username: &str,
+) -> Result<ThresholdView, AppError> {
+    let client = PgThresholdClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let row = validate_threshold(pool, &client, req).await?;
+    let id = match client.create_threshold(&row, username).await {
+        Ok(id) => id,
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return Err(AppError::ThresholdExists);
+        }
+        Err(e) => {
+            error!("Ошибка создания порога: {:?}", e);
+            return Err(e.into());
+        }
+    };
+    invalidate_threshold_cache();
+
+    info!("Пользователь {} создал порог #{}: {:?}", username, id, row);
+    fetch_threshold(&client, id).await
+}
+
+pub async fn update_threshold_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    id: i32,
+    req: &ThresholdRequest,
+    username: &str,
+) -> Result<ThresholdView, AppError> {
+    let client = PgThresholdClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let row = validate_threshold(pool, &client, req).await?;
+    match client.update_threshold(id, &row).await {
+        Ok(true) => {}
+        Ok(false) => return Err(AppError::ThresholdNotFound(id)),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            return Err(AppError::ThresholdExists);
+        }
+        Err(e) => {
+            error!("Ошибка изменения порога #{}: {:?}", id, e);
+            return Err(e.into());
+        }
+    }
+    invalidate_threshold_cache();
+
+    info!("Пользователь {} изменил порог #{}: {:?}", username, id, row);
+    fetch_threshold(&client, id).await
+}
+
+pub async fn delete_threshold_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    id: i32,
+    username: &str,
+) -> Result<(), AppError> {
+    let client = PgThresholdClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    match client.delete_threshold(id).await {
+        Ok(true) => {
+            invalidate_threshold_cache();
+            info!("Пользователь {} удалил порог #{}", username, id);
+            Ok(())
+        }
+        Ok(false) => Err(AppError::ThresholdNotFound(id)),
+        Err(e) => {
+            error!("Ошибка удаления порога #{}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+async fn fetch_threshold(client: &PgThresholdClient, id: i32) -> Result<ThresholdView, AppError> {
+    match client.get_threshold(id).await {
+        Ok(Some(threshold)) => Ok(threshold),
+        Ok(None) => Err(AppError::ThresholdNotFound(id)),
+        Err(e) => {
+            error!("Ошибка получения порога #{}: {:?}", id, e);
+            Err(e.into())
+        }
+    }
+}
+
+/// Проверяет тело запроса и сопоставляет метрику и устройство с их id.
+async fn validate_threshold(
+    pool: &web::Data<sqlx::PgPool>,
+    client: &PgThresholdClient,
+    req: &ThresholdRequest,
+) -> Result<ThresholdRow, AppError> {
+    let metric_name = req.metric_name.trim();
+    let device_name = non_empty(&req.device_name);
+    let location = non_empty(&req.location);
+    let lower = req.comparison.is_lower();
+
+    let mut errors = Vec::new();
+    if metric_name.is_empty() {
+        errors.push(FieldError::new("metric_name", "required", "Поле metric_name обязательно"));
+    }
+    if device_name.is_some() && location.is_some() {
+        errors.push(FieldError::new(
+            "location",
+            "conflicting_scope",
+            "Порог задаётся либо для устройства, либо для расположения",
+        ));
+    }
+    if location.as_ref().is_some_and(|l| l.chars().count() > MAX_LOCATION_LEN) {
+        errors.push(FieldError::new(
+            "location",
+            "too_long",
+            format!("Поле location не должно превышать {} символов", MAX_LOCATION_LEN),
+        ));
+    }
+    if req.warning_level.is_none() && req.critical_level.is_none() {
+        errors.push(FieldError::new(
+            "critical_level",
+            "required",
+            "Нужен хотя бы один из уровней warning_level или critical_level",
+        ));
+    }
+    for (field, level) in [("warning_level", req.warning_level), ("critical_level", req.critical_level)] {
+        if level.is_some_and(|v| !is_valid_metric_value(v)) {
+            errors.push(FieldError::new(
+                field,
+                "out_of_range",
+                format!("Поле {} должно быть по модулю меньше {}", field, MAX_METRIC_VALUE_ABS),
+            ));
+        }
+    }
+    if let (Some(warning), Some(critical)) = (req.warning_level, req.critical_level) {
+        if !lower && warning > critical {
+            errors.push(FieldError::new(
+                "warning_level",
+                "invalid_order",
+                "Для верхней границы warning_level не может быть больше critical_level",
+            ));
+        } else if lower && warning < critical {
+            errors.push(FieldError::new(
+                "warning_level",
+                "invalid_order",
+                "Для нижней границы warning_level не может быть меньше critical_level",
+            ));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let metric_client = PgMetricClient {
+        pool: pool.get_ref().clone(),
+    };
+    let metric_type_id = match metric_client.get_metric_type_id(metric_name).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Err(AppError::UnknownMetric(metric_name.to_string())),
+        Err(e) => {
+            error!("Ошибка поиска метрики '{}': {:?}", metric_name, e);
+            return Err(e.into());
+        }
+    };
+
+    let device_id = match &device_name {
+        None => None,
+        Some(name) => match client.get_device_id(name).await {
+            Ok(Some(id)) => Some(id),
+            Ok(None) => return Err(AppError::DeviceNotFound(name.clone())),
+            Err(e) => {
+                error!("Ошибка поиска устройства '{}': {:?}", name, e);
+                return Err(e.into());
+            }
+        },
+    };
+
+    Ok(ThresholdRow {
+        metric_type_id,
+        device_id,
+        location,
+        comparison: req.comparison,
+        warning_level: req.warning_level,
+        critical_level: req.critical_level,
+    })
+}
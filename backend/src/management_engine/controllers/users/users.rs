@@ -0,0 +1,286 @@
+use crate::management_engine::clients::clients::auth::auth::PgAuthClient;
+use crate::management_engine::clients::clients::users::users::PgUserClient;
+use crate::management_engine::clients::traits::auth::AuthClient;
+use crate::management_engine::clients::traits::users::UserClient;
+use crate::management_engine::controllers::auth::validation::{
+    password_policy, validate_profile_update,
+};
+use crate::management_engine::controllers::errors::{AppError, FieldError};
+use crate::management_engine::models::users::users::{
+    ChangePasswordRequest, UpdateProfileRequest, UserListQuery, UserListResponse, UserProfile,
+};
+use actix_web::web;
+use bcrypt::{hash, verify};
+use tracing::{error, info};
+
+// Действия в таблице logs
+pub const LOG_PASSWORD_CHANGED: &str = "password_changed";
+pub const LOG_ACCOUNT_DISABLED: &str = "account_disabled";
+pub const LOG_ACCOUNT_ENABLED: &str = "account_enabled";
+pub const LOG_ACCOUNT_DELETED: &str = "account_deleted";
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+// =========================================================
+// ПРОФИЛЬ ТЕКУЩЕГО ПОЛЬЗОВАТЕЛЯ
+// =========================================================
+
+pub async fn get_profile_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+) -> Result<UserProfile, AppError> {
+    let client = PgUserClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    match client.get_profile(username).await {
+        Ok(Some(profile)) => Ok(profile),
+        Ok(None) => Err(AppError::UserNotFound(username.to_string())),
+        Err(e) => {
+            error!("Ошибка получения профиля {}: {:?}", username, e);
+            Err(e.into())
+        }
+    }
+}
+
+pub async fn update_profile_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    req: &UpdateProfileRequest,
+) -> Result<UserProfile, AppError> {
+    let client = PgUserClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let update = match validate_profile_update(req) {
+        Ok(update) => update,
+        Err(errors) => {
+            info!("Ошибки валидации профиля {}: {:?}", username, errors);
+            return Err(AppError::Validation(errors));
+        }
+    };
+
+    if let Err(e) = client
+        .update_profile(
+            username,
+            update.full_name.as_deref(),
+            update.email.as_deref(),
+            update.phone_number.as_ref().map(Option::as_deref),
+            update.organization.as_ref().map(Option::as_deref),
+        )
+        .await
+    {
+        error!("Ошибка изменения профиля {}: {:?}", username, e);
+        return Err(e.into());
+    }
+
+    info!("Пользователь {} изменил профиль", username);
+    get_profile_logic(pool, username).await
+}
+
+/// Меняет пароль после проверки старого и завершает все сессии пользователя.
+pub async fn change_password_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    req: &ChangePasswordRequest,
+) -> Result<(), AppError> {
+    let auth_client = PgAuthClient {
+        pool: pool.get_ref().clone(),
+    };
+    let client = PgUserClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let (user_id, hashed) = match auth_client.get_user_details(username).await {
+        Ok(Some((id, hashed, _, _))) => (id, hashed),
+        Ok(None) => return Err(AppError::UserNotFound(username.to_string())),
+        Err(e) => {
+            error!("Ошибка обращения к базе данных: {:?}", e);
+            return Err(e.into());
+        }
+    };
+
+    match verify(&req.old_password, &hashed) {
+        Ok(true) => {}
+        Ok(false) => {
+            info!("Неверный текущий пароль при смене пароля {}", username);
+            return Err(AppError::Validation(vec![FieldError::new(
+                "old_password",
+                "mismatch",
+                "Неверный текущий пароль",
+            )]));
+        }
+        Err(e) => {
+            error!("Ошибка проверки пароля: {:?}", e);
+            return Err(AppError::Internal(e.to_string()));
+        }
+    }
+
+    let mut errors = password_policy().check("new_password", &req.new_password);
+    if req.new_password == req.old_password {
+        errors.push(FieldError::new(
+            "new_password",
+            "same_as_old",
+            "Новый пароль должен отличаться от текущего",
+        ));
+    }
+    if !errors.is_empty() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let new_hash = hash(&req.new_password, 12).map_err(|e| {
+        error!("Ошибка хэширования пароля: {:?}", e);
+        AppError::Internal(e.to_string())
+    })?;
+
+    if let Err(e) = client.update_password_hash(user_id, &new_hash).await {
+        error!("Ошибка сохранения пароля {}: {:?}", username, e);
+        return Err(e.into());
+    }
+
+    let revoked = client.revoke_user_refresh_tokens(user_id).await.map_err(|e| {
+        error!("Ошибка отзыва refresh-токенов {}: {:?}", username, e);
+        AppError::from(e)
+    })?;
+
+    if let Err(e) = auth_client
+        .log_auth_event(Some(user_id), LOG_PASSWORD_CHANGED, None, username, None)
+        .await
+    {
+        error!("Ошибка записи смены пароля в logs: {:?}", e);
+    }
+
+    info!("Пользователь {} сменил пароль, отозвано {} refresh-токенов", username, revoked);
+    Ok(())
+}
+
+// =========================================================
+// УПРАВЛЕНИЕ ПОЛЬЗОВАТЕЛЯМИ (АДМИНИСТРАТОР)
+// =========================================================
+
+pub async fn list_users_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    query: &UserListQuery,
+) -> Result<UserListResponse, AppError> {
+    let client = PgUserClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    let search = query
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let items = client.list_users(search, limit, offset).await.map_err(|e| {
+        error!("Ошибка получения списка пользователей: {:?}", e);
+        AppError::from(e)
+    })?;
+    let total = client.count_users(search).await.map_err(|e| {
+        error!("Ошибка подсчёта пользователей: {:?}", e);
+        AppError::from(e)
+    })?;
+
+    Ok(UserListResponse { items, total })
+}
+
+/// Отключает или включает учётную запись. При отключении отзываются
+/// все refresh-токены пользователя.
+pub async fn set_user_disabled_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    disabled: bool,
+    admin: &str,
+) -> Result<(), AppError> {
+    let auth_client = PgAuthClient {
+        pool: pool.get_ref().clone(),
+    };
+    let client = PgUserClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    if disabled {
+        ensure_not_self(username, admin)?;
+    }
+
+    let user_id = match client.set_user_disabled(username, disabled).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return Err(AppError::UserNotFound(username.to_string())),
+        Err(e) => {
+            error!("Ошибка изменения статуса пользователя {}: {:?}", username, e);
+            return Err(e.into());
+        }
+    };
+
+    if disabled
+        && let Err(e) = client.revoke_user_refresh_tokens(user_id).await
+    {
+        error!("Ошибка отзыва refresh-токенов {}: {:?}", username, e);
+        return Err(e.into());
+    }
+
+    let (action, details) = if disabled {
+        (LOG_ACCOUNT_DISABLED, format!("Отключён администратором {}", admin))
+    } else {
+        (LOG_ACCOUNT_ENABLED, format!("Включён администратором {}", admin))
+    };
+    if let Err(e) = auth_client
+        .log_auth_event(Some(user_id), action, Some(&details), username, None)
+        .await
+    {
+        error!("Ошибка записи изменения статуса в logs: {:?}", e);
+    }
+
+    info!("Пользователь {}: {} ({})", username, action, admin);
+    Ok(())
+}
+
+pub async fn delete_user_logic(
+    pool: &web::Data<sqlx::PgPool>,
+    username: &str,
+    admin: &str,
+) -> Result<(), AppError> {
+    let auth_client = PgAuthClient {
+        pool: pool.get_ref().clone(),
+    };
+    let client = PgUserClient {
+        pool: pool.get_ref().clone(),
+    };
+
+    ensure_not_self(username, admin)?;
+
+    match client.delete_user(username).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(AppError::UserNotFound(username.to_string())),
+        Err(e) => {
+            error!("Ошибка удаления пользователя {}: {:?}", username, e);
+            return Err(e.into());
+        }
+    }
+
+    let details = format!("Удалён администратором {}", admin);
+    if let Err(e) = auth_client
+        .log_auth_event(None, LOG_ACCOUNT_DELETED, Some(&details), username, None)
+        .await
+    {
+        error!("Ошибка записи удаления в logs: {:?}", e);
+    }
+
+    info!("Пользователь {} удалён администратором {}", username, admin);
+    Ok(())
+}
+
+/// Администратор не может отключить или удалить собственную учётную запись.
+fn ensure_not_self(username: &str, admin: &str) -> Result<(), AppError> {
+    if username == admin {
+        return Err(AppError::Validation(vec![FieldError::new(
+            "username",
+            "self_action",
+            "Нельзя отключить или удалить собственную учётную запись",
+        )]));
+    }
+    Ok(())
+}
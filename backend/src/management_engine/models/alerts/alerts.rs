@@ -0,0 +1,135 @@
+use crate::management_engine::models::telemetry::telemetry::Severity;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "alert_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStatus {
+    Open,
+    Acknowledged,
+    Resolved,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "alert_event_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEventType {
+    Opened,
+    Acknowledged,
+    Assigned,
+    Commented,
+    Resolved,
+    /// Закрыт после серии нормальных измерений
+    AutoResolved,
+}
+
+impl AlertEventType {
+    /// Заголовок сообщения подписчикам о событии.
+    pub fn notice_title(self) -> &'static str {
+        match self {
+            AlertEventType::Opened => "Новый алерт",
+            AlertEventType::Acknowledged => "Алерт подтверждён",
+            AlertEventType::Assigned => "Алерт назначен",
+            AlertEventType::Commented => "Комментарий к алерту",
+            AlertEventType::Resolved => "Алерт закрыт",
+            AlertEventType::AutoResolved => "Алерт закрыт автоматически",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct AlertView {
+    pub id: i32,
+    pub device_id: i32,
+    #[schema(example = "Router-01")]
+    pub device_name: String,
+    pub metric_type_id: i32,
+    #[schema(example = "cpu_usage")]
+    pub metric_name: String,
+    pub severity: Severity,
+    pub status: AlertStatus,
+    /// Что открыло алерт: детектор или `threshold`
+    pub detector: Option<String>,
+    pub first_value: f64,
+    pub last_value: f64,
+    /// Сколько аномальных измерений учтено в алерте
+    pub sample_count: i32,
+    /// Время измерения, открывшего алерт
+    pub opened_at: NaiveDateTime,
+    /// Время последнего аномального измерения
+    pub last_seen_at: NaiveDateTime,
+    pub acknowledged_by: Option<String>,
+    pub acknowledged_at: Option<NaiveDateTime>,
+    pub assigned_to: Option<String>,
+    /// Пусто у закрытого алерта, если он закрыт автоматически
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct AlertEvent {
+    pub id: i32,
+    pub event_type: AlertEventType,
+    /// Пусто для действий системы
+    pub username: Option<String>,
+    /// Комментарий или имя назначенного пользователя
+    pub details: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Алерт с полной историей действий.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AlertDetail {
+    #[serde(flatten)]
+    pub alert: AlertView,
+    pub history: Vec<AlertEvent>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AlertListResponse {
+    pub items: Vec<AlertView>,
+    /// Всего алертов, подходящих под фильтр
+    pub total: i64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AlertQuery {
+    /// Статус алерта
+    pub status: Option<AlertStatus>,
+    /// Только незакрытые (open и acknowledged)
+    pub active: Option<bool>,
+    /// Уровень алерта
+    pub severity: Option<Severity>,
+    /// Имя устройства
+    pub device_name: Option<String>,
+    /// Имя метрики
+    pub metric_name: Option<String>,
+    /// Имя назначенного пользователя
+    pub assigned_to: Option<String>,
+    /// Размер страницы (1–200, по умолчанию 50)
+    pub limit: Option<i64>,
+    /// Смещение от начала списка
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignAlertRequest {
+    /// Пусто — снять назначение
+    #[schema(example = "operator1")]
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AlertCommentRequest {
+    #[schema(example = "Перезагрузили маршрутизатор")]
+    pub comment: String,
+}
+
+/// Тело закрытия алерта; для закрытия без комментария — `{}`.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResolveAlertRequest {
+    #[schema(example = "Причина устранена")]
+    pub comment: Option<String>,
+}
@@ -1,24 +1,78 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Claims {
     pub sub: String,
     pub role: String,
+    /// Права роли на момент выдачи токена
+    #[serde(default)]
+    pub permissions: Vec<String>,
     pub exp: usize,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
-pub struct AuthRequest {
+pub struct LoginRequest {
+    #[schema(example = "ivanov")]
     pub username: String,
+    #[schema(example = "Passw0rd")]
     pub password: String,
-    pub full_name: Option<String>,
-    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RegisterRequest {
+    #[schema(example = "ivanov")]
+    pub username: String,
+    #[schema(example = "Passw0rd")]
+    pub password: String,
+    #[schema(example = "Иванов Иван Иванович")]
+    pub full_name: String,
+    #[schema(example = "ivanov@example.com")]
+    pub email: String,
+    #[schema(example = "+79991234567")]
     pub phone_number: Option<String>,
+    #[schema(example = "ООО Сеть")]
     pub organization: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct TokenResponse {
+    /// Access-токен (JWT)
+    pub token: String,
+    pub refresh_token: String,
+    /// Время жизни access-токена в секундах
+    pub expires_in: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ForgotPasswordRequest {
+    #[schema(example = "ivanov@example.com")]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResetPasswordRequest {
+    /// Токен из письма
     pub token: String,
+    #[schema(example = "N3wPassw0rd")]
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RefreshToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub username: String,
+    pub role_name: String,
+    pub family_id: String,
+    pub expires_at: NaiveDateTime,
+    pub rotated_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub user_disabled: bool,
 }
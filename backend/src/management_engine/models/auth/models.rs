@@ -1,15 +1,6 @@
 use serde::{Serialize, Deserialize};
 use chrono::{NaiveDateTime};
 
-
-#[derive(Serialize, Clone)]
-struct TelemetryEvent {
-    device_name: String,
-    ip_address: String,
-    location: String,
-    metric_value: f64,
-    action_description: String,
-}
 // =========================================================
 // ENUMS
 // =========================================================
@@ -34,6 +25,7 @@ pub struct User {
     pub role_id: Option<i32>,
     pub user_info_id: Option<i32>,
     pub created_at: NaiveDateTime,
+    pub disabled_at: Option<NaiveDateTime>,
 }
 
 // =========================================================
@@ -123,6 +115,8 @@ pub struct LogEntry {
     pub action: String,
     pub details: Option<String>,
     pub logged_at: NaiveDateTime,
+    pub username: Option<String>,
+    pub ip_address: Option<String>,
 }
 
 // =========================================================
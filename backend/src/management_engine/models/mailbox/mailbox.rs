@@ -0,0 +1,87 @@
+use crate::management_engine::models::telemetry::telemetry::Severity;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct MailboxMessage {
+    pub id: i32,
+    #[schema(example = "Новый алерт: cpu_usage на Router-01")]
+    pub title: String,
+    pub message: String,
+    /// Алерт, о котором сообщение; пусто, если алерт удалён
+    pub alert_id: Option<i32>,
+    pub read_status: bool,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MailboxPage {
+    pub items: Vec<MailboxMessage>,
+    /// Всего сообщений, подходящих под фильтр
+    pub total: i64,
+    /// Непрочитанных сообщений во всём ящике
+    pub unread: i64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MailboxQuery {
+    /// Только непрочитанные
+    pub unread_only: Option<bool>,
+    /// Размер страницы (1–200, по умолчанию 50)
+    pub limit: Option<i64>,
+    /// Смещение от начала списка
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MarkMailboxRequest {
+    #[schema(example = json!([1, 2, 3]))]
+    pub ids: Vec<i32>,
+    /// `true` — прочитано, `false` — непрочитано
+    pub read: bool,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DeleteMailboxRequest {
+    #[schema(example = json!([1, 2, 3]))]
+    pub ids: Vec<i32>,
+}
+
+/// Результат массового изменения ящика.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MailboxChange {
+    /// Сколько сообщений изменено или удалено; чужие, несуществующие и уже
+    /// имеющие нужный статус сообщения не учитываются
+    pub affected: u64,
+    /// Непрочитанных сообщений после изменения
+    pub unread: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct SubscriptionView {
+    pub id: i32,
+    /// Задан для подписки роли
+    #[schema(example = "оператор")]
+    pub role_name: Option<String>,
+    /// Задан для подписки на устройство
+    #[schema(example = "Router-01")]
+    pub device_name: Option<String>,
+    /// Задан для подписки на расположение
+    pub location: Option<String>,
+    /// Минимальный уровень алерта
+    pub min_severity: Severity,
+    pub created_at: NaiveDateTime,
+}
+
+/// Тело создания подписки. Без device_name и location подписка действует
+/// на все устройства; задать можно только одно из них.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubscriptionRequest {
+    #[schema(example = "Router-01")]
+    pub device_name: Option<String>,
+    #[schema(example = "Москва, ЦОД-1")]
+    pub location: Option<String>,
+    /// По умолчанию `warning`
+    pub min_severity: Option<Severity>,
+}
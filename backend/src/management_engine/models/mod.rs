@@ -1 +1,7 @@
+pub mod alerts;
 pub mod auth;
+pub mod mailbox;
+pub mod roles;
+pub mod telemetry;
+pub mod thresholds;
+pub mod users;
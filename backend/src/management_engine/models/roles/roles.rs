@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct RoleWithPermissions {
+    pub id: i32,
+    #[schema(example = "оператор")]
+    pub role_name: String,
+    #[schema(example = json!(["telemetry:read", "telemetry:write"]))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateRoleRequest {
+    #[schema(example = "аналитик")]
+    pub role_name: String,
+    #[schema(example = json!(["telemetry:read"]))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRolePermissionsRequest {
+    #[schema(example = json!(["telemetry:read", "thresholds:read"]))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignRoleRequest {
+    #[schema(example = "администратор")]
+    pub role_name: String,
+}
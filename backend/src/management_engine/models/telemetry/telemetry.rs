@@ -0,0 +1,293 @@
+use crate::management_engine::controllers::errors::FieldError;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Уровень измерения относительно порогов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "telemetry_severity", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Ok => "ok",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Статус устройства (`devices.status`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "device_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceStatus {
+    Active,
+    Warning,
+    /// Выставляется вручную, автоматически не меняется
+    Inactive,
+}
+
+/// Поведение при ошибках в пачке телеметрии.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum IngestMode {
+    /// Корректные события записываются, ошибочные перечисляются в ответе
+    Partial,
+    /// Любая ошибка отклоняет всю пачку
+    Atomic,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct IngestQuery {
+    /// Режим приёма; по умолчанию берётся из TELEMETRY_INGEST_MODE
+    pub mode: Option<IngestMode>,
+}
+
+/// Причина отклонения отдельного события.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct IngestEventError {
+    /// Индекс события в теле запроса
+    pub index: usize,
+    /// validation_error | unknown_metric | database_error
+    #[schema(example = "unknown_metric")]
+    pub code: &'static str,
+    pub message: String,
+    /// Ошибки по полям (только для validation_error)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
+
+/// Итог приёма пачки телеметрии.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IngestResponse {
+    pub mode: IngestMode,
+    /// Записано событий
+    pub accepted: usize,
+    /// Событий с уже записанным event_id
+    pub duplicates: usize,
+    /// Отклонено событий
+    pub rejected: usize,
+    pub errors: Vec<IngestEventError>,
+    /// Индексы событий-дубликатов в теле запроса
+    pub duplicate_indexes: Vec<usize>,
+}
+
+/// Запись телеметрии (`GET /operator/telemetry`).
+#[derive(Debug, Serialize, ToSchema, sqlx::FromRow)]
+pub struct TelemetryRecord {
+    pub id: i32,
+    pub device_id: i32,
+    #[schema(example = "Router-01")]
+    pub device_name: String,
+    #[schema(example = "192.168.1.1")]
+    pub ip_address: String,
+    pub location: Option<String>,
+    pub metric_type_id: Option<i32>,
+    #[schema(example = "cpu_usage")]
+    pub metric_name: Option<String>,
+    pub metric_value: f64,
+    pub is_anomaly: bool,
+    pub severity: Severity,
+    /// Что пометило запись аномальной: детектор или `threshold`
+    #[schema(example = "zscore")]
+    pub anomaly_detector: Option<String>,
+    /// Оценка детектора
+    pub anomaly_score: Option<f64>,
+    pub action_description: Option<String>,
+    /// Время измерения у источника
+    pub recorded_at: NaiveDateTime,
+    /// Время приёма сервером
+    pub received_at: Option<NaiveDateTime>,
+}
+
+/// Фильтры и страница выборки телеметрии.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TelemetryQuery {
+    /// Начало интервала по времени измерения (RFC 3339, включительно)
+    pub from: Option<DateTime<Utc>>,
+    /// Конец интервала (RFC 3339, не включительно)
+    pub to: Option<DateTime<Utc>>,
+    pub device_id: Option<i32>,
+    pub device_name: Option<String>,
+    pub metric_type_id: Option<i32>,
+    #[param(example = "cpu_usage")]
+    pub metric_name: Option<String>,
+    /// Только аномалии
+    pub anomaly_only: Option<bool>,
+    /// Подстрока расположения устройства
+    pub location: Option<String>,
+    /// Подстрока action_description
+    pub search: Option<String>,
+    /// Размер страницы (1–1000, по умолчанию 100)
+    pub limit: Option<i64>,
+    /// next_cursor из предыдущего ответа
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TelemetryPage {
+    pub items: Vec<TelemetryRecord>,
+    /// Курсор следующей страницы; `None`, если записей больше нет
+    pub next_cursor: Option<String>,
+}
+
+/// Размер корзины агрегации.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum BucketSize {
+    #[serde(rename = "1m")]
+    Minute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    Hour,
+    #[serde(rename = "1d")]
+    Day,
+}
+
+impl BucketSize {
+    pub fn seconds(self) -> i64 {
+        match self {
+            BucketSize::Minute => 60,
+            BucketSize::FiveMinutes => 300,
+            BucketSize::Hour => 3600,
+            BucketSize::Day => 86400,
+        }
+    }
+
+    /// Значение для `::interval` в Postgres.
+    pub fn pg_interval(self) -> &'static str {
+        match self {
+            BucketSize::Minute => "1 minute",
+            BucketSize::FiveMinutes => "5 minutes",
+            BucketSize::Hour => "1 hour",
+            BucketSize::Day => "1 day",
+        }
+    }
+
+    /// Самый крупный уровень агрегатов, из которого собирается корзина.
+    pub fn rollup_level(self) -> RollupLevel {
+        RollupLevel::ALL
+            .into_iter()
+            .rev()
+            .find(|level| self.seconds() % level.seconds() == 0)
+            .unwrap_or(RollupLevel::Minute)
+    }
+}
+
+/// Уровень таблиц-агрегатов telemetry_rollup_*.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupLevel {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl RollupLevel {
+    pub const ALL: [RollupLevel; 3] = [RollupLevel::Minute, RollupLevel::Hour, RollupLevel::Day];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RollupLevel::Minute => "1m",
+            RollupLevel::Hour => "1h",
+            RollupLevel::Day => "1d",
+        }
+    }
+
+    pub fn table(self) -> &'static str {
+        match self {
+            RollupLevel::Minute => "telemetry_rollup_1m",
+            RollupLevel::Hour => "telemetry_rollup_1h",
+            RollupLevel::Day => "telemetry_rollup_1d",
+        }
+    }
+
+    pub fn seconds(self) -> i64 {
+        match self {
+            RollupLevel::Minute => 60,
+            RollupLevel::Hour => 3600,
+            RollupLevel::Day => 86400,
+        }
+    }
+
+    pub fn pg_interval(self) -> &'static str {
+        match self {
+            RollupLevel::Minute => "1 minute",
+            RollupLevel::Hour => "1 hour",
+            RollupLevel::Day => "1 day",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AggregateQuery {
+    /// Начало интервала (RFC 3339, включительно)
+    pub from: DateTime<Utc>,
+    /// Конец интервала (RFC 3339, не включительно)
+    pub to: DateTime<Utc>,
+    /// Размер корзины: 1m, 5m, 1h или 1d
+    #[param(value_type = String, example = "5m")]
+    pub bucket: BucketSize,
+    pub device_id: Option<i32>,
+    pub device_name: Option<String>,
+    pub metric_type_id: Option<i32>,
+    #[param(example = "cpu_usage")]
+    pub metric_name: Option<String>,
+}
+
+/// Строка агрегата из БД: одна корзина одной пары устройство/метрика.
+#[derive(Debug, sqlx::FromRow)]
+pub struct AggregateRow {
+    pub device_id: i32,
+    pub device_name: String,
+    pub metric_type_id: i32,
+    pub metric_name: String,
+    pub bucket: NaiveDateTime,
+    pub count: i64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub avg: Option<f64>,
+    pub p95: Option<f64>,
+    pub anomaly_count: i64,
+}
+
+/// Значения одной корзины. Для пустой корзины count = 0, остальное `None`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AggregatePoint {
+    /// Начало корзины
+    pub bucket: NaiveDateTime,
+    pub count: i64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub avg: Option<f64>,
+    /// 95-й процентиль значений корзины. Если в ответе `p95_approximate`,
+    /// это максимум p95 вложенных минутных корзин — оценка сверху, а не процентиль
+    pub p95: Option<f64>,
+    pub anomaly_count: i64,
+}
+
+/// Ряд корзин одной метрики одного устройства.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AggregateSeries {
+    pub device_id: i32,
+    pub device_name: String,
+    pub metric_type_id: i32,
+    pub metric_name: String,
+    pub points: Vec<AggregatePoint>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AggregateResponse {
+    pub bucket: BucketSize,
+    /// `true`, если p95 корзин приближённый (корзина 5m собирается из минутных)
+    pub p95_approximate: bool,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub series: Vec<AggregateSeries>,
+}
@@ -0,0 +1,66 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Алгоритм статистического детектора аномалий.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "anomaly_detector", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DetectorKind {
+    /// Отклонение от среднего скользящего окна в стандартных отклонениях
+    Zscore,
+    /// Отклонение от экспоненциально сглаженного среднего
+    Ewma,
+    /// Робастная оценка по медиане и медианному абсолютному отклонению
+    Mad,
+    /// Скорость изменения значения в секунду
+    RateOfChange,
+}
+
+impl DetectorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DetectorKind::Zscore => "zscore",
+            DetectorKind::Ewma => "ewma",
+            DetectorKind::Mad => "mad",
+            DetectorKind::RateOfChange => "rate_of_change",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct DetectorConfig {
+    pub metric_type_id: i32,
+    #[schema(example = "latency_ms")]
+    pub metric_name: String,
+    pub detector: DetectorKind,
+    /// Сколько последних значений пары устройство/метрика учитывается
+    #[schema(example = 60)]
+    pub window_size: i32,
+    #[schema(example = 3.0)]
+    pub threshold: f64,
+    /// Коэффициент сглаживания ewma
+    pub alpha: Option<f64>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DetectorSettings {
+    pub detector: DetectorKind,
+    /// По умолчанию 60
+    #[schema(example = 60)]
+    pub window_size: Option<i32>,
+    /// Предельная оценка: сигмы для zscore/ewma, робастная оценка для mad,
+    /// изменение в секунду для rate_of_change
+    #[schema(example = 3.0)]
+    pub threshold: f64,
+    /// Только для ewma, по умолчанию 0.3
+    #[schema(example = 0.3)]
+    pub alpha: Option<f64>,
+}
+
+/// Полная замена набора детекторов метрики; пустой список отключает их.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateDetectorsRequest {
+    pub detectors: Vec<DetectorSettings>,
+}
@@ -0,0 +1,87 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Сравнение значения с порогом. `gte`/`gt` — верхняя граница,
+/// `lte`/`lt` — нижняя.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "threshold_comparison", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Comparison {
+    /// Значение ≥ порога
+    #[default]
+    Gte,
+    /// Значение > порога
+    Gt,
+    /// Значение ≤ порога
+    Lte,
+    /// Значение < порога
+    Lt,
+}
+
+impl Comparison {
+    pub fn is_lower(self) -> bool {
+        matches!(self, Comparison::Lte | Comparison::Lt)
+    }
+
+    /// Достигло ли значение порога.
+    pub fn reached(self, value: f64, level: f64) -> bool {
+        match self {
+            Comparison::Gte => value >= level,
+            Comparison::Gt => value > level,
+            Comparison::Lte => value <= level,
+            Comparison::Lt => value < level,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct ThresholdView {
+    pub id: i32,
+    pub metric_type_id: i32,
+    #[schema(example = "cpu_usage")]
+    pub metric_name: String,
+    /// Задан для порога устройства
+    pub device_id: Option<i32>,
+    pub device_name: Option<String>,
+    /// Задан для порога расположения
+    pub location: Option<String>,
+    pub comparison: Comparison,
+    #[schema(example = 70.0)]
+    pub warning_level: Option<f64>,
+    #[schema(example = 90.0)]
+    pub critical_level: Option<f64>,
+    /// Имя пользователя, создавшего порог
+    pub created_by: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Тело создания и полной замены порога. Без device_name и location порог
+/// действует для метрики глобально; задать можно только одно из них.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ThresholdRequest {
+    #[schema(example = "bandwidth_usage")]
+    pub metric_name: String,
+    #[schema(example = "router-01")]
+    pub device_name: Option<String>,
+    #[schema(example = "Москва, ЦОД-1")]
+    pub location: Option<String>,
+    /// По умолчанию `gte`
+    #[serde(default)]
+    pub comparison: Comparison,
+    #[schema(example = 10.0)]
+    pub warning_level: Option<f64>,
+    #[schema(example = 0.0)]
+    pub critical_level: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ThresholdQuery {
+    /// Имя метрики
+    pub metric_name: Option<String>,
+    /// Только пороги этого устройства
+    pub device_name: Option<String>,
+    /// Только пороги этого расположения
+    pub location: Option<String>,
+}
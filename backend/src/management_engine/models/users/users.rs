@@ -0,0 +1,75 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Профиль пользователя (`GET /me`).
+#[derive(Debug, Serialize, ToSchema, sqlx::FromRow)]
+pub struct UserProfile {
+    pub id: i32,
+    #[schema(example = "ivanov")]
+    pub username: String,
+    /// `None`, если роль отозвана
+    #[schema(example = "оператор")]
+    pub role_name: Option<String>,
+    #[schema(example = "Иванов Иван Иванович")]
+    pub full_name: Option<String>,
+    #[schema(example = "ivanov@example.com")]
+    pub email: Option<String>,
+    #[schema(example = "+79991234567")]
+    pub phone_number: Option<String>,
+    #[schema(example = "ООО Ромашка")]
+    pub organization: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Частичное изменение профиля: отсутствующие поля не меняются,
+/// пустая строка в phone_number/organization очищает поле.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateProfileRequest {
+    #[schema(example = "Иванов Иван Петрович")]
+    pub full_name: Option<String>,
+    #[schema(example = "ivanov@example.com")]
+    pub email: Option<String>,
+    #[schema(example = "+79991234567")]
+    pub phone_number: Option<String>,
+    #[schema(example = "ООО Ромашка")]
+    pub organization: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    #[schema(example = "Passw0rd")]
+    pub old_password: String,
+    #[schema(example = "N3wPassw0rd")]
+    pub new_password: String,
+}
+
+/// Строка списка пользователей для администратора.
+#[derive(Debug, Serialize, ToSchema, sqlx::FromRow)]
+pub struct UserSummary {
+    pub id: i32,
+    pub username: String,
+    pub role_name: Option<String>,
+    pub full_name: Option<String>,
+    pub email: Option<String>,
+    pub organization: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub disabled_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserListResponse {
+    pub items: Vec<UserSummary>,
+    /// Всего пользователей, подходящих под фильтр
+    pub total: i64,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct UserListQuery {
+    /// Подстрока имени пользователя, ФИО, email или организации
+    pub search: Option<String>,
+    /// Размер страницы (1–200, по умолчанию 50)
+    pub limit: Option<i64>,
+    /// Смещение от начала списка
+    pub offset: Option<i64>,
+}
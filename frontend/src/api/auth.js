@@ -9,7 +9,16 @@ export async function registerUser(data) {
 
   if (!res.ok) {
     const text = await res.text();
-    throw new Error(text);
+    let message = text;
+    try {
+      const body = JSON.parse(text);
+      message = body.errors?.length
+        ? body.errors.map((e) => e.message).join("; ")
+        : body.message ?? text;
+    } catch {
+      // тело ответа не JSON
+    }
+    throw new Error(message);
   }
 
   return res.json();
@@ -26,7 +35,16 @@ export async function loginUser(data) {
 
   if (!res.ok) {
     const text = await res.text();
-    throw new Error(text);
+    let message = text;
+    try {
+      const body = JSON.parse(text);
+      message = body.errors?.length
+        ? body.errors.map((e) => e.message).join("; ")
+        : body.message ?? text;
+    } catch {
+      // тело ответа не JSON
+    }
+    throw new Error(message);
   }
 
   const result = await res.json();
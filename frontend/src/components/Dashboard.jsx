@@ -21,10 +21,12 @@ const Dashboard = () => {
   // ====================== Запрос телеметрии ======================
   const fetchTelemetry = async () => {
     try {
-      const res = await fetch("http://localhost:8080/operator/telemetry");
+      const res = await fetch("http://localhost:8080/operator/telemetry?limit=1000", {
+        headers: { Authorization: `Bearer ${localStorage.getItem("token")}` },
+      });
       if (!res.ok) throw new Error("Ошибка при получении телеметрии");
       const data = await res.json();
-      setTelemetryData(data);
+      setTelemetryData(data.items);
       setLastUpdate(new Date());
     } catch (err) {
       console.error("Ошибка при получении телеметрии:", err);
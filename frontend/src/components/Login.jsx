@@ -17,6 +17,7 @@ export default function Login({ switchToRegister, onLoginSuccess }) {
 
       // Сохраняем токен
       localStorage.setItem("token", data.token);
+      localStorage.setItem("refresh_token", data.refresh_token);
       console.log("✅ Токен сохранён:", data.token);
 
       // Показать модалку успешного входа
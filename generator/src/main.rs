@@ -1,41 +1,33 @@
 use actix_web::{web, App, HttpResponse, HttpServer, Responder};
-use rand::Rng;
-use serde::Serialize;
+use chrono::Utc;
+use rand::RngExt;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use telemetry_protocol::{MetricRef, SCHEMA_VERSION, TelemetryEvent};
 use tokio::time;
-
-#[derive(Serialize, Clone)]
-struct TelemetryEvent {
-    device_name: String,
-    ip_address: String,
-    location: String,
-    metric_name: String,       
-    metric_value: f64,
-    action_description: String,
-}
+use uuid::Uuid;
 
 type SharedEvents = Arc<Mutex<Vec<TelemetryEvent>>>;
 
 fn generate_event() -> TelemetryEvent {
-    let devices = vec![
+    let devices = [
         "Router-01", "Router-05", "Router-22",
         "Switch-02", "Switch-10", "Switch-50",
         "Firewall-03", "Firewall-09", "Firewall-15",
     ];
 
-    let ips = vec![
+    let ips = [
         "192.168.1.1", "192.168.1.2", "192.168.2.10", "192.168.77.1",
         "172.16.0.1", "172.18.5.1", "10.0.0.1", "10.10.10.9", "10.1.15.1",
     ];
 
-    let locations = vec![
+    let locations = [
         "Москва, ЦОД-1", "СПб, Офис", "Казань, Узел A",
         "Екатеринбург, ЦОД", "Новосибирск, ЦОД", "Омск, Узел-7",
         "Москва, Офис Сормово",
     ];
 
-    let metrics = vec![
+    let metrics = [
         "cpu_usage",
         "memory_usage",
         "latency_ms",
@@ -43,20 +35,23 @@ fn generate_event() -> TelemetryEvent {
         "bandwidth_usage",
     ];
 
-    let actions = vec![
+    let actions = [
         "CPU usage spike", "Memory usage high", "Bandwidth usage normal",
         "High latency detected", "Connection reset", "Packet loss detected",
     ];
 
-    let mut rng = rand::thread_rng();
+    let mut rng = rand::rng();
 
     TelemetryEvent {
-        device_name: devices[rng.gen_range(0..devices.len())].to_string(),
-        ip_address: ips[rng.gen_range(0..ips.len())].to_string(),
-        location: locations[rng.gen_range(0..locations.len())].to_string(),
-        metric_name: metrics[rng.gen_range(0..metrics.len())].to_string(),  // ⬅
-        metric_value: rng.gen_range(0.0..100.0),
-        action_description: actions[rng.gen_range(0..actions.len())].to_string(),
+        schema_version: SCHEMA_VERSION,
+        event_id: Some(Uuid::new_v4()),
+        observed_at: Some(Utc::now()),
+        device_name: devices[rng.random_range(0..devices.len())].to_string(),
+        ip_address: ips[rng.random_range(0..ips.len())].to_string(),
+        location: Some(locations[rng.random_range(0..locations.len())].to_string()),
+        metric: MetricRef::name(metrics[rng.random_range(0..metrics.len())]),
+        metric_value: rng.random_range(0.0..100.0),
+        action_description: Some(actions[rng.random_range(0..actions.len())].to_string()),
     }
 }
 
@@ -92,7 +87,7 @@ async fn main() -> std::io::Result<()> {
                 storage.push(event.clone());
             }
 
-            println!("⚡ EVENT: {} [{}]", event.device_name, event.metric_name);
+            println!("⚡ EVENT: {} [{}]", event.device_name, event.metric);
         }
     });
 
@@ -0,0 +1,526 @@
+//! Формат обмена телеметрией между генератором и backend.
+//!
+//! Оба бинарника используют эти типы, поэтому расхождение контракта
+//! становится ошибкой компиляции.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// Текущая версия схемы события. С версии 2 обязателен `observed_at`.
+pub const SCHEMA_VERSION: u32 = 2;
+/// Самая старая версия, которую ещё принимает backend.
+pub const MIN_SUPPORTED_SCHEMA_VERSION: u32 = 1;
+
+pub const MAX_DEVICE_NAME_LEN: usize = 100;
+pub const MAX_IP_ADDRESS_LEN: usize = 45;
+pub const MAX_LOCATION_LEN: usize = 150;
+pub const MAX_METRIC_NAME_LEN: usize = 100;
+/// Значения метрик и порогов хранятся в NUMERIC(12,2): по модулю меньше 10¹⁰.
+pub const MAX_METRIC_VALUE_ABS: f64 = 1e10;
+
+// =========================================================
+// ИДЕНТИФИКАЦИЯ МЕТРИКИ
+// =========================================================
+
+/// Метрика события: по id из `metric_types` или по имени.
+///
+/// В JSON передаётся одно из полей верхнего уровня события:
+/// `"metric_type_id": 3` или `"metric_name": "cpu_usage"`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(untagged)]
+pub enum MetricRef {
+    Id { metric_type_id: i32 },
+    Name { metric_name: String },
+}
+
+impl MetricRef {
+    pub fn name(name: impl Into<String>) -> Self {
+        MetricRef::Name {
+            metric_name: name.into(),
+        }
+    }
+
+    pub fn id(metric_type_id: i32) -> Self {
+        MetricRef::Id { metric_type_id }
+    }
+}
+
+impl std::fmt::Display for MetricRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetricRef::Id { metric_type_id } => write!(f, "#{}", metric_type_id),
+            MetricRef::Name { metric_name } => f.write_str(metric_name),
+        }
+    }
+}
+
+// =========================================================
+// СОБЫТИЕ ТЕЛЕМЕТРИИ
+// =========================================================
+
+/// Одно измерение метрики устройства.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TelemetryEvent {
+    /// Версия схемы. События без поля считаются версией 1.
+    #[serde(default = "first_schema_version")]
+    pub schema_version: u32,
+    /// Идентификатор события у источника. Повтор с тем же id
+    /// не записывается второй раз.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<Uuid>,
+    /// Время измерения у источника (RFC 3339). Обязательно с версии 2;
+    /// для версии 1 используется время приёма сервером.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub observed_at: Option<DateTime<Utc>>,
+    pub device_name: String,
+    pub ip_address: String,
+    pub location: Option<String>,
+    #[serde(flatten)]
+    pub metric: MetricRef,
+    pub metric_value: f64,
+    pub action_description: Option<String>,
+}
+
+fn first_schema_version() -> u32 {
+    1
+}
+
+// =========================================================
+// ВАЛИДАЦИЯ
+// =========================================================
+
+/// Нарушение контракта в отдельном поле события.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub field: &'static str,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(field: &'static str, code: &'static str, message: impl Into<String>) -> Self {
+        ValidationError {
+            field,
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// Имя метрики: строчная латиница, цифры и `_`, начинается с буквы.
+pub fn is_valid_metric_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase())
+        && name.len() <= MAX_METRIC_NAME_LEN
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Помещается ли значение в NUMERIC(12,2) после округления до сотых.
+pub fn is_valid_metric_value(value: f64) -> bool {
+    value.is_finite() && (value.abs() * 100.0).round() < MAX_METRIC_VALUE_ABS * 100.0
+}
+
+impl TelemetryEvent {
+    /// Проверяет событие целиком и возвращает все нарушения.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        if !(MIN_SUPPORTED_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&self.schema_version) {
+            errors.push(ValidationError::new(
+                "schema_version",
+                "unsupported_version",
+                format!(
+                    "Версия схемы {} не поддерживается (допустимо {}–{})",
+                    self.schema_version, MIN_SUPPORTED_SCHEMA_VERSION, SCHEMA_VERSION
+                ),
+            ));
+        }
+
+        if self.schema_version >= 2 && self.observed_at.is_none() {
+            errors.push(ValidationError::new(
+                "observed_at",
+                "required",
+                "Поле observed_at обязательно начиная с версии схемы 2",
+            ));
+        }
+
+        let device_name = self.device_name.trim();
+        if device_name.is_empty() {
+            errors.push(ValidationError::new(
+                "device_name",
+                "required",
+                "Поле device_name обязательно",
+            ));
+        } else if device_name.chars().count() > MAX_DEVICE_NAME_LEN {
+            errors.push(ValidationError::new(
+                "device_name",
+                "too_long",
+                format!("Поле device_name не должно превышать {} символов", MAX_DEVICE_NAME_LEN),
+            ));
+        }
+
+        if self.ip_address.len() > MAX_IP_ADDRESS_LEN
+            || self.ip_address.trim().parse::<IpAddr>().is_err()
+        {
+            errors.push(ValidationError::new(
+                "ip_address",
+                "invalid_format",
+                "Некорректный IP-адрес",
+            ));
+        }
+
+        if self
+            .location
+            .as_deref()
+            .is_some_and(|l| l.chars().count() > MAX_LOCATION_LEN)
+        {
+            errors.push(ValidationError::new(
+                "location",
+                "too_long",
+                format!("Поле location не должно превышать {} символов", MAX_LOCATION_LEN),
+            ));
+        }
+
+        match &self.metric {
+            MetricRef::Id { metric_type_id } if *metric_type_id <= 0 => {
+                errors.push(ValidationError::new(
+                    "metric_type_id",
+                    "invalid_value",
+                    "metric_type_id должен быть положительным",
+                ));
+            }
+            MetricRef::Name { metric_name } if !is_valid_metric_name(metric_name.trim()) => {
+                errors.push(ValidationError::new(
+                    "metric_name",
+                    "invalid_format",
+                    "Имя метрики: строчная латиница, цифры и '_', начинается с буквы",
+                ));
+            }
+            _ => {}
+        }
+
+        if !self.metric_value.is_finite() {
+            errors.push(ValidationError::new(
+                "metric_value",
+                "invalid_value",
+                "metric_value должен быть конечным числом",
+            ));
+        } else if !is_valid_metric_value(self.metric_value) {
+            errors.push(ValidationError::new(
+                "metric_value",
+                "out_of_range",
+                format!("metric_value должен быть по модулю меньше {}", MAX_METRIC_VALUE_ABS),
+            ));
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event() -> TelemetryEvent {
+        TelemetryEvent {
+            schema_version: SCHEMA_VERSION,
+            event_id: None,
+            observed_at: Some("2024-01-01T12:00:00Z".parse().unwrap()),
+            device_name: "Router-01".to_string(),
+            ip_address: "192.168.1.1".to_string(),
+            location: Some("Москва, ЦОД-1".to_string()),
+            metric: MetricRef::name("cpu_usage"),
+            metric_value: 42.5,
+            action_description: None,
+        }
+    }
+
+    fn codes(event: &TelemetryEvent) -> Vec<(&'static str, &'static str)> {
+        match event.validate() {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|e| (e.field, e.code)).collect(),
+        }
+    }
+
+    // =========================================================
+    // СЕРИАЛИЗАЦИЯ
+    // =========================================================
+
+    #[test]
+    fn serializes_flat_event() {
+        let value = serde_json::to_value(event()).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "schema_version": 2,
+                "observed_at": "2024-01-01T12:00:00Z",
+                "device_name": "Router-01",
+                "ip_address": "192.168.1.1",
+                "location": "Москва, ЦОД-1",
+                "metric_name": "cpu_usage",
+                "metric_value": 42.5,
+                "action_description": null,
+            })
+        );
+    }
+
+    #[test]
+    fn serializes_metric_id_and_event_id() {
+        let event_id = Uuid::parse_str("5f0c7a2e-1b3d-4c5e-8f90-123456789abc").unwrap();
+        let value = serde_json::to_value(TelemetryEvent {
+            event_id: Some(event_id),
+            metric: MetricRef::id(3),
+            ..event()
+        })
+        .unwrap();
+
+        assert_eq!(value["event_id"], json!("5f0c7a2e-1b3d-4c5e-8f90-123456789abc"));
+        assert_eq!(value["metric_type_id"], json!(3));
+        assert!(value.get("metric_name").is_none());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let event = event();
+        let text = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<TelemetryEvent>(&text).unwrap(), event);
+    }
+
+    #[test]
+    fn defaults_missing_optional_fields() {
+        let event: TelemetryEvent = serde_json::from_value(json!({
+            "device_name": "Router-01",
+            "ip_address": "10.0.0.1",
+            "metric_type_id": 1,
+            "metric_value": 1.0,
+        }))
+        .unwrap();
+
+        assert_eq!(event.schema_version, 1);
+        assert_eq!(event.event_id, None);
+        assert_eq!(event.observed_at, None);
+        assert_eq!(event.location, None);
+        assert_eq!(event.action_description, None);
+        assert_eq!(event.metric, MetricRef::id(1));
+        assert_eq!(event.validate(), Ok(()));
+    }
+
+    #[test]
+    fn ignores_unknown_fields() {
+        let mut value = serde_json::to_value(event()).unwrap();
+        value["firmware"] = json!("1.2.3");
+
+        let parsed: TelemetryEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, event());
+    }
+
+    #[test]
+    fn rejects_missing_required_fields() {
+        for field in ["device_name", "ip_address", "metric_value", "metric_name"] {
+            let mut value = serde_json::to_value(event()).unwrap();
+            value.as_object_mut().unwrap().remove(field);
+            assert!(
+                serde_json::from_value::<TelemetryEvent>(value).is_err(),
+                "событие без {} принято",
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_field_types() {
+        let mut value = serde_json::to_value(event()).unwrap();
+        value["metric_value"] = json!("42.5");
+        assert!(serde_json::from_value::<TelemetryEvent>(value).is_err());
+
+        let mut value = serde_json::to_value(event()).unwrap();
+        value["device_name"] = json!(42);
+        assert!(serde_json::from_value::<TelemetryEvent>(value).is_err());
+
+        let mut value = serde_json::to_value(event()).unwrap();
+        value["observed_at"] = json!("вчера");
+        assert!(serde_json::from_value::<TelemetryEvent>(value).is_err());
+    }
+
+    #[test]
+    fn prefers_metric_id_when_both_given() {
+        let mut value = serde_json::to_value(event()).unwrap();
+        value["metric_type_id"] = json!(7);
+
+        let parsed: TelemetryEvent = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.metric, MetricRef::id(7));
+    }
+
+    // =========================================================
+    // ВАЛИДАЦИЯ
+    // =========================================================
+
+    #[test]
+    fn accepts_valid_event() {
+        assert_eq!(event().validate(), Ok(()));
+        assert_eq!(
+            TelemetryEvent {
+                schema_version: 1,
+                observed_at: None,
+                ip_address: "2001:db8::1".to_string(),
+                ..event()
+            }
+            .validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_schema_version() {
+        for schema_version in [0, SCHEMA_VERSION + 1] {
+            let event = TelemetryEvent {
+                schema_version,
+                ..event()
+            };
+            assert_eq!(codes(&event), [("schema_version", "unsupported_version")]);
+        }
+    }
+
+    #[test]
+    fn requires_observed_at_from_version_two() {
+        let event = TelemetryEvent {
+            observed_at: None,
+            ..event()
+        };
+        assert_eq!(codes(&event), [("observed_at", "required")]);
+    }
+
+    #[test]
+    fn checks_device_name() {
+        let blank = TelemetryEvent {
+            device_name: "   ".to_string(),
+            ..event()
+        };
+        let long = TelemetryEvent {
+            device_name: "я".repeat(MAX_DEVICE_NAME_LEN + 1),
+            ..event()
+        };
+        let limit = TelemetryEvent {
+            device_name: "я".repeat(MAX_DEVICE_NAME_LEN),
+            ..event()
+        };
+
+        assert_eq!(codes(&blank), [("device_name", "required")]);
+        assert_eq!(codes(&long), [("device_name", "too_long")]);
+        assert_eq!(codes(&limit), []);
+    }
+
+    #[test]
+    fn checks_ip_address() {
+        for ip_address in ["", "999.1.1.1", "router", &"1".repeat(MAX_IP_ADDRESS_LEN + 1)] {
+            let event = TelemetryEvent {
+                ip_address: ip_address.to_string(),
+                ..event()
+            };
+            assert_eq!(codes(&event), [("ip_address", "invalid_format")], "адрес {:?}", ip_address);
+        }
+
+        // Пробелы по краям допускаются
+        let padded = TelemetryEvent {
+            ip_address: " 10.0.0.1 ".to_string(),
+            ..event()
+        };
+        assert_eq!(codes(&padded), []);
+    }
+
+    #[test]
+    fn checks_location_length() {
+        let long = TelemetryEvent {
+            location: Some("л".repeat(MAX_LOCATION_LEN + 1)),
+            ..event()
+        };
+        let limit = TelemetryEvent {
+            location: Some("л".repeat(MAX_LOCATION_LEN)),
+            ..event()
+        };
+
+        assert_eq!(codes(&long), [("location", "too_long")]);
+        assert_eq!(codes(&limit), []);
+    }
+
+    #[test]
+    fn checks_metric_ref() {
+        let zero_id = TelemetryEvent {
+            metric: MetricRef::id(0),
+            ..event()
+        };
+        assert_eq!(codes(&zero_id), [("metric_type_id", "invalid_value")]);
+
+        for name in ["", "CPU", "1cpu", "cpu-usage", "загрузка"] {
+            let event = TelemetryEvent {
+                metric: MetricRef::name(name),
+                ..event()
+            };
+            assert_eq!(codes(&event), [("metric_name", "invalid_format")], "имя {:?}", name);
+        }
+    }
+
+    #[test]
+    fn validates_metric_names() {
+        assert!(is_valid_metric_name("cpu_usage"));
+        assert!(is_valid_metric_name("if2_rx_bytes"));
+        assert!(is_valid_metric_name(&"a".repeat(MAX_METRIC_NAME_LEN)));
+        assert!(!is_valid_metric_name(&"a".repeat(MAX_METRIC_NAME_LEN + 1)));
+        assert!(!is_valid_metric_name("_cpu"));
+    }
+
+    #[test]
+    fn checks_metric_value() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let event = TelemetryEvent {
+                metric_value: value,
+                ..event()
+            };
+            assert_eq!(codes(&event), [("metric_value", "invalid_value")]);
+        }
+        for value in [MAX_METRIC_VALUE_ABS, -MAX_METRIC_VALUE_ABS, 1e12] {
+            let event = TelemetryEvent {
+                metric_value: value,
+                ..event()
+            };
+            assert_eq!(codes(&event), [("metric_value", "out_of_range")]);
+        }
+    }
+
+    #[test]
+    fn metric_value_bound_follows_rounding() {
+        assert!(is_valid_metric_value(9_999_999_999.99));
+        assert!(is_valid_metric_value(-9_999_999_999.99));
+        // Округляется до 10¹⁰ и в NUMERIC(12,2) не помещается
+        assert!(!is_valid_metric_value(9_999_999_999.996));
+        assert!(is_valid_metric_value(0.0));
+    }
+
+    #[test]
+    fn reports_all_errors_at_once() {
+        let event = TelemetryEvent {
+            schema_version: 2,
+            observed_at: None,
+            device_name: String::new(),
+            ip_address: "router".to_string(),
+            metric: MetricRef::id(-1),
+            metric_value: f64::NAN,
+            ..event()
+        };
+        assert_eq!(
+            codes(&event),
+            [
+                ("observed_at", "required"),
+                ("device_name", "required"),
+                ("ip_address", "invalid_format"),
+                ("metric_type_id", "invalid_value"),
+                ("metric_value", "invalid_value"),
+            ]
+        );
+    }
+}